@@ -0,0 +1,51 @@
+// cargo bench --bench occurrences
+use std::hint::black_box;
+
+use brahma::occurrence::next_occurrences;
+use brahma::types::*;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn from() -> DateTime {
+    DateTime::new(2025, 3, 1, 10, 17).unwrap()
+}
+
+fn next_after(c: &mut Criterion) {
+    let cases = [
+        ("daily", Schedule::new().at(9, 30).daily()),
+        ("hourly", Schedule::new().minute(15).hourly()),
+        ("nth_weekday", Schedule::new().every_nth_day(3, Days::SAT)),
+        ("yearly_date", Schedule::new().date(2, 29)),
+    ];
+    for (name, s) in &cases {
+        c.bench_function(&format!("next_after/{}", name), |b| {
+            b.iter(|| s.next_after(black_box(from())))
+        });
+    }
+}
+
+fn enumerate(c: &mut Criterion) {
+    let s = Schedule::new().hourly();
+    let to = DateTime::new(2026, 3, 1, 0, 0).unwrap();
+    c.bench_function("occurrences_between/hourly_year", |b| {
+        b.iter(|| s.occurrences_between(black_box(from()), to).count())
+    });
+}
+
+fn batch(c: &mut Criterion) {
+    let schedules: Vec<_> = (0..1000u32)
+        .map(|i| match i % 4 {
+            0 => Schedule::new().at((i % 24) as u8, 0).daily(),
+            1 => Schedule::new().every_nth_day((i % 4 + 1) as u8, Days::MON),
+            2 => Schedule::new()
+                .day_with_time((i % 28 + 1) as u8, 6, 0)
+                .monthly(),
+            _ => Schedule::new().minute((i % 60) as u8).hourly(),
+        })
+        .collect();
+    c.bench_function("next_occurrences/1000", |b| {
+        b.iter(|| next_occurrences(black_box(&schedules), from()))
+    });
+}
+
+criterion_group!(benches, next_after, enumerate, batch);
+criterion_main!(benches);
@@ -0,0 +1,14 @@
+#![no_main]
+
+use brahma::parse::{to_cron, to_rrule};
+use brahma::types::{DateTime, Schedule};
+use libfuzzer_sys::fuzz_target;
+
+// The format detection in front of the three parsers, as `str::parse` does it.
+fuzz_target!(|input: &str| {
+    if let Ok(s) = input.parse::<Schedule>() {
+        let _ = (to_cron(&s), to_rrule(&s), s.to_string());
+        let from = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        s.upcoming(from).take(3).for_each(drop);
+    }
+});
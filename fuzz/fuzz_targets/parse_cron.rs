@@ -0,0 +1,14 @@
+#![no_main]
+
+use brahma::parse::{from_cron, to_cron, to_rrule};
+use brahma::types::DateTime;
+use libfuzzer_sys::fuzz_target;
+
+// Whatever parses must also render and produce occurrences without panicking.
+fuzz_target!(|input: &str| {
+    if let Ok(s) = from_cron(input) {
+        let _ = (to_cron(&s), to_rrule(&s), s.to_string());
+        let from = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        s.upcoming(from).take(3).for_each(drop);
+    }
+});
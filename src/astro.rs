@@ -0,0 +1,189 @@
+// Sunrise and sunset at a place on Earth, for schedules that follow the sun
+// ("30 minutes before sunset") instead of a fixed time of day.
+//
+// Solar times come from the NOAA sunrise equation and are good to a minute or
+// two away from the poles. They are worked out in UTC and moved onto the wall
+// clock by the location's fixed UTC offset, so a zone with daylight saving
+// needs the offset of the season it is run in.
+use std::time::Duration;
+
+use crate::time::{Date, DateTime, Time};
+
+// Degrees are kept as millionths, about 10 cm, so schedules stay Eq + Hash.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub(crate) latitude_e6: i32,
+    pub(crate) longitude_e6: i32,
+    // Minutes east of UTC.
+    pub(crate) utc_offset: i32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SolarEvent {
+    Sunrise,
+    Sunset,
+}
+
+// A solar event at a location, shifted by a number of minutes (negative for
+// before).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Solar {
+    pub event: SolarEvent,
+    pub location: Location,
+    pub shift: i32,
+}
+
+impl Location {
+    // Latitude north and longitude east are positive. None outside ±90/±180.
+    pub fn new(latitude: f64, longitude: f64) -> Option<Location> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+        Some(Location {
+            latitude_e6: (latitude * 1e6).round() as i32,
+            longitude_e6: (longitude * 1e6).round() as i32,
+            utc_offset: 0,
+        })
+    }
+
+    // The wall clock's offset from UTC in minutes, e.g. 60 for CET.
+    pub fn with_utc_offset(self, minutes: i32) -> Location {
+        Location {
+            utc_offset: minutes,
+            ..self
+        }
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude_e6 as f64 / 1e6
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude_e6 as f64 / 1e6
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        self.latitude_e6.abs() <= 90_000_000 && self.longitude_e6.abs() <= 180_000_000
+    }
+
+    // When `event` happens on `date`, on this location's wall clock. None
+    // during polar day or night.
+    pub fn solar_time(&self, event: SolarEvent, date: Date) -> Option<DateTime> {
+        let (lat, lon) = (self.latitude().to_radians(), self.longitude());
+        let days = date.at(Time::MIDNIGHT).unix().div_euclid(86_400) as f64;
+        // Days from J2000 to local solar noon.
+        let noon = days + UNIX_EPOCH_JD + 0.5 - J2000 + 0.0008 - lon / 360.0;
+        let anomaly = (357.5291 + 0.98560028 * noon)
+            .rem_euclid(360.0)
+            .to_radians();
+        let center = 1.9148 * anomaly.sin()
+            + 0.0200 * (2.0 * anomaly).sin()
+            + 0.0003 * (3.0 * anomaly).sin();
+        let ecliptic = (anomaly.to_degrees() + center + 180.0 + 102.9372)
+            .rem_euclid(360.0)
+            .to_radians();
+        let transit = J2000 + noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic).sin();
+        let declination = (ecliptic.sin() * 23.4397_f64.to_radians().sin()).asin();
+        let cos_hour = ((-0.833_f64).to_radians().sin() - lat.sin() * declination.sin())
+            / (lat.cos() * declination.cos());
+        if !(-1.0..=1.0).contains(&cos_hour) {
+            return None;
+        }
+        let half_day = cos_hour.acos().to_degrees() / 360.0;
+        let at = match event {
+            SolarEvent::Sunrise => transit - half_day,
+            SolarEvent::Sunset => transit + half_day,
+        };
+        let seconds = ((at - UNIX_EPOCH_JD) * 86_400.0).round() as i64;
+        // + 30 rounds to the nearest minute.
+        Some(DateTime::from_unix(seconds + 30).add_minutes(self.utc_offset as i64))
+    }
+}
+
+const J2000: f64 = 2_451_545.0;
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+impl Solar {
+    pub fn sunrise(location: Location) -> Solar {
+        Solar {
+            event: SolarEvent::Sunrise,
+            location,
+            shift: 0,
+        }
+    }
+
+    pub fn sunset(location: Location) -> Solar {
+        Solar {
+            event: SolarEvent::Sunset,
+            location,
+            shift: 0,
+        }
+    }
+
+    // Whole minutes; seconds are dropped.
+    pub fn before(self, d: Duration) -> Solar {
+        Solar {
+            shift: self.shift - (d.as_secs() / 60) as i32,
+            ..self
+        }
+    }
+
+    pub fn after(self, d: Duration) -> Solar {
+        Solar {
+            shift: self.shift + (d.as_secs() / 60) as i32,
+            ..self
+        }
+    }
+
+    // The time this fires on `date`, if the shifted event falls on that day.
+    pub(crate) fn time_on(&self, date: Date) -> Option<Time> {
+        let at = self
+            .location
+            .solar_time(self.event, date)?
+            .add_minutes(self.shift as i64);
+        (at.date() == date).then(|| at.time())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes_apart(a: DateTime, b: DateTime) -> i64 {
+        (a.unix() - b.unix()).abs() / 60
+    }
+
+    #[test]
+    fn london_midsummer() {
+        let london = Location::new(51.5074, -0.1278).unwrap().with_utc_offset(60);
+        let day = Date::new(2025, 6, 21).unwrap();
+        let sunrise = london.solar_time(SolarEvent::Sunrise, day).unwrap();
+        let sunset = london.solar_time(SolarEvent::Sunset, day).unwrap();
+
+        assert!(minutes_apart(sunrise, day.at(Time::new(4, 43).unwrap())) <= 2);
+        assert!(minutes_apart(sunset, day.at(Time::new(21, 21).unwrap())) <= 2);
+    }
+
+    #[test]
+    fn polar_night_has_no_sunrise() {
+        let tromso = Location::new(69.6496, 18.956).unwrap();
+        let day = Date::new(2025, 12, 21).unwrap();
+        assert_eq!(tromso.solar_time(SolarEvent::Sunrise, day), None);
+        assert!(Location::new(91.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn shifts_stay_on_the_day() {
+        let equator = Location::new(0.0, 0.0).unwrap();
+        let day = Date::new(2025, 3, 20).unwrap();
+        let sunset = Solar::sunset(equator).before(Duration::from_secs(1800));
+        let at = sunset.time_on(day).unwrap();
+        assert!((17 * 60 + 35..=17 * 60 + 50).contains(&at.minutes_since_midnight()));
+
+        let late = Solar::sunset(equator).after(Duration::from_secs(8 * 3600));
+        assert_eq!(late.time_on(day), None);
+    }
+}
@@ -0,0 +1,206 @@
+// The `brahma` command-line tool, for checking a schedule from a shell
+// before it goes into a program or a config file:
+//
+//     $ brahma next "every 3rd saturday at 22:00" --count 3
+//     every 3rd Saturday of the month at 22:00
+//       1. Saturday 2025-09-20 22:00
+//       2. Saturday 2025-10-18 22:00
+//       3. Saturday 2025-11-15 22:00
+//     $ brahma explain "0 9 * * MON"
+//     every Monday at 09:00
+//     $ brahma convert --to rrule "0 9 * * MON"
+//     FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0
+//
+// Schedules are read as by `str::parse::<Schedule>()`: cron, RRULE or
+// English. Times are UTC unless `--from` says where to start.
+use std::process::ExitCode;
+
+use brahma::parse::ParseError;
+use brahma::parse::{to_cron, to_rrule};
+use brahma::scheduler::{Clock, SystemClock};
+use brahma::types::{DateTime, Schedule};
+use clap::{Arg, ArgMatches, Command, value_parser};
+
+fn schedule() -> Arg {
+    Arg::new("schedule")
+        .required(true)
+        .help("A cron expression, an RRULE or English, e.g. \"every day at 9:30\"")
+}
+
+fn command() -> Command {
+    Command::new("brahma")
+        .about("Check job schedules from the command line")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("next")
+                .about("List the upcoming occurrences of a schedule")
+                .arg(schedule())
+                .arg(
+                    Arg::new("count")
+                        .short('n')
+                        .long("count")
+                        .value_parser(value_parser!(usize))
+                        .default_value("10")
+                        .help("How many occurrences to list"),
+                )
+                .arg(
+                    Arg::new("from")
+                        .long("from")
+                        .value_name("YYYY-MM-DD HH:MM")
+                        .help("Start from this time instead of now"),
+                ),
+        )
+        .subcommand(
+            Command::new("explain")
+                .about("Describe a schedule in English")
+                .arg(schedule()),
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Write a schedule out in another format")
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .required(true)
+                        .value_parser(["cron", "rrule", "text"])
+                        .help("The format to write"),
+                )
+                .arg(schedule()),
+        )
+}
+
+fn run(matches: &ArgMatches) -> Result<String, String> {
+    match matches.subcommand() {
+        Some(("next", args)) => next(args),
+        Some(("explain", args)) => Ok(format!("{}\n", schedule_arg(args)?)),
+        Some(("convert", args)) => convert(args),
+        _ => unreachable!("clap requires a subcommand"),
+    }
+}
+
+fn next(args: &ArgMatches) -> Result<String, String> {
+    let schedule: Schedule = schedule_arg(args)?;
+    let from = match args.get_one::<String>("from") {
+        Some(from) => from
+            .parse::<DateTime>()
+            .map_err(|_| format!("--from {:?} is not a YYYY-MM-DD HH:MM time", from))?,
+        None => SystemClock.now(),
+    };
+    let count = *args.get_one::<usize>("count").unwrap_or(&10);
+    Ok(schedule.simulate(from, count).to_string())
+}
+
+// Fails for schedules the target format has no way to say, naming what's
+// missing, rather than writing out something close.
+fn convert(args: &ArgMatches) -> Result<String, String> {
+    let schedule = schedule_arg(args)?;
+    let out = match args.get_one::<String>("to").map(String::as_str) {
+        Some("cron") => to_cron(&schedule),
+        Some("rrule") => to_rrule(&schedule),
+        _ => Ok(schedule.to_string()),
+    };
+    out.map(|out| out + "\n")
+        .map_err(|e| format!("can't convert {:?}: {}", schedule.to_string(), e.message()))
+}
+
+fn schedule_arg(args: &ArgMatches) -> Result<Schedule, String> {
+    let text = args
+        .get_one::<String>("schedule")
+        .map_or("", String::as_str);
+    text.parse().map_err(|e: ParseError| e.to_string())
+}
+
+fn main() -> ExitCode {
+    let matches = command().get_matches();
+    match run(&matches) {
+        Ok(out) => {
+            print!("{}", out);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("brahma: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brahma(args: &[&str]) -> Result<String, String> {
+        let matches = command()
+            .try_get_matches_from(std::iter::once("brahma").chain(args.iter().copied()))
+            .map_err(|e| e.to_string())?;
+        run(&matches)
+    }
+
+    #[test]
+    fn next_lists_occurrences() {
+        let out = brahma(&[
+            "next",
+            "every 3rd saturday at 22:00",
+            "--count",
+            "2",
+            "--from",
+            "2025-09-01 00:00",
+        ])
+        .unwrap();
+        assert_eq!(
+            out,
+            "every 3rd Saturday of the month at 22:00\n  \
+             1. Saturday 2025-09-20 22:00\n  \
+             2. Saturday 2025-10-18 22:00\n"
+        );
+        let cron = brahma(&[
+            "next",
+            "30 9 * * *",
+            "-n",
+            "1",
+            "--from",
+            "2025-01-01 10:00",
+        ]);
+        assert!(cron.unwrap().ends_with("1. Thursday 2025-01-02 09:30\n"));
+    }
+
+    #[test]
+    fn bad_input_is_reported() {
+        assert!(
+            brahma(&["next", "every blue moon"])
+                .unwrap_err()
+                .starts_with("invalid schedule")
+        );
+        assert!(
+            brahma(&["next", "daily", "--from", "tomorrow"])
+                .unwrap_err()
+                .contains("--from \"tomorrow\"")
+        );
+        assert!(brahma(&["next", "daily", "--count", "x"]).is_err());
+        assert!(brahma(&[]).is_err());
+    }
+
+    #[test]
+    fn explain_and_convert() {
+        assert_eq!(
+            brahma(&["explain", "0 9 * * MON"]).unwrap(),
+            "every Monday at 09:00\n"
+        );
+        let rrule = brahma(&["convert", "--to", "rrule", "0 9 * * MON"]).unwrap();
+        assert_eq!(rrule, "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0\n");
+        assert_eq!(
+            brahma(&["convert", "--to", "cron", rrule.trim()]).unwrap(),
+            "0 9 * * MON\n"
+        );
+        assert_eq!(
+            brahma(&["convert", "--to", "text", "@daily"]).unwrap(),
+            brahma(&["explain", "@daily"]).unwrap()
+        );
+        assert!(
+            brahma(&["convert", "--to", "cron", "every day at 9:00 except may"])
+                .unwrap_err()
+                .starts_with("can't convert")
+        );
+        assert!(brahma(&["convert", "--to", "ical", "@daily"]).is_err());
+    }
+}
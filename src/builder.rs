@@ -0,0 +1,296 @@
+// Typestate builder for Schedule.
+//
+// The date, time and frequency slots can only be filled once: calling `at`
+// twice is a compile error instead of the "already set, ignoring" log that the
+// lenient setters on Schedule print.
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use crate::time::{is_leap_day_outside_leap_year, is_valid_day_for_month};
+use crate::types::*;
+
+pub struct Unset;
+pub struct Set;
+
+// The builder validates each value as it is given, so `build` only has to
+// move the collected parts into a Schedule and can't fail.
+pub struct ScheduleBuilder<D = Unset, T = Unset, F = Unset> {
+    parts: ScheduleParts,
+    _state: PhantomData<(D, T, F)>,
+}
+
+impl Schedule {
+    pub fn builder() -> ScheduleBuilder {
+        ScheduleBuilder::new()
+    }
+}
+
+impl Default for ScheduleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScheduleBuilder {
+    pub fn new() -> Self {
+        Self {
+            parts: ScheduleParts::default(),
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<D, T, F> ScheduleBuilder<D, T, F> {
+    fn transition<D2, T2, F2>(self) -> ScheduleBuilder<D2, T2, F2> {
+        ScheduleBuilder {
+            parts: self.parts,
+            _state: PhantomData,
+        }
+    }
+
+    pub fn except(mut self, e: Except) -> Self {
+        if self.parts.except.is_none() {
+            self.parts.except = Some(e);
+        } else {
+            eprintln!("Except is already set. Ignoring.");
+        }
+        self
+    }
+
+    pub fn repeat(mut self, n: u32) -> Self {
+        if self.parts.repeat.count().is_none() {
+            self.parts.repeat = self.parts.repeat.with_count(n);
+        } else {
+            eprintln!("Repeat count already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    pub fn until(
+        mut self,
+        d: Option<u8>,
+        m: Option<Month>,
+        h: Option<u8>,
+        min: Option<u8>,
+    ) -> Self {
+        self.parts.repeat = self.parts.repeat.or_until(EndDate {
+            year: None,
+            day: d,
+            month: m,
+            hour: h,
+            minute: min,
+        });
+        self
+    }
+
+    pub fn between(mut self, start: (u8, u8), end: (u8, u8)) -> Self {
+        if self.parts.range.is_some() {
+            eprintln!("Range already set. Ignoring new range.");
+        } else if let Some(window) = Time::new(start.0, start.1).zip(Time::new(end.0, end.1)) {
+            self.parts.range = Some(window);
+        } else {
+            eprintln!("Invalid range: {:?} - {:?}.", start, end);
+        }
+        self
+    }
+
+    pub fn offset_by(mut self, d: Duration) -> Self {
+        self.parts.offset = Some((d.as_secs() / 60) as u32);
+        self
+    }
+
+    pub fn and_run_immediately(mut self) -> Self {
+        self.parts.immediate = true;
+        self
+    }
+
+    pub fn every_nth_occurrence(mut self, n: u32) -> Self {
+        if n == 0 {
+            eprintln!("Invalid stride: {}. Ignoring.", n);
+        } else {
+            self.parts.stride = Some(n);
+        }
+        self
+    }
+
+    pub fn fiscal_year_starting(mut self, start: Month) -> Self {
+        self.parts.fiscal_year = FiscalYear::starting(start);
+        self
+    }
+
+    pub fn on_leap_day(mut self, policy: LeapDay) -> Self {
+        self.parts.leap_day = policy;
+        self
+    }
+
+    pub fn build(self) -> Schedule {
+        Schedule::from_parts(self.parts)
+    }
+}
+
+impl<T, F> ScheduleBuilder<Unset, T, F> {
+    pub fn date(mut self, month: u8, day: u8) -> ScheduleBuilder<Set, T, F> {
+        match Month::from_u8(month) {
+            Some(m)
+                if (1..=31).contains(&day)
+                    && is_valid_day_for_month(m as u8, day)
+                    && self
+                        .parts
+                        .year
+                        .is_none_or(|y| !is_leap_day_outside_leap_year(y, m as u8, day)) =>
+            {
+                self.parts.month = Some(m);
+                self.parts.day = Some(day);
+            }
+            _ => eprintln!("Invalid date: month {} day {}.", month, day),
+        }
+        self.transition()
+    }
+
+    // Checked as a whole before anything is set, so a date that doesn't
+    // exist leaves no stray year behind.
+    pub fn full_date(mut self, year: i32, month: u8, day: u8) -> ScheduleBuilder<Set, T, F> {
+        if Date::new(year, month, day).is_none() {
+            eprintln!("Invalid date: {}-{:02}-{:02}.", year, month, day);
+            return self.transition();
+        }
+        self.parts.year = Some(year);
+        self.date(month, day)
+    }
+
+    pub fn on_day_of_year(mut self, n: u16) -> ScheduleBuilder<Set, T, F> {
+        if (1..=366).contains(&n) {
+            self.parts.day_of_year = Some(n);
+        } else {
+            eprintln!("Invalid day of year: {}. Must be 1–366.", n);
+        }
+        self.transition()
+    }
+
+    pub fn on_fiscal(mut self, period: FiscalPeriod) -> ScheduleBuilder<Set, T, F> {
+        self.parts.fiscal = Some(period);
+        self.transition()
+    }
+
+    pub fn on_day(mut self, day: u8) -> ScheduleBuilder<Set, T, F> {
+        if (1..=31).contains(&day) {
+            self.parts.day = Some(day);
+        } else {
+            eprintln!("Invalid day: {}. Must be 1–31.", day);
+        }
+        self.transition()
+    }
+}
+
+impl<D, F> ScheduleBuilder<D, Unset, F> {
+    pub fn at(mut self, hour: u8, minute: u8) -> ScheduleBuilder<D, Set, F> {
+        if let Some(t) = Time::new(hour, minute) {
+            self.parts.hour = Some(t.hour);
+            self.parts.minute = Some(t.minute);
+        } else {
+            eprintln!("Invalid time: {}:{}.", hour, minute);
+        }
+        self.transition()
+    }
+}
+
+impl<D, T> ScheduleBuilder<D, T, Unset> {
+    pub fn every(mut self, f: FrequencyPattern) -> ScheduleBuilder<D, T, Set> {
+        self.parts.frequency = Some(f);
+        self.transition()
+    }
+
+    pub fn hourly(self) -> ScheduleBuilder<D, T, Set> {
+        self.every(FrequencyPattern::Frequency(Frequency::Hourly))
+    }
+
+    pub fn daily(self) -> ScheduleBuilder<D, T, Set> {
+        self.every(FrequencyPattern::Frequency(Frequency::Daily))
+    }
+
+    pub fn weekly(self) -> ScheduleBuilder<D, T, Set> {
+        self.every(FrequencyPattern::Frequency(Frequency::Weekly))
+    }
+
+    pub fn monthly(self) -> ScheduleBuilder<D, T, Set> {
+        self.every(FrequencyPattern::Frequency(Frequency::Monthly))
+    }
+
+    pub fn every_nth_day(self, n: u8, day: Days) -> ScheduleBuilder<D, T, Set> {
+        self.every(FrequencyPattern::ByDay((Some(n), day)))
+    }
+
+    pub fn every_on_day(self, day: Days) -> ScheduleBuilder<D, T, Set> {
+        self.every(FrequencyPattern::ByDay((None, day)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_matches_fluent_api() {
+        let s = Schedule::builder().on_day(20).at(22, 30).monthly().build();
+
+        assert_eq!(s.spec().day, Some(20));
+        assert_eq!(s.spec().hour, Some(22));
+        assert_eq!(
+            s.frequency(),
+            Some(FrequencyPattern::Frequency(Frequency::Monthly))
+        );
+    }
+
+    #[test]
+    fn builder_slots_in_any_order() {
+        let s = Schedule::builder()
+            .every_nth_day(3, Days::SAT)
+            .except(Except::Month(Month::DEC))
+            .at(10, 0)
+            .build();
+
+        assert_eq!(
+            s.frequency(),
+            Some(FrequencyPattern::ByDay((Some(3), Days::SAT)))
+        );
+        assert_eq!(s.spec().except, Some(Except::Month(Month::DEC)));
+        assert_eq!(s.spec().hour, Some(10));
+    }
+
+    #[test]
+    fn builder_drops_invalid_date() {
+        let s = Schedule::builder().date(2, 31).at(24, 0).build();
+
+        assert_eq!(s.spec().day, None);
+        assert_eq!(s.spec().month, None);
+        assert_eq!(s.spec().hour, None);
+
+        let s = Schedule::builder().full_date(2027, 2, 29).build();
+        assert_eq!(s.spec().year, None);
+        assert_eq!(s.spec().day, None);
+        let s = Schedule::builder().full_date(2028, 2, 29).build();
+        assert_eq!(s.spec().year, Some(2028));
+        assert_eq!(s.spec().day, Some(29));
+    }
+
+    #[test]
+    fn builder_keeps_until_with_repeat() {
+        let s = Schedule::builder()
+            .daily()
+            .repeat(10)
+            .until(Some(3), Some(Month::MAR), None, None)
+            .build();
+
+        let repeat = s.spec().repeat;
+        assert_eq!(repeat.count(), Some(10));
+        assert_eq!(repeat.end_date(), Some(EndDate::on(3, Month::MAR)));
+    }
+
+    #[test]
+    fn default_schedule_is_empty() {
+        let s = Schedule::default();
+        assert_eq!(s.spec().day, None);
+        assert_eq!(s.frequency(), None);
+        assert_eq!(s.spec().repeat.count(), None);
+    }
+}
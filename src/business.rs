@@ -0,0 +1,175 @@
+// Business days: Monday to Friday, less any holidays. A schedule can move
+// the days its pattern picks onto them with `offset_business_days`: a day
+// off rolls forward to the next business day, or back to the one before
+// for the end of a period, and the offset then counts business days on
+// from there. So the 3rd business day of each quarter is the quarter start
+// offset by 2, and the last but one before it ends the quarter end offset
+// by -1. The last business day of the month, for payroll and invoicing,
+// rolls back from the 31st clamped to the month's end.
+use crate::calendar::weekday_of;
+use crate::time::{Date, DateTime, is_valid_day_for_month};
+use crate::types::*;
+
+// Far enough to get past any run of days off.
+const SEARCH_DAYS: i64 = 366;
+
+pub fn is_business_day(date: Date) -> bool {
+    !matches!(weekday_of(date), Days::SAT | Days::SUN)
+}
+
+// Days off on top of weekends: dates that come every year, like Dec 25,
+// and dates in one year only, like a moved bank holiday. Both kept sorted.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Holidays {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) yearly: Vec<(Month, u8)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) dates: Vec<Date>,
+}
+
+impl Holidays {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Feb 29 only counts in leap years.
+    pub fn every_year(mut self, month: Month, day: u8) -> Self {
+        if day == 0 || !is_valid_day_for_month(month as u8, day) {
+            eprintln!("Invalid day {} for month {:?}. Ignoring.", day, month);
+            return self;
+        }
+        self.yearly.push((month, day));
+        self.sorted()
+    }
+
+    pub fn on(mut self, date: Date) -> Self {
+        self.dates.push(date);
+        self.sorted()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.yearly.is_empty() && self.dates.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.yearly.len() + self.dates.len()
+    }
+
+    pub fn contains(&self, date: Date) -> bool {
+        self.dates.binary_search(&date).is_ok()
+            || self
+                .yearly
+                .iter()
+                .any(|(m, d)| (*m as u8 + 1, *d) == (date.month, date.day))
+    }
+
+    pub fn is_business_day(&self, date: Date) -> bool {
+        is_business_day(date) && !self.contains(date)
+    }
+
+    // Whether every yearly date exists.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.yearly
+            .iter()
+            .all(|(m, d)| *d > 0 && is_valid_day_for_month(*m as u8, *d))
+    }
+
+    pub(crate) fn sorted(mut self) -> Self {
+        self.yearly.sort_by_key(|(m, d)| (*m as u8, *d));
+        self.yearly.dedup();
+        self.dates.sort();
+        self.dates.dedup();
+        self
+    }
+
+    // The nearest business day strictly after `date`, or before it for a
+    // negative `step`.
+    fn next_business_day(&self, date: Date, step: i64) -> Option<Date> {
+        (1..=SEARCH_DAYS)
+            .map(|n| date.add_days(n * step))
+            .find(|d| self.is_business_day(*d))
+    }
+}
+
+impl Schedule {
+    // Whether days off roll back, as from the end of a period, rather than
+    // forward.
+    pub(crate) fn rolls_back(&self) -> bool {
+        matches!(
+            self.fiscal,
+            Some(FiscalPeriod::QuarterEnd | FiscalPeriod::YearEnd)
+        ) || self.on_month_end()
+    }
+
+    // Whether the day is the 31st clamped to the end of shorter months.
+    pub(crate) fn on_month_end(&self) -> bool {
+        self.month_step.is_some() && self.day == Some(31)
+    }
+
+    // Whether `date` is a day the pattern picks once rolled onto a business
+    // day and offset by `n`. Walks back from `date` to the business day it
+    // was rolled to, then checks every day that rolls onto that one.
+    pub(crate) fn business_day_matches(&self, date: Date, n: i8) -> bool {
+        let holidays = &self.holidays;
+        if !holidays.is_business_day(date) {
+            return false;
+        }
+        let step = -(n.signum() as i64);
+        let mut rolled = Some(date);
+        for _ in 0..n.unsigned_abs() {
+            rolled = rolled.and_then(|d| holidays.next_business_day(d, step));
+        }
+        let Some(rolled) = rolled else {
+            return false;
+        };
+        let back = self.rolls_back();
+        let mut day = rolled;
+        loop {
+            let at = DateTime::new(day.year, day.month, day.day, 0, 0);
+            if at.is_some_and(|at| self.pattern_matches(&at, day.weekday())) {
+                return true;
+            }
+            day = day.add_days(if back { 1 } else { -1 });
+            if holidays.is_business_day(day) || rolled.days_until(day).abs() > SEARCH_DAYS {
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn weekends_and_holidays_are_off() {
+        assert!(is_business_day(date(2025, 1, 3)));
+        assert!(!is_business_day(date(2025, 1, 4)));
+        let none = Holidays::new();
+        assert_eq!(
+            none.next_business_day(date(2025, 1, 3), 1),
+            Some(date(2025, 1, 6))
+        );
+        assert_eq!(
+            none.next_business_day(date(2025, 1, 6), -1),
+            Some(date(2025, 1, 3))
+        );
+
+        let holidays = Holidays::new()
+            .on(date(2025, 12, 26))
+            .every_year(Month::DEC, 25)
+            .every_year(Month::APR, 31);
+        assert_eq!(holidays.len(), 2);
+        assert!(!holidays.is_business_day(date(2030, 12, 25)));
+        assert!(holidays.is_business_day(date(2030, 12, 26)));
+        assert_eq!(
+            holidays.next_business_day(date(2025, 12, 24), 1),
+            Some(date(2025, 12, 29))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+// Proleptic Gregorian calendar arithmetic, the same the occurrence engine
+// uses, for callers writing their own rules on top of Date.
+//
+// Months are 1-based (1 = January) throughout, as in Date.
+use std::ops::{Add, Neg, Sub};
+
+use crate::time::{Date, DateTime, weekday};
+pub use crate::time::{days_in_month, is_leap_year, is_valid_day_of_year};
+use crate::types::Days;
+
+pub fn days_in_year(year: i32) -> u16 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+pub fn weekday_of(date: Date) -> Days {
+    weekday(date.year, date.month, date.day)
+}
+
+// The `n`th (1-based) `day` of a month, e.g. the 3rd Saturday. None if the
+// month has fewer, or for a month outside 1–12.
+pub fn nth_weekday_of_month(year: i32, month: u8, n: u8, day: Days) -> Option<Date> {
+    let first = Date::new(year, month, 1)?;
+    let ahead = (day as i64 - weekday_of(first) as i64).rem_euclid(7);
+    let date = first.add_days(ahead + (n as i64 - 1) * 7);
+    (n >= 1 && date.month == month).then_some(date)
+}
+
+// The last `day` of a month, e.g. the last Friday.
+pub fn last_weekday_of_month(year: i32, month: u8, day: Days) -> Option<Date> {
+    let last = Date::new(year, month, days_in_month(year, month))?;
+    let back = (weekday_of(last) as i64 - day as i64).rem_euclid(7);
+    Some(last.add_days(-back))
+}
+
+// A length of time counted on the calendar: "1 month" is a month whatever
+// its length, and "2 weeks" 14 days. Added to a date-time, months go first
+// (clamping the day to the end of a shorter month, so Jan 31 plus a month is
+// Feb 28), then days, then minutes.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub months: i64,
+    pub days: i64,
+    pub minutes: i64,
+}
+
+impl Span {
+    pub fn years(n: i64) -> Span {
+        Span::months(n * 12)
+    }
+
+    pub fn months(n: i64) -> Span {
+        Span {
+            months: n,
+            ..Span::default()
+        }
+    }
+
+    pub fn weeks(n: i64) -> Span {
+        Span::days(n * 7)
+    }
+
+    pub fn days(n: i64) -> Span {
+        Span {
+            days: n,
+            ..Span::default()
+        }
+    }
+
+    pub fn hours(n: i64) -> Span {
+        Span::minutes(n * 60)
+    }
+
+    pub fn minutes(n: i64) -> Span {
+        Span {
+            minutes: n,
+            ..Span::default()
+        }
+    }
+}
+
+impl Add for Span {
+    type Output = Span;
+
+    fn add(self, other: Span) -> Span {
+        Span {
+            months: self.months + other.months,
+            days: self.days + other.days,
+            minutes: self.minutes + other.minutes,
+        }
+    }
+}
+
+impl Neg for Span {
+    type Output = Span;
+
+    fn neg(self) -> Span {
+        Span {
+            months: -self.months,
+            days: -self.days,
+            minutes: -self.minutes,
+        }
+    }
+}
+
+impl Add<Span> for DateTime {
+    type Output = DateTime;
+
+    fn add(self, span: Span) -> DateTime {
+        self.add_months(span.months)
+            .add_days(span.days)
+            .add_minutes(span.minutes)
+    }
+}
+
+// Subtracting applies the negated span, months first, so it doesn't always
+// undo an addition: Mar 31 minus a month is Feb 28, plus a month Mar 28.
+impl Sub<Span> for DateTime {
+    type Output = DateTime;
+
+    fn sub(self, span: Span) -> DateTime {
+        self + -span
+    }
+}
+
+// The ISO 8601 week `date` falls in, as (year, week 1 to 53). Weeks start on
+// Monday and week 1 holds the year's first Thursday, so Dec 29 can already
+// be in week 1 of the next year and Jan 3 still in week 53 of the last.
+pub fn iso_week(date: Date) -> (i32, u8) {
+    let from_monday = (weekday_of(date) as i64 + 6) % 7;
+    let thursday = date.add_days(3 - from_monday);
+    (thursday.year, ((thursday.ordinal() - 1) / 7 + 1) as u8)
+}
+
+// Which occurrence of its weekday in the month `date` is, 1 to 5.
+pub fn weekday_ordinal(date: Date) -> u8 {
+    (date.day - 1) / 7 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn weekdays_in_a_month() {
+        // September 2025 starts on a Monday.
+        assert_eq!(weekday_of(date(2025, 9, 1)), Days::MON);
+        assert_eq!(
+            nth_weekday_of_month(2025, 9, 3, Days::SAT),
+            Some(date(2025, 9, 20))
+        );
+        assert_eq!(
+            nth_weekday_of_month(2025, 9, 1, Days::MON),
+            Some(date(2025, 9, 1))
+        );
+        assert_eq!(nth_weekday_of_month(2025, 9, 5, Days::WED), None);
+        assert_eq!(nth_weekday_of_month(2025, 9, 0, Days::WED), None);
+        assert_eq!(nth_weekday_of_month(2025, 13, 1, Days::WED), None);
+        assert_eq!(
+            last_weekday_of_month(2025, 9, Days::TUE),
+            Some(date(2025, 9, 30))
+        );
+        assert_eq!(
+            last_weekday_of_month(2025, 2, Days::FRI),
+            Some(date(2025, 2, 28))
+        );
+        assert_eq!(weekday_ordinal(date(2025, 9, 20)), 3);
+    }
+
+    #[test]
+    fn iso_weeks() {
+        assert_eq!(iso_week(date(2025, 1, 1)), (2025, 1));
+        assert_eq!(iso_week(date(2024, 12, 30)), (2025, 1));
+        assert_eq!(iso_week(date(2021, 1, 3)), (2020, 53));
+        assert_eq!(iso_week(date(2025, 9, 21)), (2025, 38));
+        assert_eq!(iso_week(date(2025, 9, 22)), (2025, 39));
+    }
+
+    #[test]
+    fn spans_clamp_to_the_end_of_the_month() {
+        let at = |y, m, d| DateTime::new(y, m, d, 9, 30).unwrap();
+        assert_eq!(at(2025, 1, 31) + Span::months(1), at(2025, 2, 28));
+        assert_eq!(at(2024, 1, 31) + Span::months(1), at(2024, 2, 29));
+        assert_eq!(at(2024, 2, 29) + Span::years(1), at(2025, 2, 28));
+        assert_eq!(at(2025, 3, 31) - Span::months(1), at(2025, 2, 28));
+        assert_eq!(at(2025, 12, 20) + Span::weeks(2), at(2026, 1, 3));
+        assert_eq!(at(2025, 1, 1) - Span::months(13), at(2023, 12, 1));
+        assert_eq!(
+            at(2025, 1, 31) + (Span::months(1) + Span::days(1) + Span::hours(15)),
+            DateTime::new(2025, 3, 2, 0, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn month_and_year_lengths() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_year(2000), 366);
+        assert_eq!(days_in_year(2025), 365);
+    }
+}
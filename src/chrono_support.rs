@@ -0,0 +1,83 @@
+// Conversions from chrono date-times, for building one-shot schedules without
+// decomposing the date at every call site.
+use chrono::{Datelike, NaiveDateTime, TimeZone, Timelike};
+
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+// Seconds and below are dropped.
+impl From<NaiveDateTime> for DateTime {
+    fn from(dt: NaiveDateTime) -> Self {
+        DateTime {
+            year: dt.year(),
+            month: dt.month() as u8,
+            day: dt.day() as u8,
+            hour: dt.hour() as u8,
+            minute: dt.minute() as u8,
+        }
+    }
+}
+
+impl From<NaiveDateTime> for Schedule {
+    fn from(dt: NaiveDateTime) -> Self {
+        Schedule::from_datetime(dt.into())
+    }
+}
+
+// Uses the wall-clock time in the date-time's own zone.
+impl<Tz: TimeZone> From<chrono::DateTime<Tz>> for Schedule {
+    fn from(dt: chrono::DateTime<Tz>) -> Self {
+        Schedule::from(dt.naive_local())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate};
+
+    #[test]
+    fn naive_datetime_as_an_end_bound() {
+        let end = NaiveDate::from_ymd_opt(2027, 3, 3)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let s = Schedule::new().daily().repeat(1000).until_datetime(end);
+        let last = s.upcoming(DateTime::new(2027, 3, 1, 0, 0).unwrap()).last();
+
+        assert_eq!(last, DateTime::new(2027, 3, 3, 0, 0));
+    }
+
+    #[test]
+    fn naive_datetime_to_one_shot() {
+        let dt = NaiveDate::from_ymd_opt(2025, 9, 20)
+            .unwrap()
+            .and_hms_opt(22, 0, 45)
+            .unwrap();
+        let s = Schedule::from(dt);
+
+        assert_eq!(s.spec().year, Some(2025));
+        assert_eq!(s.spec().day, Some(20));
+        assert_eq!(s.spec().hour, Some(22));
+        assert_eq!(s.spec().minute, Some(0));
+    }
+
+    #[test]
+    fn zoned_datetime_uses_local_wall_clock() {
+        let tz = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        let dt = tz.with_ymd_and_hms(2025, 9, 20, 22, 30, 0).unwrap();
+        let s = Schedule::from(dt);
+
+        assert_eq!(s.spec().hour, Some(22));
+        assert_eq!(s.spec().minute, Some(30));
+    }
+
+    #[test]
+    fn negative_year_is_kept() {
+        let dt = NaiveDate::from_ymd_opt(-50, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_eq!(Schedule::from(dt).spec().year, Some(-50));
+    }
+}
@@ -0,0 +1,220 @@
+// Registration shaped like tokio-cron-scheduler's, so code written as
+//
+//     let sched = JobScheduler::new()?;
+//     sched.add(Job::new("0 0 9 * * *", |ctx| ...)?)?;
+//     sched.start()?;
+//
+// moves over by dropping the `.await`s. Jobs get a generated id instead of a
+// Uuid, and their closure a JobContext instead of the id and scheduler.
+// Expressions are five-field cron, or six with a leading seconds field of 0;
+// anything else FromStr accepts works too.
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use crate::parse::{ParseError, from_cron};
+use crate::scheduler::{CancellationToken, Clock, JobContext, JobError, Runtime, SystemClock};
+use crate::types::Schedule;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobSchedulerError {
+    ParseSchedule(ParseError),
+    NotFound(String),
+    AlreadyStarted,
+    Job(JobError),
+}
+
+impl fmt::Display for JobSchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobSchedulerError::ParseSchedule(e) => write!(f, "invalid schedule: {}", e),
+            JobSchedulerError::NotFound(id) => write!(f, "no job {}", id),
+            JobSchedulerError::AlreadyStarted => write!(f, "scheduler already started"),
+            JobSchedulerError::Job(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for JobSchedulerError {}
+
+impl From<ParseError> for JobSchedulerError {
+    fn from(e: ParseError) -> Self {
+        JobSchedulerError::ParseSchedule(e)
+    }
+}
+
+impl From<JobError> for JobSchedulerError {
+    fn from(e: JobError) -> Self {
+        JobSchedulerError::Job(e)
+    }
+}
+
+pub struct Job {
+    schedule: Schedule,
+    task: Box<dyn FnMut(&JobContext) + Send>,
+}
+
+impl Job {
+    pub fn new(
+        expr: &str,
+        task: impl FnMut(&JobContext) + Send + 'static,
+    ) -> Result<Self, JobSchedulerError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let schedule = match fields[..] {
+            ["0", ref rest @ ..] if rest.len() == 5 => from_cron(&rest.join(" "))?,
+            [_, ref rest @ ..] if rest.len() == 5 => {
+                return Err(ParseError::new("only second 0 is supported").into());
+            }
+            _ => expr.parse()?,
+        };
+        Ok(Self::with_schedule(schedule, task))
+    }
+
+    pub fn new_one_shot(delay: Duration, task: impl FnMut(&JobContext) + Send + 'static) -> Self {
+        Self::with_schedule(Schedule::after(delay), task)
+    }
+
+    pub fn with_schedule(
+        schedule: Schedule,
+        task: impl FnMut(&JobContext) + Send + 'static,
+    ) -> Self {
+        Self {
+            schedule,
+            task: Box::new(task),
+        }
+    }
+}
+
+impl fmt::Debug for Job {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Job")
+            .field("schedule", &self.schedule)
+            .finish()
+    }
+}
+
+// Cheap to clone; clones drive the same jobs.
+#[derive(Debug, Clone)]
+pub struct JobScheduler {
+    runtime: Arc<Mutex<Runtime>>,
+    // The runtime's, reachable while a job holds the lock.
+    cancel: CancellationToken,
+    next_id: Arc<AtomicU64>,
+    started: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+}
+
+impl JobScheduler {
+    pub fn new() -> Result<Self, JobSchedulerError> {
+        Ok(Self::with_clock(SystemClock))
+    }
+
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        let runtime = Runtime::new(clock);
+        Self {
+            cancel: runtime.cancellation_token(),
+            runtime: Arc::new(Mutex::new(runtime)),
+            next_id: Arc::new(AtomicU64::new(1)),
+            started: Arc::new(AtomicBool::new(false)),
+            stop: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    // The id to remove the job by later.
+    pub fn add(&self, job: Job) -> Result<String, JobSchedulerError> {
+        let id = format!("job-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let id = self.lock().add(id, job.schedule, job.task)?;
+        Ok(id.to_string())
+    }
+
+    pub fn remove(&self, id: &str) -> Result<(), JobSchedulerError> {
+        match self.lock().remove(id) {
+            Some(_) => Ok(()),
+            None => Err(JobSchedulerError::NotFound(id.to_string())),
+        }
+    }
+
+    // Runs whatever is due now on the calling thread, for hosts that would
+    // rather not have start's background thread.
+    pub fn tick(&self) -> usize {
+        self.lock().run_pending()
+    }
+
+    // Polls twice a second on a background thread until shutdown; jobs run
+    // on that thread.
+    pub fn start(&self) -> Result<(), JobSchedulerError> {
+        if self.started.swap(true, Ordering::AcqRel) {
+            return Err(JobSchedulerError::AlreadyStarted);
+        }
+        let sched = self.clone();
+        thread::spawn(move || {
+            while !sched.stop.load(Ordering::Acquire) {
+                sched.tick();
+                thread::sleep(Duration::from_millis(500));
+            }
+        });
+        Ok(())
+    }
+
+    // A job still running is told through its JobContext's token first.
+    pub fn shutdown(&self) -> Result<(), JobSchedulerError> {
+        self.stop.store(true, Ordering::Release);
+        self.cancel.cancel();
+        self.lock().shutdown();
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Runtime> {
+        self.runtime.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::ManualClock;
+    use crate::time::DateTime;
+
+    #[test]
+    fn tokio_cron_style_registration() {
+        let clock = ManualClock::new(DateTime::new(2025, 1, 1, 0, 0).unwrap());
+        let sched = JobScheduler::with_clock(clock.clone());
+        let runs = Arc::new(AtomicU64::new(0));
+        let count = Arc::clone(&runs);
+        let id = sched
+            .add(
+                Job::new("0 0 9 * * *", move |_| {
+                    count.fetch_add(1, Ordering::Relaxed);
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        sched
+            .add(Job::new_one_shot(Duration::from_secs(600), |_| {}))
+            .unwrap();
+
+        clock.advance(24 * 60);
+        assert_eq!(sched.tick(), 2);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+        assert_eq!(sched.remove(&id), Ok(()));
+        assert_eq!(
+            sched.remove(&id),
+            Err(JobSchedulerError::NotFound(id.clone()))
+        );
+    }
+
+    #[test]
+    fn expressions() {
+        assert_eq!(
+            Job::new("0 30 22 20 * *", |_| {}).unwrap().schedule,
+            Schedule::new().day_with_time(20, 22, 30).monthly()
+        );
+        assert_eq!(
+            Job::new("30 22 20 * *", |_| {}).unwrap().schedule,
+            Schedule::new().day_with_time(20, 22, 30).monthly()
+        );
+        assert!(Job::new("1/10 * * * * *", |_| {}).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// A Schedule with its expanded occurrence plan and last answer kept around,
+// for callers that ask the same schedule for its next occurrence over and
+// over. Schedules are values, so a changed schedule is a new compile.
+use std::cell::Cell;
+
+use crate::occurrence::{Occurrences, Plan};
+use crate::recurrence::Recurrence;
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+#[derive(Debug, Clone)]
+pub struct CompiledSchedule {
+    schedule: Schedule,
+    plan: Plan,
+    // The last next_after query and its answer.
+    last: Cell<Option<(DateTime, Option<DateTime>)>>,
+}
+
+impl Schedule {
+    pub fn compile(self) -> CompiledSchedule {
+        CompiledSchedule {
+            plan: self.plan(),
+            schedule: self,
+            last: Cell::new(None),
+        }
+    }
+}
+
+impl CompiledSchedule {
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    pub fn into_schedule(self) -> Schedule {
+        self.schedule
+    }
+
+    pub fn upcoming(&self, from: DateTime) -> Occurrences<'_> {
+        self.schedule
+            .resume_with(self.plan, self.schedule.start(from))
+    }
+
+    pub fn occurrences_between(
+        &self,
+        from: DateTime,
+        to: DateTime,
+    ) -> impl Iterator<Item = DateTime> + '_ {
+        self.upcoming(from).take_while(move |at| *at < to)
+    }
+
+    // Nothing fires strictly between a previous query and its answer, so any
+    // query in that span gets the same answer without another search.
+    pub fn next_after(&self, after: DateTime) -> Option<DateTime> {
+        // Relative one-shots and immediate runs are anchored on the query.
+        let anchored = self.schedule.delay.is_some() || self.schedule.immediate;
+        if let Some((prev, next)) = self.last.get().filter(|_| !anchored) {
+            let hit = match next {
+                Some(next) => prev <= after && after < next,
+                // Until bounds and repeat counts move with the query, so an
+                // exhausted answer only carries over for unbounded schedules.
+                None => prev <= after && self.schedule.repeat.is_forever(),
+            };
+            if hit {
+                return next;
+            }
+        }
+        let next = self.upcoming(after.next_minute()).next();
+        self.last.set(Some((after, next)));
+        next
+    }
+}
+
+impl From<Schedule> for CompiledSchedule {
+    fn from(s: Schedule) -> Self {
+        s.compile()
+    }
+}
+
+impl Recurrence for CompiledSchedule {
+    fn upcoming(&self, from: DateTime) -> Box<dyn Iterator<Item = DateTime> + '_> {
+        Box::new(CompiledSchedule::upcoming(self, from))
+    }
+
+    fn next_after(&self, after: DateTime) -> Option<DateTime> {
+        CompiledSchedule::next_after(self, after)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Days, Month};
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn matches_the_uncompiled_schedule() {
+        let s = Schedule::new()
+            .at(22, 0)
+            .every_nth_day(3, Days::SAT)
+            .except_on_month(Month::OCT);
+        let from = dt(2025, 1, 1, 0, 0);
+        let plain: Vec<_> = s.upcoming(from).take(12).collect();
+        let compiled: Vec<_> = s.compile().upcoming(from).take(12).collect();
+
+        assert_eq!(plain, compiled);
+    }
+
+    #[test]
+    fn repeated_queries_reuse_the_last_answer() {
+        let c = Schedule::new().at(9, 0).daily().compile();
+        let next = c.next_after(dt(2025, 1, 1, 10, 0));
+        assert_eq!(next, Some(dt(2025, 1, 2, 9, 0)));
+        assert_eq!(c.last.get(), Some((dt(2025, 1, 1, 10, 0), next)));
+
+        // Inside the cached span: answered without moving the cache.
+        assert_eq!(c.next_after(dt(2025, 1, 2, 8, 0)), next);
+        assert_eq!(c.last.get(), Some((dt(2025, 1, 1, 10, 0), next)));
+
+        // Past it: searched again.
+        assert_eq!(
+            c.next_after(dt(2025, 1, 2, 9, 0)),
+            Some(dt(2025, 1, 3, 9, 0))
+        );
+    }
+
+    #[test]
+    fn month_only_schedules_skip_whole_months() {
+        let c = Schedule::new().month(12).day(25).compile();
+        assert_eq!(
+            c.next_after(dt(2025, 1, 1, 0, 0)),
+            Some(dt(2025, 12, 25, 0, 0))
+        );
+    }
+}
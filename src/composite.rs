@@ -0,0 +1,265 @@
+// Schedules combined into a single occurrence stream.
+use std::iter::Peekable;
+
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+type Stream<'a> = Box<dyn Iterator<Item = DateTime> + 'a>;
+
+// How far past the starting instant combinators keep looking for a match.
+const HORIZON_YEARS: i32 = 10;
+
+// Single is the common case, and boxing it would cost every plain schedule
+// an allocation to save space only in the combined ones.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CompositeSchedule {
+    Single(Schedule),
+    // Fires whenever either side does.
+    Union(Box<CompositeSchedule>, Box<CompositeSchedule>),
+    // Fires only at instants both sides fire at.
+    Intersection(Box<CompositeSchedule>, Box<CompositeSchedule>),
+    // Fires when the left side does, unless the right side fires too.
+    Difference(Box<CompositeSchedule>, Box<CompositeSchedule>),
+}
+
+impl From<Schedule> for CompositeSchedule {
+    fn from(s: Schedule) -> Self {
+        CompositeSchedule::Single(s)
+    }
+}
+
+impl Schedule {
+    pub fn or(self, other: impl Into<CompositeSchedule>) -> CompositeSchedule {
+        CompositeSchedule::from(self).or(other)
+    }
+
+    pub fn and(self, other: impl Into<CompositeSchedule>) -> CompositeSchedule {
+        CompositeSchedule::from(self).and(other)
+    }
+
+    pub fn minus(self, other: impl Into<CompositeSchedule>) -> CompositeSchedule {
+        CompositeSchedule::from(self).minus(other)
+    }
+}
+
+impl CompositeSchedule {
+    pub fn or(self, other: impl Into<CompositeSchedule>) -> CompositeSchedule {
+        CompositeSchedule::Union(Box::new(self), Box::new(other.into()))
+    }
+
+    pub fn and(self, other: impl Into<CompositeSchedule>) -> CompositeSchedule {
+        CompositeSchedule::Intersection(Box::new(self), Box::new(other.into()))
+    }
+
+    pub fn minus(self, other: impl Into<CompositeSchedule>) -> CompositeSchedule {
+        CompositeSchedule::Difference(Box::new(self), Box::new(other.into()))
+    }
+
+    // Each side keeps its own repeat/until bounds; the streams are merged in
+    // time order and an instant both sides fire at is reported once.
+    pub fn upcoming(&self, from: DateTime) -> Stream<'_> {
+        match self {
+            CompositeSchedule::Single(s) => Box::new(s.upcoming(from)),
+            CompositeSchedule::Union(a, b) => Box::new(Union {
+                a: a.upcoming(from).peekable(),
+                b: b.upcoming(from).peekable(),
+            }),
+            CompositeSchedule::Intersection(a, b) => Box::new(Intersection {
+                a: a.upcoming(from).peekable(),
+                b: b.upcoming(from).peekable(),
+                horizon: DateTime {
+                    year: from.year + HORIZON_YEARS,
+                    ..from
+                },
+            }),
+            CompositeSchedule::Difference(a, b) => Box::new(Difference {
+                a: a.upcoming(from),
+                b: b.upcoming(from).peekable(),
+            }),
+        }
+    }
+
+    pub fn next_after(&self, after: DateTime) -> Option<DateTime> {
+        self.upcoming(after.next_minute()).next()
+    }
+}
+
+struct Union<'a> {
+    a: Peekable<Stream<'a>>,
+    b: Peekable<Stream<'a>>,
+}
+
+impl Iterator for Union<'_> {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(x), Some(y)) if x < y => self.a.next(),
+            (Some(x), Some(y)) if y < x => self.b.next(),
+            (Some(_), Some(_)) => {
+                self.b.next();
+                self.a.next()
+            }
+            (Some(_), None) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+}
+
+struct Intersection<'a> {
+    a: Peekable<Stream<'a>>,
+    b: Peekable<Stream<'a>>,
+    // Two unbounded streams that never meet would otherwise be walked forever.
+    horizon: DateTime,
+}
+
+impl Iterator for Intersection<'_> {
+    type Item = DateTime;
+
+    // Advances whichever side is behind until both agree on an instant.
+    fn next(&mut self) -> Option<DateTime> {
+        loop {
+            let (x, y) = (*self.a.peek()?, *self.b.peek()?);
+            if x.max(y) > self.horizon {
+                return None;
+            }
+            if x < y {
+                self.a.next();
+            } else if y < x {
+                self.b.next();
+            } else {
+                self.b.next();
+                return self.a.next();
+            }
+        }
+    }
+}
+
+struct Difference<'a> {
+    a: Stream<'a>,
+    b: Peekable<Stream<'a>>,
+}
+
+impl Iterator for Difference<'_> {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        for x in self.a.by_ref() {
+            while self.b.next_if(|y| *y < x).is_some() {}
+            if self.b.peek() != Some(&x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Days, Month};
+
+    fn dt(year: i32, month: u8, day: u8) -> DateTime {
+        DateTime::new(year, month, day, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn union_merges_in_time_order() {
+        // 1st of the month and every Friday.
+        let s = Schedule::new()
+            .monthly()
+            .or(Schedule::new().every_on_day(Days::FRI));
+        let next: Vec<_> = s.upcoming(dt(2025, 7, 20)).take(4).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 7, 25),
+                dt(2025, 8, 1),
+                dt(2025, 8, 8),
+                dt(2025, 8, 15)
+            ]
+        );
+    }
+
+    #[test]
+    fn union_chains_and_respects_counts() {
+        let s = Schedule::new()
+            .date(1, 1)
+            .or(Schedule::new().date(7, 4))
+            .or(Schedule::new().daily().repeat(2));
+        let next: Vec<_> = s.upcoming(dt(2025, 6, 30)).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 6, 30),
+                dt(2025, 7, 1),
+                dt(2025, 7, 4),
+                dt(2026, 1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn intersection_keeps_common_instants() {
+        // Daily at 9, but only in Q4.
+        let q4 = Schedule::new()
+            .month(10)
+            .hourly()
+            .or(Schedule::new().month(11).hourly())
+            .or(Schedule::new().month(12).hourly());
+        let s = Schedule::new().at(9, 0).daily().and(q4);
+        let next: Vec<_> = s.upcoming(dt(2025, 9, 30)).take(2).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                DateTime::new(2025, 10, 1, 9, 0).unwrap(),
+                DateTime::new(2025, 10, 2, 9, 0).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn intersection_gives_up_past_horizon() {
+        // Midnight and 09:00 never coincide, and both sides are unbounded.
+        let s = Schedule::new()
+            .daily()
+            .and(Schedule::new().at(9, 0).daily());
+        assert_eq!(s.next_after(dt(2025, 1, 1)), None);
+    }
+
+    #[test]
+    fn intersection_of_disjoint_schedules_is_empty() {
+        let s = Schedule::new()
+            .monthly()
+            .except_on_month(Month::JAN)
+            .and(Schedule::new().date(1, 1));
+
+        assert_eq!(s.next_after(dt(2025, 1, 1)), None);
+    }
+
+    #[test]
+    fn difference_removes_coinciding_occurrences() {
+        // Every Friday, but not when it is the 1st of the month.
+        let s = Schedule::new()
+            .every_on_day(Days::FRI)
+            .minus(Schedule::new().monthly());
+        let next: Vec<_> = s.upcoming(dt(2025, 7, 20)).take(3).collect();
+
+        assert_eq!(next, vec![dt(2025, 7, 25), dt(2025, 8, 8), dt(2025, 8, 15)]);
+    }
+
+    #[test]
+    fn difference_nests_with_union() {
+        let s = Schedule::new()
+            .daily()
+            .repeat(5)
+            .minus(Schedule::new().date(1, 2).or(Schedule::new().date(1, 4)));
+        let next: Vec<_> = s.upcoming(dt(2025, 1, 1)).collect();
+
+        assert_eq!(next, vec![dt(2025, 1, 1), dt(2025, 1, 3), dt(2025, 1, 5)]);
+    }
+}
@@ -0,0 +1,80 @@
+// Detecting when two schedules fire too close to each other.
+use std::time::Duration;
+
+use crate::recurrence::Recurrence;
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+impl Schedule {
+    // Pairs of (ours, theirs) occurrences between `from` and `horizon` that
+    // are at most `proximity` apart. A zero proximity means the same minute.
+    pub fn conflicts_with(
+        &self,
+        other: &dyn Recurrence,
+        from: DateTime,
+        horizon: DateTime,
+        proximity: Duration,
+    ) -> Vec<(DateTime, DateTime)> {
+        let p = (proximity.as_secs() / 60) as i64;
+        let ours: Vec<_> = self
+            .upcoming(from)
+            .take_while(|at| *at <= horizon)
+            .collect();
+        let theirs: Vec<_> = other
+            .upcoming(from.add_minutes(-p))
+            .take_while(|at| *at <= horizon.add_minutes(p))
+            .collect();
+
+        let mut conflicts = Vec::new();
+        let mut start = 0;
+        for a in ours {
+            let a_min = a.minutes_since_epoch();
+            while start < theirs.len() && theirs[start].minutes_since_epoch() < a_min - p {
+                start += 1;
+            }
+            for b in theirs[start..]
+                .iter()
+                .take_while(|b| b.minutes_since_epoch() <= a_min + p)
+            {
+                conflicts.push((a, *b));
+            }
+        }
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(2025, 9, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn same_minute_conflicts() {
+        let a = Schedule::new().at(2, 0).daily();
+        let b = Schedule::new().minute(0).hourly();
+        let conflicts = a.conflicts_with(&b, dt(1, 0, 0), dt(2, 23, 59), Duration::ZERO);
+
+        assert_eq!(
+            conflicts,
+            vec![(dt(1, 2, 0), dt(1, 2, 0)), (dt(2, 2, 0), dt(2, 2, 0))]
+        );
+    }
+
+    #[test]
+    fn proximity_window() {
+        let a = Schedule::new().at(2, 0).daily();
+        let b = Schedule::new().at(1, 50).daily();
+
+        assert!(
+            a.conflicts_with(&b, dt(1, 0, 0), dt(1, 23, 0), Duration::from_secs(5 * 60))
+                .is_empty()
+        );
+        assert_eq!(
+            a.conflicts_with(&b, dt(1, 0, 0), dt(1, 23, 0), Duration::from_secs(10 * 60)),
+            vec![(dt(1, 2, 0), dt(1, 1, 50))]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// Conversions to and from the `cron` crate's Schedule, for code moving
+// between the two crates. Only what both sides can represent converts; the
+// rest is a ParseError naming what didn't fit.
+use std::str::FromStr;
+
+use ::cron::TimeUnitSpec;
+
+use crate::parse::{ParseError, from_cron, to_cron};
+use crate::types::Schedule;
+
+const DAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+// Seconds are always 0. One-shots need a year, which `cron` takes as a
+// seventh field.
+impl TryFrom<&Schedule> for ::cron::Schedule {
+    type Error = ParseError;
+
+    fn try_from(s: &Schedule) -> Result<Self, Self::Error> {
+        let year = s.year.map_or("*".to_string(), |y| y.to_string());
+        let fields = match (s.frequency_or_daily(), s.month, s.day) {
+            (None, Some(m), Some(d)) if s.year.is_some() => format!(
+                "{} {} {} {} *",
+                s.minute.unwrap_or(0),
+                s.hour.unwrap_or(0),
+                d,
+                m as u8 + 1
+            ),
+            (None, ..) => {
+                return Err(ParseError::new(
+                    "a one-shot needs a year, month and day to convert",
+                ));
+            }
+            _ => to_cron(&Schedule {
+                year: None,
+                ..s.clone()
+            })?,
+        };
+        ::cron::Schedule::from_str(&format!("0 {} {}", fields, year))
+            .map_err(|e| ParseError::new(format!("rejected by cron: {}", e)))
+    }
+}
+
+impl TryFrom<Schedule> for ::cron::Schedule {
+    type Error = ParseError;
+
+    fn try_from(s: Schedule) -> Result<Self, Self::Error> {
+        ::cron::Schedule::try_from(&s)
+    }
+}
+
+impl TryFrom<&::cron::Schedule> for Schedule {
+    type Error = ParseError;
+
+    fn try_from(c: &::cron::Schedule) -> Result<Self, Self::Error> {
+        if single(c.seconds(), "second")? != Some(0) {
+            return Err(ParseError::new("only second 0 is supported"));
+        }
+        let minute = single(c.minutes(), "minute")?
+            .ok_or_else(|| ParseError::new("every-minute schedules are not supported"))?;
+        let hour = single(c.hours(), "hour")?;
+        let day = single(c.days_of_month(), "day-of-month")?;
+        let month = single(c.months(), "month")?;
+        let dow = single(c.days_of_week(), "day-of-week")?;
+        let year = single(c.years(), "year")?
+            .map(|y| i32::try_from(y).map_err(|_| ParseError::new("year out of range")))
+            .transpose()?;
+
+        if let (Some(y), Some(m), Some(d), Some(h), None) = (year, month, day, hour, dow) {
+            return Ok(Schedule::new().year(y).date_with_time(
+                m as u8,
+                d as u8,
+                h as u8,
+                minute as u8,
+            ));
+        }
+        let field = |v: Option<u32>| v.map_or("*".to_string(), |v| v.to_string());
+        // `cron` numbers weekdays 1-7 from Sunday.
+        let dow = dow.map_or("*", |d| DAYS[(d as usize + 6) % 7]);
+        let s = from_cron(&format!(
+            "{} {} {} {} {}",
+            minute,
+            field(hour),
+            field(day),
+            field(month),
+            dow
+        ))?;
+        Ok(match year {
+            Some(y) => s.year(y),
+            None => s,
+        })
+    }
+}
+
+impl TryFrom<::cron::Schedule> for Schedule {
+    type Error = ParseError;
+
+    fn try_from(c: ::cron::Schedule) -> Result<Self, Self::Error> {
+        Schedule::try_from(&c)
+    }
+}
+
+// `*` is None, a single value is Some; lists, ranges and steps are errors.
+fn single(spec: &impl TimeUnitSpec, name: &str) -> Result<Option<u32>, ParseError> {
+    if spec.is_all() {
+        return Ok(None);
+    }
+    let mut values = spec.iter();
+    match (values.next(), values.next()) {
+        (Some(v), None) => Ok(Some(v)),
+        _ => Err(ParseError::new(format!(
+            "lists, ranges and steps are not supported in the {} field",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::DateTime;
+    use crate::types::Days;
+    use chrono::{NaiveDate, Utc};
+
+    #[test]
+    fn both_crates_agree_on_occurrences() {
+        let from = NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let schedules = [
+            Schedule::new().minute(15).hourly(),
+            Schedule::new().at(9, 30).daily(),
+            Schedule::new().at(6, 0).every_on_day(Days::FRI),
+            Schedule::new().day_with_time(20, 22, 30).monthly(),
+            Schedule::new().year(2026).date_with_time(9, 20, 22, 0),
+        ];
+        for s in &schedules {
+            let c = ::cron::Schedule::try_from(s).unwrap();
+            let theirs: Vec<DateTime> = c
+                .after(&from.and_utc())
+                .take(20)
+                .map(|t: chrono::DateTime<Utc>| t.naive_utc().into())
+                .collect();
+            let ours: Vec<_> = s.upcoming(from.into()).take(20).collect();
+            assert_eq!(ours, theirs, "{}", c);
+            assert_eq!(Schedule::try_from(&c).as_ref(), Ok(s));
+        }
+    }
+
+    #[test]
+    fn what_does_not_fit_is_an_error() {
+        let every_5 = ::cron::Schedule::from_str("0 */5 * * * *").unwrap();
+        assert!(Schedule::try_from(every_5).is_err());
+        let seconds = ::cron::Schedule::from_str("30 0 9 * * *").unwrap();
+        assert!(Schedule::try_from(seconds).is_err());
+        assert!(::cron::Schedule::try_from(Schedule::new().every_nth_day(3, Days::SAT)).is_err());
+        assert!(::cron::Schedule::try_from(Schedule::new().date(9, 20)).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+// Crontab lines for installing schedules into system cron, for setups where
+// cron does the running but brahma stays the source of truth. Cron has no
+// years, repeat counts, offsets past midnight or relative starts, so those
+// are approximated, and every approximation gets a comment above its line.
+// Exclusions and time windows turn into field lists and stay exact.
+use crate::parse::{ParseError, to_cron};
+use crate::types::*;
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+// One entry per pair. Schedules cron can't run at all are kept as comments
+// saying why, so nothing disappears silently.
+pub fn to_crontab<'a>(entries: impl IntoIterator<Item = (&'a Schedule, &'a str)>) -> String {
+    entries
+        .into_iter()
+        .map(|(s, command)| match crontab_line(s, command) {
+            Ok(line) => line,
+            Err(e) => format!("# skipped, {}: {}\n# {}", e.message(), s, command),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+// The line for one schedule, preceded by a `#` comment per approximation.
+pub fn crontab_line(s: &Schedule, command: &str) -> Result<String, ParseError> {
+    let mut notes = Vec::new();
+    let [mut minute, mut hour, mut dom, mut month, mut dow] = fields(s, &mut notes)?;
+
+    if let Some(y) = s.year {
+        notes.push(format!("cron has no year; this also runs outside {}", y));
+    }
+    if !s.years.is_empty() {
+        let years: Vec<String> = s.years.iter().map(|y| y.to_string()).collect();
+        notes.push(format!(
+            "cron has no year; this also runs outside {}",
+            years.join(", ")
+        ));
+    }
+    if s.month_step.is_some() {
+        let skip = match s.recurring.except {
+            Some(Except::Month(m)) => m as u8 + 1,
+            _ => 0,
+        };
+        month = (1..=12)
+            .filter(|m| s.in_month_step(*m) && *m != skip)
+            .map(|m| MONTHS[m as usize - 1])
+            .collect::<Vec<_>>()
+            .join(",");
+        if let Some(d) = s.day.filter(|d| *d > 28) {
+            notes.push(format!(
+                "cron skips months without day {}; brahma fires on their last day",
+                d
+            ));
+        }
+    }
+    match s.recurring.except {
+        Some(Except::Month(_)) if s.month_step.is_some() => {}
+        Some(Except::Day(d)) if dow == "*" => dow = except(&DAYS, d as usize),
+        Some(Except::Month(m)) if month == "*" => month = except(&MONTHS, m as usize),
+        Some(Except::N(n)) if dom == "*" => {
+            dom = (1..=31)
+                .filter(|d| *d != n)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+        Some(e) => notes.push(format!("cron can't skip {:?}; it runs then too", e)),
+        None => {}
+    }
+    if let Some(n) = s.repeat.count() {
+        notes.push(format!(
+            "stops after {} runs in brahma; cron keeps going",
+            n
+        ));
+    }
+    if s.range.is_some() || !s.times.is_empty() || s.interval.is_some() {
+        let times: Vec<Time> = s.times_of_day().iter().collect();
+        if times.is_empty() {
+            return Err(ParseError::new("never fires inside its time window"));
+        }
+        let mut hours: Vec<u8> = times.iter().map(|t| t.hour).collect();
+        let mut minutes: Vec<u8> = times.iter().map(|t| t.minute).collect();
+        hours.dedup();
+        minutes.sort_unstable();
+        minutes.dedup();
+        // Cron fires at every listed minute past every listed hour.
+        if hours.len() * minutes.len() != times.len() {
+            return Err(ParseError::new(
+                "cron can't fire at different minutes past different hours",
+            ));
+        }
+        let list = |v: Vec<u8>| {
+            v.iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        hour = if hours.len() == 24 {
+            "*".to_string()
+        } else {
+            list(hours)
+        };
+        minute = list(minutes);
+    }
+    if !s.weekdays.is_empty() {
+        dow = s
+            .weekdays
+            .iter()
+            .map(|d| DAYS[d as usize])
+            .collect::<Vec<_>>()
+            .join(",");
+    }
+    if let Some(offset) = s.offset {
+        shift(
+            offset,
+            [&mut minute, &mut hour],
+            [&dom, &month, &dow],
+            &mut notes,
+        );
+    }
+    if s.immediate {
+        notes.push("the immediate first run is not installed".to_string());
+    }
+    if let Some(start) = s.not_before {
+        notes.push(format!("runs before {} too; cron has no start date", start));
+    }
+    if let Some(n) = s.stride {
+        notes.push(format!("cron fires on every match, not every {}th", n));
+    }
+    if s.leap_day_policy().is_some() {
+        notes.push("cron skips Feb 29 outside leap years".to_string());
+    }
+
+    let mut out: String = notes.iter().map(|n| format!("# {}\n", n)).collect();
+    out.push_str(&format!(
+        "{} {} {} {} {} {}",
+        minute, hour, dom, month, dow, command
+    ));
+    Ok(out)
+}
+
+// The five fields before exclusions, windows and offsets are applied.
+fn fields(s: &Schedule, notes: &mut Vec<String>) -> Result<[String; 5], ParseError> {
+    if s.delay.is_some() {
+        return Err(ParseError::new("a relative start has no fixed time"));
+    }
+    if s.day_of_year.is_some() {
+        return Err(ParseError::new("cron has no day-of-year field"));
+    }
+    if s.fiscal.is_some() {
+        return Err(ParseError::new("cron has no fiscal calendar"));
+    }
+    if s.business_days.is_some() {
+        return Err(ParseError::new(
+            "cron can't tell business days from days off",
+        ));
+    }
+    if s.week_parity.is_some() {
+        return Err(ParseError::new("cron has no week numbers"));
+    }
+    if s.follows_sun() {
+        return Err(ParseError::new("sunrise and sunset move every day"));
+    }
+    if s.follows_moon() {
+        return Err(ParseError::new("cron has no lunar calendar"));
+    }
+    let field = |v: Option<u8>| v.map_or("*".to_string(), |v| v.to_string());
+    let plain = Schedule {
+        year: None,
+        years: Vec::new(),
+        recurring: Recurring {
+            frequency: s.frequency_or_daily(),
+            except: None,
+        },
+        repeat: Repeat::Forever,
+        range: None,
+        offset: None,
+        immediate: false,
+        stride: None,
+        not_before: None,
+        leap_day: LeapDay::Skip,
+        times: Vec::new(),
+        interval: None,
+        weekdays: Weekdays::default(),
+        month_step: None,
+        ..s.clone()
+    };
+    let expr = match (s.frequency_or_daily(), s.day, s.month, s.hour) {
+        (None, ..) => {
+            notes.push("brahma runs this once; cron repeats it".to_string());
+            format!(
+                "{} {} {} {} *",
+                s.minute.unwrap_or(0),
+                s.hour.unwrap_or(0),
+                field(s.day),
+                field(s.month.map(|m| m as u8 + 1))
+            )
+        }
+        // Yearly on a date: fine for cron, only from_cron lacks it.
+        (Some(FrequencyPattern::Frequency(_)), Some(d), Some(m), Some(h))
+            if to_cron(&plain).is_err() =>
+        {
+            format!("{} {} {} {} *", s.minute.unwrap_or(0), h, d, m as u8 + 1)
+        }
+        _ => to_cron(&plain)?,
+    };
+    let fields: Vec<String> = expr.split(' ').map(str::to_string).collect();
+    Ok(fields
+        .try_into()
+        .expect("cron expressions have five fields"))
+}
+
+fn except(names: &[&str], skip: usize) -> String {
+    names
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != skip)
+        .map(|(_, n)| *n)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Offsets move minute and hour; exact unless they carry into another day on
+// a schedule that cares which day it is.
+fn shift(
+    offset: u32,
+    [minute, hour]: [&mut String; 2],
+    days: [&String; 3],
+    notes: &mut Vec<String>,
+) {
+    let Ok(m) = minute.parse::<u32>() else {
+        notes.push(format!("the {} min offset is not applied", offset));
+        return;
+    };
+    if hour == "*" {
+        *minute = ((m + offset) % 60).to_string();
+        return;
+    }
+    let hours: Vec<u32> = hour.split(',').filter_map(|h| h.parse().ok()).collect();
+    let shifted: Vec<u32> = hours.iter().map(|h| h * 60 + m + offset).collect();
+    if shifted.iter().any(|t| *t >= 24 * 60) && days.iter().any(|d| *d != "*") {
+        notes.push(format!(
+            "the {} min offset carries into the next day, which cron can't follow",
+            offset
+        ));
+    }
+    *minute = ((m + offset) % 60).to_string();
+    let mut hours: Vec<u32> = shifted.iter().map(|t| t / 60 % 24).collect();
+    hours.sort_unstable();
+    *hour = hours
+        .iter()
+        .map(|h| h.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn exact_translations_have_no_comments() {
+        let s = Schedule::new()
+            .minute(0)
+            .hourly()
+            .between((9, 0), (17, 0))
+            .except_on_day(Days::SUN);
+        assert_eq!(
+            crontab_line(&s, "/usr/bin/sync").unwrap(),
+            "0 9,10,11,12,13,14,15,16,17 * * MON,TUE,WED,THU,FRI,SAT /usr/bin/sync"
+        );
+        let s = Schedule::new()
+            .at(23, 30)
+            .daily()
+            .offset_by(Duration::from_secs(3600));
+        assert_eq!(crontab_line(&s, "backup").unwrap(), "30 0 * * * backup");
+        let s = Schedule::new().date_with_time(12, 25, 8, 0).daily();
+        assert_eq!(crontab_line(&s, "gifts").unwrap(), "0 8 25 12 * gifts");
+        let s = Schedule::new().on_days_at(&[Days::WED, Days::MON], &[(17, 0), (9, 0)]);
+        assert_eq!(crontab_line(&s, "pull").unwrap(), "0 9,17 * * MON,WED pull");
+        let s = Schedule::new().on_days_at(&[Days::MON], &[(9, 0), (17, 30)]);
+        assert!(crontab_line(&s, "pull").is_err());
+        let s = Schedule::new().minutes_step(10).hours_step(4);
+        assert_eq!(crontab_line(&s, "poll").unwrap(), "*/10 */4 * * * poll");
+        let s = Schedule::new().every_minutes(15);
+        assert_eq!(crontab_line(&s, "poll").unwrap(), "0,15,30,45 * * * * poll");
+        let s = Schedule::new()
+            .every_on_day(Days::MON)
+            .every_minutes(30)
+            .between((9, 0), (11, 59));
+        assert_eq!(
+            crontab_line(&s, "poll").unwrap(),
+            "0,30 9,10,11 * * MON poll"
+        );
+        // 17:00 but not 17:30.
+        let s = Schedule::new().every_minutes(30).between((9, 0), (17, 0));
+        assert!(crontab_line(&s, "poll").is_err());
+        let s = Schedule::new()
+            .every_months_from(3, Month::FEB)
+            .day_with_time(15, 6, 0)
+            .except_on_month(Month::AUG);
+        assert_eq!(
+            crontab_line(&s, "report").unwrap(),
+            "0 6 15 FEB,MAY,NOV * report"
+        );
+    }
+
+    #[test]
+    fn approximations_are_commented() {
+        let s = Schedule::new().year(2026).date_with_time(9, 20, 22, 0);
+        assert_eq!(
+            crontab_line(&s, "launch").unwrap(),
+            "# brahma runs this once; cron repeats it\n\
+             # cron has no year; this also runs outside 2026\n\
+             0 22 20 9 * launch"
+        );
+        let s = Schedule::new().at(9, 0).daily().in_years(&[2025, 2026]);
+        assert_eq!(
+            crontab_line(&s, "promo").unwrap(),
+            "# cron has no year; this also runs outside 2025, 2026\n0 9 * * * promo"
+        );
+        let s = Schedule::new().every_months(6).on_day(31);
+        assert_eq!(
+            crontab_line(&s, "close").unwrap(),
+            "# cron skips months without day 31; brahma fires on their last day\n\
+             0 0 31 JAN,JUL * close"
+        );
+        let s = Schedule::new().at(9, 0).daily().repeat(3);
+        assert!(
+            crontab_line(&s, "x")
+                .unwrap()
+                .starts_with("# stops after 3 runs")
+        );
+    }
+
+    #[test]
+    fn crontab_keeps_what_it_skips() {
+        let every_day = Schedule::new().at(2, 0).daily();
+        let later = Schedule::after(Duration::from_secs(600));
+        let tab = to_crontab([(&every_day, "backup"), (&later, "ping")]);
+        assert_eq!(
+            tab,
+            "0 2 * * * backup\n# skipped, a relative start has no fixed time: once, 10 min from now\n# ping\n"
+        );
+    }
+}
@@ -0,0 +1,1102 @@
+// Human-readable schedule descriptions, e.g. "every 3rd Saturday at 22:00
+// except in December". Display always renders English; other languages are
+// available through `Schedule::describe` with the `i18n` feature.
+use std::fmt;
+use std::time::Duration;
+
+use crate::calendar::weekday_of;
+use crate::types::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Locale {
+    En,
+    De,
+    Es,
+    Fr,
+}
+
+const DAYS_EN: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const DAYS_DE: [&str; 7] = [
+    "Sonntag",
+    "Montag",
+    "Dienstag",
+    "Mittwoch",
+    "Donnerstag",
+    "Freitag",
+    "Samstag",
+];
+const DAYS_ES: [&str; 7] = [
+    "domingo",
+    "lunes",
+    "martes",
+    "miércoles",
+    "jueves",
+    "viernes",
+    "sábado",
+];
+const DAYS_FR: [&str; 7] = [
+    "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+];
+
+const MONTHS_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const MONTHS_DE: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+const MONTHS_ES: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+const MONTHS_FR: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+impl Locale {
+    fn day(self, d: Days) -> &'static str {
+        let days = match self {
+            Locale::En => &DAYS_EN,
+            Locale::De => &DAYS_DE,
+            Locale::Es => &DAYS_ES,
+            Locale::Fr => &DAYS_FR,
+        };
+        days[d as usize]
+    }
+
+    fn month(self, m: Month) -> &'static str {
+        let months = match self {
+            Locale::En => &MONTHS_EN,
+            Locale::De => &MONTHS_DE,
+            Locale::Es => &MONTHS_ES,
+            Locale::Fr => &MONTHS_FR,
+        };
+        months[m as usize]
+    }
+
+    fn ordinal(self, n: u32) -> String {
+        match self {
+            Locale::En => {
+                let suffix = match (n % 10, n % 100) {
+                    (1, 11) | (2, 12) | (3, 13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", n, suffix)
+            }
+            Locale::De => format!("{}.", n),
+            Locale::Es => format!("{}.º", n),
+            Locale::Fr if n == 1 => "1er".to_string(),
+            Locale::Fr => format!("{}e", n),
+        }
+    }
+
+    fn frequency(self, f: FrequencyPattern) -> String {
+        match (self, f) {
+            (Locale::En, FrequencyPattern::Frequency(f)) => match f {
+                Frequency::Hourly => "every hour",
+                Frequency::Daily => "every day",
+                Frequency::Weekly => "every week",
+                Frequency::Monthly => "every month",
+            }
+            .to_string(),
+            (Locale::De, FrequencyPattern::Frequency(f)) => match f {
+                Frequency::Hourly => "jede Stunde",
+                Frequency::Daily => "jeden Tag",
+                Frequency::Weekly => "jede Woche",
+                Frequency::Monthly => "jeden Monat",
+            }
+            .to_string(),
+            (Locale::Es, FrequencyPattern::Frequency(f)) => match f {
+                Frequency::Hourly => "cada hora",
+                Frequency::Daily => "cada día",
+                Frequency::Weekly => "cada semana",
+                Frequency::Monthly => "cada mes",
+            }
+            .to_string(),
+            (Locale::Fr, FrequencyPattern::Frequency(f)) => match f {
+                Frequency::Hourly => "toutes les heures",
+                Frequency::Daily => "tous les jours",
+                Frequency::Weekly => "toutes les semaines",
+                Frequency::Monthly => "tous les mois",
+            }
+            .to_string(),
+            (Locale::En, FrequencyPattern::ByDay((None, d))) => format!("every {}", self.day(d)),
+            (Locale::De, FrequencyPattern::ByDay((None, d))) => format!("jeden {}", self.day(d)),
+            (Locale::Es, FrequencyPattern::ByDay((None, d))) => format!("cada {}", self.day(d)),
+            (Locale::Fr, FrequencyPattern::ByDay((None, d))) => format!("chaque {}", self.day(d)),
+            (Locale::En, FrequencyPattern::ByDay((Some(n), d))) => {
+                format!(
+                    "every {} {} of the month",
+                    self.ordinal(n.into()),
+                    self.day(d)
+                )
+            }
+            (Locale::De, FrequencyPattern::ByDay((Some(n), d))) => {
+                format!("jeden {} {} im Monat", self.ordinal(n.into()), self.day(d))
+            }
+            (Locale::Es, FrequencyPattern::ByDay((Some(n), d))) => {
+                format!("el {} {} de cada mes", self.ordinal(n.into()), self.day(d))
+            }
+            (Locale::Fr, FrequencyPattern::ByDay((Some(n), d))) => {
+                format!(
+                    "le {} {} de chaque mois",
+                    self.ordinal(n.into()),
+                    self.day(d)
+                )
+            }
+        }
+    }
+
+    // "a, b and c"
+    fn list(self, items: Vec<String>) -> String {
+        let and = match self {
+            Locale::En => "and",
+            Locale::De => "und",
+            Locale::Es => "y",
+            Locale::Fr => "et",
+        };
+        match items.split_last() {
+            Some((last, rest)) if !rest.is_empty() => {
+                format!("{} {} {}", rest.join(", "), and, last)
+            }
+            _ => items.concat(),
+        }
+    }
+
+    fn dates(self, dates: &[(Month, u8)]) -> String {
+        // Reuse the "on <date>" phrasing, keeping the first preposition only.
+        let dates: Vec<String> = dates
+            .iter()
+            .filter_map(|(m, d)| self.date(None, Some(*m), Some(*d)))
+            .enumerate()
+            .map(|(i, date)| match date.split_once(' ') {
+                Some((_, rest)) if i > 0 => rest.to_string(),
+                _ => date,
+            })
+            .collect();
+        self.list(dates)
+    }
+
+    fn every_months(self, n: u8, start: Month) -> String {
+        if n == 1 {
+            return self.frequency(FrequencyPattern::Frequency(Frequency::Monthly));
+        }
+        let start = self.month(start);
+        match self {
+            Locale::En => format!("every {} months from {}", n, start),
+            Locale::De => format!("alle {} Monate ab {}", n, start),
+            Locale::Es => format!("cada {} meses desde {}", n, start),
+            Locale::Fr => format!("tous les {} mois à partir de {}", n, start),
+        }
+    }
+
+    fn years(self, years: &[i32]) -> String {
+        let years = self.list(years.iter().map(|y| y.to_string()).collect());
+        match self {
+            Locale::En => format!("in {}", years),
+            Locale::De => format!("in den Jahren {}", years),
+            Locale::Es | Locale::Fr => format!("en {}", years),
+        }
+    }
+
+    fn weekdays(self, days: Weekdays) -> String {
+        let days = self.list(days.iter().map(|d| self.day(d).to_string()).collect());
+        match self {
+            Locale::En => format!("every {}", days),
+            Locale::De => format!("jeden {}", days),
+            Locale::Es => format!("cada {}", days),
+            Locale::Fr => format!("chaque {}", days),
+        }
+    }
+
+    fn times(self, times: &[Time]) -> String {
+        let times = self.list(
+            times
+                .iter()
+                .map(|t| format!("{:02}:{:02}", t.hour, t.minute))
+                .collect(),
+        );
+        match self {
+            Locale::En => format!("at {}", times),
+            Locale::De => format!("um {}", times),
+            Locale::Es => format!("a las {}", times),
+            Locale::Fr => format!("à {}", times),
+        }
+    }
+
+    fn steps(self, hours: Option<u8>, minutes: Option<u8>) -> Option<String> {
+        Some(match (hours, minutes) {
+            (None, None) => return None,
+            (None, Some(m)) => self.every_minutes(m.into()),
+            (Some(h), None) => match self {
+                Locale::En => format!("every {} hours", h),
+                Locale::De => format!("alle {} Stunden", h),
+                Locale::Es => format!("cada {} horas", h),
+                Locale::Fr => format!("toutes les {} heures", h),
+            },
+            (Some(h), Some(m)) => match self {
+                Locale::En => format!(
+                    "every {} minutes of every {} hour",
+                    m,
+                    self.ordinal(h.into())
+                ),
+                Locale::De => format!("alle {} Minuten in jeder {}. Stunde", m, h),
+                Locale::Es => format!("cada {} minutos, una hora de cada {}", m, h),
+                Locale::Fr => format!("toutes les {} minutes, une heure sur {}", m, h),
+            },
+        })
+    }
+
+    fn every_minutes(self, n: u16) -> String {
+        match self {
+            Locale::En => format!("every {} minutes", n),
+            Locale::De => format!("alle {} Minuten", n),
+            Locale::Es => format!("cada {} minutos", n),
+            Locale::Fr => format!("toutes les {} minutes", n),
+        }
+    }
+
+    fn date(self, year: Option<i32>, month: Option<Month>, day: Option<u8>) -> Option<String> {
+        let date = match (self, month, day) {
+            (_, None, None) => None,
+            (Locale::En, Some(m), Some(d)) => Some(format!("on {} {}", d, self.month(m))),
+            (Locale::De, Some(m), Some(d)) => Some(format!("am {}. {}", d, self.month(m))),
+            (Locale::Es, Some(m), Some(d)) => Some(format!("el {} de {}", d, self.month(m))),
+            (Locale::Fr, Some(m), Some(d)) => Some(format!("le {} {}", d, self.month(m))),
+            (Locale::En, Some(m), None) => Some(format!("in {}", self.month(m))),
+            (Locale::De, Some(m), None) => Some(format!("im {}", self.month(m))),
+            (Locale::Es | Locale::Fr, Some(m), None) => Some(format!("en {}", self.month(m))),
+            (Locale::En, None, Some(d)) => Some(format!("on day {}", d)),
+            (Locale::De, None, Some(d)) => Some(format!("am {}.", d)),
+            (Locale::Es, None, Some(d)) => Some(format!("el día {}", d)),
+            (Locale::Fr, None, Some(d)) => Some(format!("le {}", d)),
+        };
+        let year = year.map(|y| match self {
+            Locale::En => format!("in {}", y),
+            Locale::De => format!("im Jahr {}", y),
+            Locale::Es | Locale::Fr => format!("en {}", y),
+        });
+        match (date, year) {
+            (Some(d), Some(y)) => Some(format!("{} {}", d, y)),
+            (d, y) => d.or(y),
+        }
+    }
+
+    fn at(self, hour: Option<u8>, minute: Option<u8>) -> Option<String> {
+        let time = match (hour, minute) {
+            (None, None) => return None,
+            (Some(h), m) => format!("{:02}:{:02}", h, m.unwrap_or(0)),
+            (None, Some(m)) => {
+                return Some(match self {
+                    Locale::En => format!("at minute {}", m),
+                    Locale::De => format!("zur Minute {}", m),
+                    Locale::Es => format!("en el minuto {}", m),
+                    Locale::Fr => format!("à la minute {}", m),
+                });
+            }
+        };
+        Some(match self {
+            Locale::En => format!("at {}", time),
+            Locale::De => format!("um {}", time),
+            Locale::Es => format!("a las {}", time),
+            Locale::Fr => format!("à {}", time),
+        })
+    }
+
+    fn between(self, start: Time, end: Time) -> String {
+        let (s, e) = (
+            format!("{:02}:{:02}", start.hour, start.minute),
+            format!("{:02}:{:02}", end.hour, end.minute),
+        );
+        match self {
+            Locale::En => format!("between {} and {}", s, e),
+            Locale::De => format!("zwischen {} und {}", s, e),
+            Locale::Es => format!("entre las {} y las {}", s, e),
+            Locale::Fr => format!("entre {} et {}", s, e),
+        }
+    }
+
+    fn except(self, e: Except) -> String {
+        match (self, e) {
+            (Locale::En, Except::Day(d)) => format!("except on {}s", self.day(d)),
+            (Locale::De, Except::Day(d)) => format!("außer {}s", self.day(d).to_lowercase()),
+            (Locale::Es, Except::Day(d)) => format!("excepto los {}", self.day(d)),
+            (Locale::Fr, Except::Day(d)) => format!("sauf le {}", self.day(d)),
+            (Locale::En, Except::N(n)) => format!("except on day {}", n),
+            (Locale::De, Except::N(n)) => format!("außer am {}.", n),
+            (Locale::Es, Except::N(n)) => format!("excepto el día {}", n),
+            (Locale::Fr, Except::N(n)) => format!("sauf le {}", n),
+            (Locale::En, Except::NthDay((n, d))) => {
+                format!("except the {} {}", self.ordinal(n.into()), self.day(d))
+            }
+            (Locale::De, Except::NthDay((n, d))) => {
+                format!("außer am {} {}", self.ordinal(n.into()), self.day(d))
+            }
+            (Locale::Es, Except::NthDay((n, d))) => {
+                format!("excepto el {} {}", self.ordinal(n.into()), self.day(d))
+            }
+            (Locale::Fr, Except::NthDay((n, d))) => {
+                format!("sauf le {} {}", self.ordinal(n.into()), self.day(d))
+            }
+            (Locale::En, Except::Month(m)) => format!("except in {}", self.month(m)),
+            (Locale::De, Except::Month(m)) => format!("außer im {}", self.month(m)),
+            (Locale::Es, Except::Month(m)) => format!("excepto en {}", self.month(m)),
+            (Locale::Fr, Except::Month(m)) => format!("sauf en {}", self.month(m)),
+        }
+    }
+
+    fn day_of_year(self, n: u16) -> String {
+        match self {
+            Locale::En => format!("on day {} of the year", n),
+            Locale::De => format!("am {}. Tag des Jahres", n),
+            Locale::Es => format!("el día {} del año", n),
+            Locale::Fr => format!("le {}e jour de l'année", n),
+        }
+    }
+
+    fn fiscal(self, period: FiscalPeriod, year: FiscalYear) -> String {
+        if year == FiscalYear::default() {
+            return self.calendar_period(period).to_string();
+        }
+        let phrase = match (self, period) {
+            (Locale::En, FiscalPeriod::QuarterStart) => "on the first day of each fiscal quarter",
+            (Locale::En, FiscalPeriod::QuarterEnd) => "on the last day of each fiscal quarter",
+            (Locale::En, FiscalPeriod::YearStart) => "on the first day of the fiscal year",
+            (Locale::En, FiscalPeriod::YearEnd) => "on the last day of the fiscal year",
+            (Locale::De, FiscalPeriod::QuarterStart) => "am ersten Tag jedes Geschäftsquartals",
+            (Locale::De, FiscalPeriod::QuarterEnd) => "am letzten Tag jedes Geschäftsquartals",
+            (Locale::De, FiscalPeriod::YearStart) => "am ersten Tag des Geschäftsjahres",
+            (Locale::De, FiscalPeriod::YearEnd) => "am letzten Tag des Geschäftsjahres",
+            (Locale::Es, FiscalPeriod::QuarterStart) => "el primer día de cada trimestre fiscal",
+            (Locale::Es, FiscalPeriod::QuarterEnd) => "el último día de cada trimestre fiscal",
+            (Locale::Es, FiscalPeriod::YearStart) => "el primer día del año fiscal",
+            (Locale::Es, FiscalPeriod::YearEnd) => "el último día del año fiscal",
+            (Locale::Fr, FiscalPeriod::QuarterStart) => {
+                "le premier jour de chaque trimestre fiscal"
+            }
+            (Locale::Fr, FiscalPeriod::QuarterEnd) => "le dernier jour de chaque trimestre fiscal",
+            (Locale::Fr, FiscalPeriod::YearStart) => "le premier jour de l'exercice",
+            (Locale::Fr, FiscalPeriod::YearEnd) => "le dernier jour de l'exercice",
+        };
+        let month = self.month(year.start);
+        match self {
+            Locale::En => format!("{} (starting in {})", phrase, month),
+            Locale::De => format!("{} (Beginn im {})", phrase, month),
+            Locale::Es => format!("{} (desde {})", phrase, month),
+            Locale::Fr => format!("{} (à partir de {})", phrase, month),
+        }
+    }
+
+    // `fiscal` for a fiscal year that is the calendar year.
+    fn calendar_period(self, period: FiscalPeriod) -> &'static str {
+        match (self, period) {
+            (Locale::En, FiscalPeriod::QuarterStart) => "on the first day of each quarter",
+            (Locale::En, FiscalPeriod::QuarterEnd) => "on the last day of each quarter",
+            (Locale::En, FiscalPeriod::YearStart) => "on the first day of the year",
+            (Locale::En, FiscalPeriod::YearEnd) => "on the last day of the year",
+            (Locale::De, FiscalPeriod::QuarterStart) => "am ersten Tag jedes Quartals",
+            (Locale::De, FiscalPeriod::QuarterEnd) => "am letzten Tag jedes Quartals",
+            (Locale::De, FiscalPeriod::YearStart) => "am ersten Tag des Jahres",
+            (Locale::De, FiscalPeriod::YearEnd) => "am letzten Tag des Jahres",
+            (Locale::Es, FiscalPeriod::QuarterStart) => "el primer día de cada trimestre",
+            (Locale::Es, FiscalPeriod::QuarterEnd) => "el último día de cada trimestre",
+            (Locale::Es, FiscalPeriod::YearStart) => "el primer día del año",
+            (Locale::Es, FiscalPeriod::YearEnd) => "el último día del año",
+            (Locale::Fr, FiscalPeriod::QuarterStart) => "le premier jour de chaque trimestre",
+            (Locale::Fr, FiscalPeriod::QuarterEnd) => "le dernier jour de chaque trimestre",
+            (Locale::Fr, FiscalPeriod::YearStart) => "le premier jour de l'année",
+            (Locale::Fr, FiscalPeriod::YearEnd) => "le dernier jour de l'année",
+        }
+    }
+
+    // A day off rolls forward, or `back`, onto a business day, which is
+    // then moved by `n` more.
+    fn business_days(self, n: i8, back: bool) -> String {
+        let count = n.unsigned_abs();
+        let days = match (self, count) {
+            (Locale::En, 1) => "1 business day".to_string(),
+            (Locale::En, _) => format!("{} business days", count),
+            (Locale::De, 1) => "1 Werktag".to_string(),
+            (Locale::De, _) => format!("{} Werktage", count),
+            (Locale::Es, 1) => "1 día hábil".to_string(),
+            (Locale::Es, _) => format!("{} días hábiles", count),
+            (Locale::Fr, 1) => "1 jour ouvré".to_string(),
+            (Locale::Fr, _) => format!("{} jours ouvrés", count),
+        };
+        match (self, n.signum(), back) {
+            (Locale::En, 0, false) => "or the next business day".to_string(),
+            (Locale::En, 0, true) => "or the business day before".to_string(),
+            (Locale::En, 1, _) => format!("{} later", days),
+            (Locale::En, _, _) => format!("{} earlier", days),
+            (Locale::De, 0, false) => "oder am nächsten Werktag".to_string(),
+            (Locale::De, 0, true) => "oder am Werktag davor".to_string(),
+            (Locale::De, 1, _) => format!("{} später", days),
+            (Locale::De, _, _) => format!("{} früher", days),
+            (Locale::Es, 0, false) => "o el siguiente día hábil".to_string(),
+            (Locale::Es, 0, true) => "o el día hábil anterior".to_string(),
+            (Locale::Es, 1, _) => format!("{} después", days),
+            (Locale::Es, _, _) => format!("{} antes", days),
+            (Locale::Fr, 0, false) => "ou le jour ouvré suivant".to_string(),
+            (Locale::Fr, 0, true) => "ou le jour ouvré précédent".to_string(),
+            (Locale::Fr, 1, _) => format!("{} plus tard", days),
+            (Locale::Fr, _, _) => format!("{} plus tôt", days),
+        }
+    }
+
+    fn parity(self, parity: Parity) -> &'static str {
+        match (self, parity) {
+            (Locale::En, Parity::Odd) => "in odd weeks",
+            (Locale::En, Parity::Even) => "in even weeks",
+            (Locale::De, Parity::Odd) => "in ungeraden Wochen",
+            (Locale::De, Parity::Even) => "in geraden Wochen",
+            (Locale::Es, Parity::Odd) => "en semanas impares",
+            (Locale::Es, Parity::Even) => "en semanas pares",
+            (Locale::Fr, Parity::Odd) => "les semaines impaires",
+            (Locale::Fr, Parity::Even) => "les semaines paires",
+        }
+    }
+
+    fn month_end(self) -> &'static str {
+        match self {
+            Locale::En => "on the last day of the month",
+            Locale::De => "am letzten Tag des Monats",
+            Locale::Es => "el último día del mes",
+            Locale::Fr => "le dernier jour du mois",
+        }
+    }
+
+    fn holidays(self, n: usize) -> String {
+        match (self, n) {
+            (Locale::En, 1) => "with 1 holiday off".to_string(),
+            (Locale::En, _) => format!("with {} holidays off", n),
+            (Locale::De, 1) => "mit 1 Feiertag".to_string(),
+            (Locale::De, _) => format!("mit {} Feiertagen", n),
+            (Locale::Es, 1) => "con 1 festivo".to_string(),
+            (Locale::Es, _) => format!("con {} festivos", n),
+            (Locale::Fr, 1) => "avec 1 jour férié".to_string(),
+            (Locale::Fr, _) => format!("avec {} jours fériés", n),
+        }
+    }
+
+    #[cfg(feature = "astro")]
+    fn solar(self, solar: Solar) -> String {
+        let event = match (self, solar.event) {
+            (Locale::En, SolarEvent::Sunrise) => "sunrise",
+            (Locale::En, SolarEvent::Sunset) => "sunset",
+            (Locale::De, SolarEvent::Sunrise) => "Sonnenaufgang",
+            (Locale::De, SolarEvent::Sunset) => "Sonnenuntergang",
+            (Locale::Es, SolarEvent::Sunrise) => "amanecer",
+            (Locale::Es, SolarEvent::Sunset) => "atardecer",
+            (Locale::Fr, SolarEvent::Sunrise) => "lever du soleil",
+            (Locale::Fr, SolarEvent::Sunset) => "coucher du soleil",
+        };
+        let n = solar.shift.unsigned_abs();
+        let when = match (self, solar.shift.signum()) {
+            (Locale::En, 0) => format!("at {}", event),
+            (Locale::En, -1) => format!("{} min before {}", n, event),
+            (Locale::En, _) => format!("{} min after {}", n, event),
+            (Locale::De, 0) => format!("bei {}", event),
+            (Locale::De, -1) => format!("{} Min. vor {}", n, event),
+            (Locale::De, _) => format!("{} Min. nach {}", n, event),
+            (Locale::Es, 0) => format!("al {}", event),
+            (Locale::Es, -1) => format!("{} min antes del {}", n, event),
+            (Locale::Es, _) => format!("{} min después del {}", n, event),
+            (Locale::Fr, 0) => format!("au {}", event),
+            (Locale::Fr, -1) => format!("{} min avant le {}", n, event),
+            (Locale::Fr, _) => format!("{} min après le {}", n, event),
+        };
+        let place = solar.location;
+        format!(
+            "{} ({:.4}°, {:.4}°)",
+            when,
+            place.latitude(),
+            place.longitude()
+        )
+    }
+
+    #[cfg(feature = "panchang")]
+    fn lunar(self, lunar: Lunar) -> String {
+        let name = match lunar.event {
+            LunarEvent::Tithi(n) => format!("tithi {}", n),
+            LunarEvent::Ekadashi => "Ekadashi".to_string(),
+            LunarEvent::Purnima => "Purnima".to_string(),
+            LunarEvent::Amavasya => "Amavasya".to_string(),
+        };
+        let place = lunar.location;
+        let day = match self {
+            Locale::En => format!("on {}", name),
+            Locale::De => format!("an {}", name),
+            Locale::Es => format!("en {}", name),
+            Locale::Fr => format!("le jour de {}", name),
+        };
+        format!(
+            "{} ({:.4}°, {:.4}°)",
+            day,
+            place.latitude(),
+            place.longitude()
+        )
+    }
+
+    fn leap_day(self, policy: LeapDay) -> Option<String> {
+        let date = match policy {
+            LeapDay::Skip => return None,
+            LeapDay::Feb28 => self.date(None, Some(Month::FEB), Some(28))?,
+            LeapDay::Mar1 => self.date(None, Some(Month::MAR), Some(1))?,
+        };
+        Some(match self {
+            Locale::En => format!("or {} in other years", date),
+            Locale::De => format!("sonst {}", date),
+            Locale::Es => format!("o {} los demás años", date),
+            Locale::Fr => format!("sinon {}", date),
+        })
+    }
+
+    fn repeat(self, repeat: Repeat) -> Option<String> {
+        let times = repeat.count().map(|n| match self {
+            Locale::En => format!("{} times", n),
+            Locale::De => format!("{} Mal", n),
+            Locale::Es => format!("{} veces", n),
+            Locale::Fr => format!("{} fois", n),
+        });
+        let Some(date) = repeat
+            .end_date()
+            .and_then(|until| self.date(until.year, until.month, until.day))
+        else {
+            return times;
+        };
+        // Reuse the "on <date>" phrasing without its preposition.
+        let date = date
+            .split_once(' ')
+            .map_or(date.clone(), |(_, rest)| rest.to_string());
+        let until = match self {
+            Locale::En => format!("until {}", date),
+            Locale::De => format!("bis {}", date),
+            Locale::Es => format!("hasta el {}", date),
+            Locale::Fr => format!("jusqu'au {}", date),
+        };
+        Some(match times {
+            Some(times) => format!("{} {}", times, until),
+            None => until,
+        })
+    }
+
+    fn offset(self, minutes: u32) -> String {
+        match self {
+            Locale::En => format!("shifted by {} min", minutes),
+            Locale::De => format!("um {} Min. verschoben", minutes),
+            Locale::Es => format!("desplazado {} min", minutes),
+            Locale::Fr => format!("décalé de {} min", minutes),
+        }
+    }
+
+    fn after(self, minutes: u32) -> String {
+        match self {
+            Locale::En => format!("once, {} min from now", minutes),
+            Locale::De => format!("einmal, in {} Min.", minutes),
+            Locale::Es => format!("una vez, dentro de {} min", minutes),
+            Locale::Fr => format!("une fois, dans {} min", minutes),
+        }
+    }
+
+    fn follows(self, leader: &str, minutes: u64) -> String {
+        match self {
+            Locale::En => format!("{} minutes after {} succeeds", minutes, leader),
+            Locale::De => format!("{} Minuten nachdem {} erfolgreich war", minutes, leader),
+            Locale::Es => format!("{} minutos después de que {} termine bien", minutes, leader),
+            Locale::Fr => format!("{} minutes après la réussite de {}", minutes, leader),
+        }
+    }
+
+    fn stride(self, n: u32) -> String {
+        match self {
+            Locale::En => format!("on every {} occurrence", self.ordinal(n)),
+            Locale::De => format!("jedes {}. Mal", n),
+            Locale::Es => format!("una de cada {} veces", n),
+            Locale::Fr => format!("une fois sur {}", n),
+        }
+    }
+
+    fn starting(self, start: DateTime) -> String {
+        match self {
+            Locale::En => format!("from {}", start),
+            Locale::De => format!("ab {}", start),
+            Locale::Es => format!("desde {}", start),
+            Locale::Fr => format!("à partir du {}", start),
+        }
+    }
+
+    fn immediately(self) -> &'static str {
+        match self {
+            Locale::En => "and right away",
+            Locale::De => "und sofort",
+            Locale::Es => "y de inmediato",
+            Locale::Fr => "et tout de suite",
+        }
+    }
+
+    fn once(self) -> &'static str {
+        match self {
+            Locale::En => "once",
+            Locale::De => "einmal",
+            Locale::Es => "una vez",
+            Locale::Fr => "une fois",
+        }
+    }
+}
+
+pub(crate) fn describe_in(s: &Schedule, locale: Locale) -> String {
+    let spec = s.spec();
+    let mut parts = Vec::new();
+    match (s.frequency_or_daily(), spec.every_months) {
+        (Some(FrequencyPattern::Frequency(Frequency::Weekly)), _) if !spec.weekdays.is_empty() => {
+            parts.push(locale.weekdays(spec.weekdays));
+        }
+        (Some(_), Some((n, start))) => parts.push(locale.every_months(n, start)),
+        // "every 30 minutes" already says every day.
+        (Some(FrequencyPattern::Frequency(Frequency::Daily)), None)
+            if spec.every_minutes.is_some()
+                || spec.hours_step.is_some()
+                || spec.minutes_step.is_some() => {}
+        (Some(FrequencyPattern::Frequency(Frequency::Hourly)), None)
+            if spec.minutes_step.is_some() => {}
+        (Some(f), None) => parts.push(locale.frequency(f)),
+        (None, _) => {}
+    }
+    if let Some(p) = spec.week_parity {
+        parts.push(locale.parity(p).to_string());
+    }
+    if let Some(n) = spec.day_of_year {
+        parts.push(locale.day_of_year(n));
+    }
+    if let Some(p) = spec.fiscal {
+        parts.push(locale.fiscal(p, spec.fiscal_year));
+    }
+    #[cfg(feature = "panchang")]
+    if let Some(lunar) = spec.lunar {
+        parts.push(locale.lunar(lunar));
+    }
+    if !spec.dates.is_empty() {
+        parts.push(locale.dates(&spec.dates));
+    }
+    if s.on_month_end() {
+        parts.push(locale.month_end().to_string());
+    } else {
+        parts.extend(locale.date(spec.year, spec.month, spec.day));
+    }
+    if let Some(n) = spec.business_days {
+        parts.push(locale.business_days(n, s.rolls_back()));
+    }
+    if !spec.holidays.is_empty() {
+        parts.push(locale.holidays(spec.holidays.len()));
+    }
+    if !spec.years.is_empty() {
+        parts.push(locale.years(&spec.years));
+    }
+    parts.extend(locale.steps(spec.hours_step, spec.minutes_step));
+    parts.extend(locale.at(spec.hour, spec.minute));
+    if !spec.times.is_empty() {
+        parts.push(locale.times(&spec.times));
+    }
+    if let Some(n) = spec.every_minutes {
+        parts.push(locale.every_minutes(n));
+    }
+
+    #[cfg(feature = "astro")]
+    if let Some(solar) = spec.solar {
+        parts.push(locale.solar(solar));
+    }
+    if let Some((start, end)) = spec.range {
+        parts.push(locale.between(start, end));
+    }
+    parts.extend(s.leap_day_policy().and_then(|p| locale.leap_day(p)));
+    if let Some(e) = spec.except {
+        parts.push(locale.except(e));
+    }
+    if let Some(n) = spec.stride {
+        parts.push(locale.stride(n));
+    }
+    if let Some(start) = spec.starting_from {
+        parts.push(locale.starting(start));
+    }
+    parts.extend(locale.repeat(spec.repeat));
+    if let Some(o) = spec.offset {
+        parts.push(locale.offset(o));
+    }
+    if let Some(d) = spec.delay {
+        parts.push(locale.after(d));
+    }
+    if spec.run_immediately {
+        parts.push(locale.immediately().to_string());
+    }
+    if parts.is_empty() {
+        parts.push(locale.once().to_string());
+    }
+    parts.join(" ")
+}
+
+// A job started by another's successes rather than by a schedule; see
+// Runtime::add_after.
+pub(crate) fn describe_follower(leader: &str, delay: Duration, locale: Locale) -> String {
+    locale.follows(leader, delay.as_secs() / 60)
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&describe_in(self, Locale::En))
+    }
+}
+
+// A dry run of a schedule: what it says it does and when it would actually
+// fire. Display lists one occurrence per line, e.g.
+//
+//   every 3rd Saturday of the month at 22:00 except in December
+//     1. Saturday 2025-09-20 22:00
+//     2. Saturday 2025-10-18 22:00
+//   (no more occurrences)
+//
+// with the last line only when the schedule ran out before `n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation {
+    pub description: String,
+    pub occurrences: Vec<DateTime>,
+    pub exhausted: bool,
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.description)?;
+        let width = self.occurrences.len().to_string().len();
+        for (i, at) in self.occurrences.iter().enumerate() {
+            let day = Locale::En.day(weekday_of(at.date()));
+            writeln!(f, "  {:>width$}. {} {}", i + 1, day, at)?;
+        }
+        if self.exhausted {
+            writeln!(f, "(no more occurrences)")?;
+        }
+        Ok(())
+    }
+}
+
+impl Schedule {
+    // The first `n` occurrences at or after `from`, to check a schedule does
+    // what was meant before it is deployed.
+    pub fn simulate(&self, from: DateTime, n: usize) -> Simulation {
+        let mut upcoming = self.upcoming(from);
+        let occurrences: Vec<_> = upcoming.by_ref().take(n).collect();
+        Simulation {
+            description: self.to_string(),
+            exhausted: occurrences.len() < n,
+            occurrences,
+        }
+    }
+}
+
+#[cfg(feature = "i18n")]
+impl Schedule {
+    pub fn describe(&self, locale: Locale) -> String {
+        describe_in(self, locale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_display() {
+        let s = Schedule::new()
+            .every_nth_day(3, Days::SAT)
+            .at(22, 0)
+            .except_on_month(Month::DEC);
+        assert_eq!(
+            s.to_string(),
+            "every 3rd Saturday of the month at 22:00 except in December"
+        );
+    }
+
+    #[test]
+    fn simulation_lists_occurrences() {
+        let s = Schedule::new()
+            .every_nth_day(3, Days::SAT)
+            .at(22, 0)
+            .except_on_month(Month::DEC)
+            .until_date(1, 3);
+        let from = DateTime::new(2025, 11, 1, 0, 0).unwrap();
+        let sim = s.simulate(from, 5);
+        assert!(sim.exhausted);
+        assert_eq!(
+            sim.to_string(),
+            "every 3rd Saturday of the month at 22:00 except in December until 1 March\n  \
+             1. Saturday 2025-11-15 22:00\n  \
+             2. Saturday 2026-01-17 22:00\n  \
+             3. Saturday 2026-02-21 22:00\n\
+             (no more occurrences)\n"
+        );
+
+        let daily = Schedule::new().daily().at(9, 0).simulate(from, 10);
+        assert!(!daily.exhausted);
+        assert!(
+            daily
+                .to_string()
+                .contains("\n  10. Monday 2025-11-10 09:00\n")
+        );
+    }
+
+    #[test]
+    fn english_one_shot_and_repeat() {
+        let s = Schedule::new().date_with_time(9, 20, 22, 0);
+        assert_eq!(s.to_string(), "on 20 September at 22:00");
+
+        let s = Schedule::new().daily().repeat_until_date(10, 3, Month::MAR);
+        assert_eq!(s.to_string(), "every day 10 times until 3 March");
+
+        let s = Schedule::new()
+            .every_on_day(Days::MON)
+            .every_nth_occurrence(2);
+        assert_eq!(s.to_string(), "every Monday on every 2nd occurrence");
+
+        let start = DateTime::new(2026, 3, 1, 0, 0).unwrap();
+        let s = Schedule::new().at(9, 0).daily().starting_from(start);
+        assert_eq!(s.to_string(), "every day at 09:00 from 2026-03-01 00:00");
+
+        let s = Schedule::new().every_minutes(30).between((9, 0), (17, 0));
+        assert_eq!(s.to_string(), "every 30 minutes between 09:00 and 17:00");
+        let s = Schedule::new().every_on_day(Days::MON).every_minutes(45);
+        assert_eq!(s.to_string(), "every Monday every 45 minutes");
+        let s = Schedule::new().minutes_step(15).hours_step(2);
+        assert_eq!(s.to_string(), "every 15 minutes of every 2nd hour");
+        let s = Schedule::new().hours_step(6).minute(30);
+        assert_eq!(s.to_string(), "every 6 hours at minute 30");
+
+        let s = Schedule::new()
+            .date_with_time(2, 29, 9, 0)
+            .on_leap_day(LeapDay::Feb28);
+        assert_eq!(
+            s.to_string(),
+            "on 29 February at 09:00 or on 28 February in other years"
+        );
+
+        #[cfg(feature = "astro")]
+        {
+            let london = Location::new(51.5074, -0.1278).unwrap();
+            let solar = Solar::sunset(london).before(std::time::Duration::from_secs(1800));
+            let s = Schedule::new().daily().at_solar(solar);
+            assert_eq!(
+                s.to_string(),
+                "every day 30 min before sunset (51.5074°, -0.1278°)"
+            );
+        }
+
+        #[cfg(feature = "panchang")]
+        {
+            let delhi = Location::new(28.6139, 77.209).unwrap();
+            let s = Schedule::new().daily().on_lunar(Lunar::purnima(delhi));
+            assert_eq!(s.to_string(), "every day on Purnima (28.6139°, 77.2090°)");
+        }
+
+        let s = Schedule::new().on_days_at(&[Days::WED, Days::MON], &[(17, 0), (9, 0)]);
+        assert_eq!(
+            s.to_string(),
+            "every Monday and Wednesday at 09:00 and 17:00"
+        );
+
+        let s = Schedule::new()
+            .on_dates(&[(Month::DEC, 25), (Month::JAN, 1), (Month::JUL, 4)])
+            .at(9, 0)
+            .daily();
+        assert_eq!(
+            s.to_string(),
+            "every day on 1 January, 4 July and 25 December at 09:00"
+        );
+
+        let s = Schedule::new()
+            .on_quarter_start()
+            .offset_business_days(2)
+            .monthly();
+        assert_eq!(
+            s.to_string(),
+            "every month on the first day of each quarter 2 business days later"
+        );
+        let s = Schedule::new().on_quarter_end().offset_business_days(0);
+        assert_eq!(
+            s.to_string(),
+            "on the last day of each quarter or the business day before"
+        );
+
+        let s = Schedule::new()
+            .on_first_business_day()
+            .at(8, 0)
+            .holiday_calendar(Holidays::new().every_year(Month::JAN, 1));
+        assert_eq!(
+            s.to_string(),
+            "every month on day 1 or the next business day with 1 holiday off at 08:00"
+        );
+
+        let s = Schedule::new().on_last_business_day().at(17, 0);
+        assert_eq!(
+            s.to_string(),
+            "every month on the last day of the month or the business day before at 17:00"
+        );
+
+        let s = Schedule::new()
+            .every_on_day(Days::TUE)
+            .at(7, 0)
+            .on_alternate_weeks(Parity::Even);
+        assert_eq!(s.to_string(), "every Tuesday in even weeks at 07:00");
+
+        let s = Schedule::new().every_months(3).on_day(15).at(9, 0);
+        assert_eq!(
+            s.to_string(),
+            "every 3 months from January on day 15 at 09:00"
+        );
+
+        let s = Schedule::new().at(9, 0).daily().in_years(&[2025, 2026]);
+        assert_eq!(s.to_string(), "every day in 2025 and 2026 at 09:00");
+
+        let s = Schedule::new().on_day_of_year(100).at(6, 0).daily();
+        assert_eq!(s.to_string(), "every day on day 100 of the year at 06:00");
+
+        let s = Schedule::new()
+            .fiscal_year_starting(Month::APR)
+            .on_fiscal_quarter_start()
+            .daily();
+        assert_eq!(
+            s.to_string(),
+            "every day on the first day of each fiscal quarter (starting in April)"
+        );
+    }
+
+    #[cfg(feature = "i18n")]
+    #[test]
+    fn other_locales() {
+        let s = Schedule::new()
+            .every_on_day(Days::SAT)
+            .at(10, 30)
+            .except_on_month(Month::DEC);
+        assert_eq!(
+            s.describe(Locale::De),
+            "jeden Samstag um 10:30 außer im Dezember"
+        );
+        assert_eq!(
+            s.describe(Locale::Es),
+            "cada sábado a las 10:30 excepto en diciembre"
+        );
+        assert_eq!(
+            s.describe(Locale::Fr),
+            "chaque samedi à 10:30 sauf en décembre"
+        );
+        assert_eq!(s.describe(Locale::En), s.to_string());
+
+        let s = Schedule::new().on_days_at(&[Days::MON, Days::WED, Days::FRI], &[(9, 0)]);
+        assert_eq!(
+            s.describe(Locale::De),
+            "jeden Montag, Mittwoch und Freitag um 09:00"
+        );
+
+        let s = Schedule::new().on_dates(&[(Month::MAY, 1), (Month::OCT, 3)]);
+        assert_eq!(s.describe(Locale::De), "am 1. Mai und 3. Oktober");
+        assert_eq!(s.describe(Locale::Es), "el 1 de mayo y 3 de octubre");
+        assert_eq!(s.describe(Locale::Fr), "le 1 mai et 3 octobre");
+        let q = Schedule::new().on_quarter_end().offset_business_days(-2);
+        assert_eq!(
+            q.describe(Locale::De),
+            "am letzten Tag jedes Quartals 2 Werktage früher"
+        );
+        let q = Schedule::new().on_quarter_start().offset_business_days(0);
+        assert_eq!(
+            q.describe(Locale::Fr),
+            "le premier jour de chaque trimestre ou le jour ouvré suivant"
+        );
+        let pickup = Schedule::new()
+            .every_on_day(Days::TUE)
+            .on_alternate_weeks(Parity::Odd);
+        assert_eq!(
+            pickup.describe(Locale::De),
+            "jeden Dienstag in ungeraden Wochen"
+        );
+        assert_eq!(
+            pickup.describe(Locale::Fr),
+            "chaque mardi les semaines impaires"
+        );
+        let payday = Schedule::new().on_last_business_day();
+        assert_eq!(
+            payday.describe(Locale::Es),
+            "cada mes el último día del mes o el día hábil anterior"
+        );
+        let steps = Schedule::new().minutes_step(10).hours_step(3);
+        assert_eq!(
+            steps.describe(Locale::De),
+            "alle 10 Minuten in jeder 3. Stunde"
+        );
+        let slots = Schedule::new().every_minutes(30).between((9, 0), (17, 0));
+        assert_eq!(
+            slots.describe(Locale::Fr),
+            "toutes les 30 minutes entre 09:00 et 17:00"
+        );
+        let quarterly = Schedule::new().every_months_from(3, Month::FEB);
+        assert_eq!(quarterly.describe(Locale::De), "alle 3 Monate ab Februar");
+        assert_eq!(quarterly.describe(Locale::Es), "cada 3 meses desde febrero");
+        assert_eq!(
+            quarterly.describe(Locale::Fr),
+            "tous les 3 mois à partir de février"
+        );
+        let s = s.in_years(&[2025, 2026, 2027]);
+        assert_eq!(
+            s.describe(Locale::De),
+            "am 1. Mai und 3. Oktober in den Jahren 2025, 2026 und 2027"
+        );
+    }
+}
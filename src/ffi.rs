@@ -0,0 +1,127 @@
+// C entry points for evaluating schedules from other languages. Expressions
+// are anything Schedule's FromStr takes (cron, RRULE, natural language) and
+// times are Unix seconds in UTC. Build the shared library with
+//
+//     cargo rustc --release --features ffi --crate-type cdylib
+//
+// and declare, for example:
+//
+//     typedef struct BrahmaSchedule BrahmaSchedule;
+//     BrahmaSchedule *brahma_schedule_parse(const char *expr);
+//     void brahma_schedule_free(BrahmaSchedule *schedule);
+//     long brahma_schedule_next(const BrahmaSchedule *schedule, int64_t after,
+//                               int64_t *out, size_t n);
+//     long brahma_next_occurrences(const char *expr, int64_t after,
+//                                  int64_t *out, size_t n);
+//
+// Every function is unsafe only in the pointers it is handed; what each
+// needs is noted above it.
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{CStr, c_char, c_long};
+
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+// NULL when `expr` is NULL, not UTF-8 or doesn't parse. Free the result with
+// brahma_schedule_free.
+// Safety: `expr` must be NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn brahma_schedule_parse(expr: *const c_char) -> *mut Schedule {
+    match unsafe { parse(expr) } {
+        Some(s) => Box::into_raw(Box::new(s)),
+        None => std::ptr::null_mut(),
+    }
+}
+
+// Safety: `schedule` must be NULL or come from brahma_schedule_parse, and
+// not be used again.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn brahma_schedule_free(schedule: *mut Schedule) {
+    if !schedule.is_null() {
+        drop(unsafe { Box::from_raw(schedule) });
+    }
+}
+
+// Writes up to `n` occurrences strictly after `after` into `out` and returns
+// how many; -1 for a NULL argument.
+// Safety: `schedule` must be NULL or come from brahma_schedule_parse, and
+// `out` must be NULL or have room for `n` values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn brahma_schedule_next(
+    schedule: *const Schedule,
+    after: i64,
+    out: *mut i64,
+    n: usize,
+) -> c_long {
+    if schedule.is_null() || out.is_null() {
+        return -1;
+    }
+    let schedule = unsafe { &*schedule };
+    let out = unsafe { std::slice::from_raw_parts_mut(out, n) };
+    let from = DateTime::from_unix(after).next_minute();
+    let mut written = 0;
+    for (slot, at) in out.iter_mut().zip(schedule.upcoming(from)) {
+        *slot = at.unix();
+        written += 1;
+    }
+    written
+}
+
+// brahma_schedule_parse and brahma_schedule_next in one call; -1 when
+// `expr` doesn't parse.
+// Safety: as for brahma_schedule_parse and brahma_schedule_next.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn brahma_next_occurrences(
+    expr: *const c_char,
+    after: i64,
+    out: *mut i64,
+    n: usize,
+) -> c_long {
+    match unsafe { parse(expr) } {
+        Some(s) => unsafe { brahma_schedule_next(&s, after, out, n) },
+        None => -1,
+    }
+}
+
+unsafe fn parse(expr: *const c_char) -> Option<Schedule> {
+    if expr.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(expr) }.to_str().ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_occurrences_as_epoch_seconds() {
+        let after = DateTime::new(2025, 1, 1, 9, 0).unwrap().unix();
+        let mut out = [0i64; 3];
+        let n =
+            unsafe { brahma_next_occurrences(c"0 9 * * *".as_ptr(), after, out.as_mut_ptr(), 3) };
+
+        assert_eq!(n, 3);
+        assert_eq!(
+            out,
+            [after + 86_400, after + 2 * 86_400, after + 3 * 86_400]
+        );
+        assert_eq!(
+            unsafe { brahma_next_occurrences(c"nonsense".as_ptr(), after, out.as_mut_ptr(), 3) },
+            -1
+        );
+    }
+
+    #[test]
+    fn handles_run_out_and_free() {
+        unsafe {
+            let s = brahma_schedule_parse(c"FREQ=DAILY;BYHOUR=9;BYMINUTE=0;COUNT=2".as_ptr());
+            assert!(!s.is_null());
+            let mut out = [0i64; 5];
+            assert_eq!(brahma_schedule_next(s, 0, out.as_mut_ptr(), 5), 2);
+            brahma_schedule_free(s);
+            assert!(brahma_schedule_parse(std::ptr::null()).is_null());
+        }
+    }
+}
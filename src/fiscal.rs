@@ -0,0 +1,136 @@
+// Fiscal calendars: a year that starts on the 1st of some month other than
+// January, split into four quarters of three months each.
+//
+// A fiscal year is named for the calendar year it ends in, so with an April
+// start FY2026 runs from 1 April 2025 to 31 March 2026.
+use crate::time::{Date, days_in_month};
+use crate::types::Month;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FiscalYear {
+    pub start: Month,
+}
+
+// The day within a fiscal year a schedule fires on.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FiscalPeriod {
+    QuarterStart,
+    QuarterEnd,
+    YearStart,
+    YearEnd,
+}
+
+impl Default for FiscalYear {
+    fn default() -> Self {
+        FiscalYear { start: Month::JAN }
+    }
+}
+
+impl FiscalYear {
+    pub fn starting(start: Month) -> Self {
+        FiscalYear { start }
+    }
+
+    pub fn year_of(&self, date: Date) -> i32 {
+        if self.start == Month::JAN || date.month <= self.start as u8 {
+            date.year
+        } else {
+            date.year + 1
+        }
+    }
+
+    // 1 to 4.
+    pub fn quarter_of(&self, date: Date) -> u8 {
+        self.months_in(date) / 3 + 1
+    }
+
+    // First day of `quarter` (1 to 4) of fiscal year `year`.
+    pub fn quarter_start(&self, year: i32, quarter: u8) -> Option<Date> {
+        if !(1..=4).contains(&quarter) {
+            return None;
+        }
+        let first = if self.start == Month::JAN {
+            year
+        } else {
+            year - 1
+        };
+        let start = Date::new(first, self.start as u8 + 1, 1)?;
+        Some(start.add_months((quarter as i64 - 1) * 3))
+    }
+
+    // Last day of `quarter` (1 to 4) of fiscal year `year`.
+    pub fn quarter_end(&self, year: i32, quarter: u8) -> Option<Date> {
+        let next = match quarter {
+            1..=3 => self.quarter_start(year, quarter + 1),
+            4 => self.quarter_start(year + 1, 1),
+            _ => None,
+        }?;
+        Some(next.add_days(-1))
+    }
+
+    pub(crate) fn contains(&self, period: FiscalPeriod, date: Date) -> bool {
+        let months = self.months_in(date);
+        let last_day = date.day == days_in_month(date.year, date.month);
+        match period {
+            FiscalPeriod::QuarterStart => months.is_multiple_of(3) && date.day == 1,
+            FiscalPeriod::QuarterEnd => months % 3 == 2 && last_day,
+            FiscalPeriod::YearStart => months == 0 && date.day == 1,
+            FiscalPeriod::YearEnd => months == 11 && last_day,
+        }
+    }
+
+    // Months in which `period` can fall, indexed from January.
+    pub(crate) fn months(&self, period: FiscalPeriod) -> [bool; 12] {
+        let mut months = [false; 12];
+        for (i, allowed) in months.iter_mut().enumerate() {
+            let into_year = (i + 12 - self.start as usize) % 12;
+            *allowed = match period {
+                FiscalPeriod::QuarterStart => into_year.is_multiple_of(3),
+                FiscalPeriod::QuarterEnd => into_year % 3 == 2,
+                FiscalPeriod::YearStart => into_year == 0,
+                FiscalPeriod::YearEnd => into_year == 11,
+            };
+        }
+        months
+    }
+
+    // Whole months from the start of the fiscal year to `date`'s month.
+    fn months_in(&self, date: Date) -> u8 {
+        (date.month + 11 - self.start as u8) % 12
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn april_start() {
+        let fy = FiscalYear::starting(Month::APR);
+        assert_eq!(fy.year_of(date(2025, 3, 31)), 2025);
+        assert_eq!(fy.year_of(date(2025, 4, 1)), 2026);
+        assert_eq!(fy.quarter_of(date(2025, 4, 1)), 1);
+        assert_eq!(fy.quarter_of(date(2025, 12, 31)), 3);
+        assert_eq!(fy.quarter_of(date(2026, 3, 1)), 4);
+        assert_eq!(fy.quarter_start(2026, 1), Some(date(2025, 4, 1)));
+        assert_eq!(fy.quarter_start(2026, 4), Some(date(2026, 1, 1)));
+        assert_eq!(fy.quarter_end(2026, 4), Some(date(2026, 3, 31)));
+        assert_eq!(fy.quarter_start(2026, 5), None);
+    }
+
+    #[test]
+    fn calendar_year_by_default() {
+        let fy = FiscalYear::default();
+        assert_eq!(fy.year_of(date(2025, 12, 31)), 2025);
+        assert_eq!(fy.quarter_start(2025, 3), Some(date(2025, 7, 1)));
+        assert_eq!(fy.quarter_end(2025, 1), Some(date(2025, 3, 31)));
+        assert!(fy.contains(FiscalPeriod::YearEnd, date(2025, 12, 31)));
+        assert!(!fy.contains(FiscalPeriod::QuarterEnd, date(2025, 3, 30)));
+    }
+}
@@ -0,0 +1,202 @@
+// Conversions from jiff date-times, matching the chrono ones for projects
+// that use jiff instead, and a Runtime clock in a named time zone.
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use jiff::civil;
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+
+use crate::scheduler::Clock;
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+// Seconds and below are dropped.
+impl From<civil::DateTime> for DateTime {
+    fn from(dt: civil::DateTime) -> Self {
+        DateTime {
+            year: dt.year() as i32,
+            month: dt.month() as u8,
+            day: dt.day() as u8,
+            hour: dt.hour() as u8,
+            minute: dt.minute() as u8,
+        }
+    }
+}
+
+impl From<civil::DateTime> for Schedule {
+    fn from(dt: civil::DateTime) -> Self {
+        Schedule::from_datetime(dt.into())
+    }
+}
+
+// Uses the wall-clock time in the date-time's own zone.
+impl From<&Zoned> for Schedule {
+    fn from(dt: &Zoned) -> Self {
+        Schedule::from(dt.datetime())
+    }
+}
+
+impl From<Zoned> for Schedule {
+    fn from(dt: Zoned) -> Self {
+        Schedule::from(&dt)
+    }
+}
+
+// Schedules are wall-clock patterns and know nothing of zones: they, and
+// their starting_from and until bounds, are read in whatever zone the
+// Runtime's clock keeps. SystemClock keeps UTC; this keeps `tz`, daylight
+// saving included, so "daily until 3 March" ends at local midnight there.
+// When the clocks go back, `now` holds at the latest time it gave until the
+// wall clock catches up, so the repeated hour doesn't run twice and the
+// Runtime never sees time go backwards.
+#[derive(Debug, Clone)]
+pub struct ZonedClock {
+    tz: TimeZone,
+    // The latest time `now` gave. Clones share it.
+    latest: Arc<Mutex<Option<DateTime>>>,
+}
+
+impl ZonedClock {
+    pub fn new(tz: TimeZone) -> Self {
+        Self {
+            tz,
+            latest: Arc::default(),
+        }
+    }
+
+    // The host's zone.
+    pub fn system() -> Self {
+        Self::new(TimeZone::system())
+    }
+
+    // `at` as this clock shows it, for bounds given in some other zone.
+    pub fn wall_clock(&self, at: &Zoned) -> DateTime {
+        at.with_time_zone(self.tz.clone()).datetime().into()
+    }
+
+    fn no_earlier(&self, wall: DateTime) -> DateTime {
+        let mut latest = self.latest.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = latest.map_or(wall, |l| l.max(wall));
+        *latest = Some(now);
+        now
+    }
+}
+
+impl Clock for ZonedClock {
+    fn now(&self) -> DateTime {
+        self.no_earlier(self.wall_clock(&Zoned::now()))
+    }
+
+    // A wall-clock time skipped by a daylight saving change is waited for as
+    // the instant it would have been; one that happens twice, the first time.
+    // Past jiff's ±9999 years there is nothing to convert: a time that long
+    // gone returns straight away, one that far off waits the usual second.
+    fn sleep_until(&self, at: DateTime) {
+        let local = i16::try_from(at.year).ok().and_then(|year| {
+            civil::DateTime::new(
+                year,
+                at.month as i8,
+                at.day as i8,
+                at.hour as i8,
+                at.minute as i8,
+                0,
+                0,
+            )
+            .ok()
+        });
+        let Some(local) = local else {
+            if at.year > 0 {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+            return;
+        };
+        let wait = local
+            .to_zoned(self.tz.clone())
+            .ok()
+            .and_then(|z| Duration::try_from(z.timestamp().duration_since(Timestamp::now())).ok())
+            .unwrap_or_default();
+        std::thread::sleep(wait.min(Duration::from_secs(1)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::tz::{Offset, TimeZone};
+
+    #[test]
+    fn civil_datetime_as_an_end_bound() {
+        let s = Schedule::new()
+            .daily()
+            .until_datetime(civil::date(2027, 3, 3).at(10, 0, 0, 0));
+        let last = s.upcoming(DateTime::new(2027, 3, 1, 0, 0).unwrap()).last();
+
+        assert_eq!(last, DateTime::new(2027, 3, 3, 0, 0));
+    }
+
+    #[test]
+    fn civil_datetime_to_one_shot() {
+        let s = Schedule::from(civil::date(2025, 9, 20).at(22, 0, 45, 0));
+
+        assert_eq!(s.spec().year, Some(2025));
+        assert_eq!(s.spec().day, Some(20));
+        assert_eq!(s.spec().hour, Some(22));
+        assert_eq!(s.spec().minute, Some(0));
+    }
+
+    #[test]
+    fn zoned_datetime_uses_local_wall_clock() {
+        let tz = TimeZone::fixed(Offset::from_seconds(5 * 3600 + 1800).unwrap());
+        let dt = civil::date(2025, 9, 20)
+            .at(22, 30, 0, 0)
+            .to_zoned(tz)
+            .unwrap();
+        let s = Schedule::from(dt);
+
+        assert_eq!(s.spec().hour, Some(22));
+        assert_eq!(s.spec().minute, Some(30));
+    }
+
+    #[test]
+    fn zoned_clock_keeps_its_zones_wall_clock() {
+        let tz = TimeZone::fixed(Offset::from_seconds(5 * 3600 + 1800).unwrap());
+        let clock = ZonedClock::new(tz);
+        let utc_midnight = civil::date(2025, 3, 3)
+            .at(0, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        assert_eq!(
+            clock.wall_clock(&utc_midnight),
+            DateTime::new(2025, 3, 3, 5, 30).unwrap()
+        );
+
+        let ahead = crate::scheduler::SystemClock.now().add_minutes(330);
+        let now = clock.now();
+        assert!(now == ahead || now == ahead.add_minutes(1), "{}", now);
+    }
+
+    #[test]
+    fn zoned_clock_holds_through_a_repeated_hour() {
+        let clock = ZonedClock::new(TimeZone::UTC);
+        let first = DateTime::new(2025, 11, 2, 1, 59).unwrap();
+        assert_eq!(clock.no_earlier(first), first);
+        // 01:00 again after the clocks went back.
+        assert_eq!(
+            clock.no_earlier(DateTime::new(2025, 11, 2, 1, 0).unwrap()),
+            first
+        );
+        let later = DateTime::new(2025, 11, 2, 2, 0).unwrap();
+        assert_eq!(clock.clone().no_earlier(later), later);
+        assert_eq!(clock.no_earlier(first), later);
+
+        // Long gone for jiff, so no wait and no panic.
+        clock.sleep_until(DateTime::new(-20_000, 1, 1, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn negative_year_is_kept() {
+        let dt = civil::date(-50, 1, 1).at(0, 0, 0, 0);
+        assert_eq!(Schedule::from(dt).spec().year, Some(-50));
+    }
+}
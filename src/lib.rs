@@ -7,16 +7,61 @@
 // - Repetition: 10 times, until 3rd of March etc.
 #![allow(dead_code)]
 
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "astro")]
+pub mod astro;
+pub mod builder;
+pub mod business;
+pub mod calendar;
+#[cfg(feature = "chrono")]
+mod chrono_support;
+pub mod compat;
+pub mod compiled;
+pub mod composite;
+pub mod conflict;
+#[cfg(feature = "cron")]
+mod cron_support;
+pub mod crontab;
+mod describe;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fiscal;
+#[cfg(feature = "jiff")]
+mod jiff_support;
+pub mod occurrence;
+#[cfg(feature = "panchang")]
+pub mod panchang;
+pub mod parse;
+pub mod presets;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod recurrence;
+pub mod scheduler;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+pub mod set;
+#[cfg(feature = "stream")]
+pub mod stream;
+#[cfg(any(test, feature = "proptest"))]
+pub mod testing;
 mod time;
 pub mod types;
 
 use crate::types::*;
+use std::time::Duration;
 
 impl Schedule {
     pub fn monthly(self) -> Schedule {
         self.every(FrequencyPattern::Frequency(Frequency::Monthly))
     }
 
+    // Every `n` months from January; see `every_months_from`.
+    pub fn every_months(self, n: u8) -> Schedule {
+        self.every_months_from(n, Month::JAN)
+    }
+
     pub fn weekly(self) -> Schedule {
         self.every(FrequencyPattern::Frequency(Frequency::Weekly))
     }
@@ -63,7 +108,7 @@ impl Schedule {
     }
 
     pub fn except_on_nthday(self, n: u8, day: Days) -> Schedule {
-        self.except(Except::NthDay((n,day)))
+        self.except(Except::NthDay((n, day)))
     }
 
     pub fn except_on_day(self, day: Days) -> Schedule {
@@ -84,14 +129,67 @@ impl Schedule {
         }
     }
 
-    pub fn repeat_until_date(self, n: u8, day: u8, month: Month) -> Schedule {
+    pub fn repeat_until_date(self, n: u32, day: u8, month: Month) -> Schedule {
         self.repeat(n).until(Some(day), Some(month), None, None)
     }
 
+    // Ends at exactly `dt`, keeping any repeat count; chrono and jiff
+    // date-times convert too.
+    pub fn until_datetime(mut self, dt: impl Into<DateTime>) -> Schedule {
+        let dt = dt.into();
+        self.repeat = self.repeat.or_until(EndDate {
+            year: Some(dt.year),
+            month: Month::from_u8(dt.month),
+            day: Some(dt.day),
+            hour: Some(dt.hour),
+            minute: Some(dt.minute),
+        });
+        self
+    }
+
+    // One-shot at exactly `dt`, however many years away.
+    pub fn from_datetime(dt: DateTime) -> Schedule {
+        Schedule::new()
+            .year(dt.year)
+            .date_with_time(dt.month, dt.day, dt.hour, dt.minute)
+    }
+
+    // One-shot at a full date and time, or None if that instant does not
+    // exist (Feb 29 outside a leap year, hour 24, ...).
+    pub fn once(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> Option<Schedule> {
+        DateTime::new(year, month, day, hour, minute).map(Schedule::from_datetime)
+    }
+
+    // One-shot `d` after whatever instant it is resolved against, truncated
+    // to whole minutes, e.g. "once, 2 hours from now".
+    pub fn after(d: Duration) -> Schedule {
+        Schedule {
+            delay: Some((d.as_secs() / 60) as u32),
+            ..Schedule::new()
+        }
+    }
+
+    // Pins a `Schedule::after` one-shot to a wall-clock instant, counting
+    // from `now`. Any other schedule is returned unchanged.
+    pub fn resolve(&self, now: DateTime) -> Schedule {
+        match self.delay {
+            Some(d) => Schedule {
+                offset: self.offset,
+                ..Schedule::from_datetime(now.add_minutes(d as i64))
+            },
+            None => self.clone(),
+        }
+    }
+
+    // One-shots have no frequency and fire at most once.
+    pub fn is_recurring(&self) -> bool {
+        self.frequency_or_daily().is_some()
+    }
+
     pub fn until_date(self, day: u8, month: u8) -> Schedule {
-        match Month::from_u8(month){
-            Some(m) => self.until(Some(day),Some(m),None,None),
-            None => self
+        match Month::from_u8(month) {
+            Some(m) => self.until(Some(day), Some(m), None, None),
+            None => self,
         }
     }
 }
@@ -105,19 +203,19 @@ mod integration_tests {
     fn basic_scheduler() {
         let schedule = Schedule::new().date_with_time(9, 20, 22, 00);
 
-        assert_eq!(get_day(&schedule), Some(20));
-        assert_eq!(get_hour(&schedule), Some(22));
-        assert_eq!(get_month(&schedule), Some(Month::SEP));
+        assert_eq!(schedule.spec().day, Some(20));
+        assert_eq!(schedule.spec().hour, Some(22));
+        assert_eq!(schedule.spec().month, Some(Month::SEP));
     }
 
     // Monthly schedule on 20th at 10:30 PM (22:30)
     #[test]
     fn recurring_schedule_monthly_on_date() {
         let schedule = Schedule::new().day_with_time(20, 22, 30).monthly();
-        assert_eq!(get_day(&schedule), Some(20));
-        assert_eq!(get_hour(&schedule), Some(22));
+        assert_eq!(schedule.spec().day, Some(20));
+        assert_eq!(schedule.spec().hour, Some(22));
         assert_eq!(
-            get_frequency(&schedule).unwrap(),
+            schedule.frequency().unwrap(),
             FrequencyPattern::Frequency(Frequency::Monthly)
         );
     }
@@ -128,7 +226,7 @@ mod integration_tests {
         let s = Schedule::new().daily();
 
         assert_eq!(
-            get_frequency(&s).unwrap(),
+            s.frequency().unwrap(),
             FrequencyPattern::Frequency(Frequency::Daily)
         );
     }
@@ -138,7 +236,7 @@ mod integration_tests {
         let s = Schedule::new().monthly();
 
         assert_eq!(
-            get_frequency(&s).unwrap(),
+            s.frequency().unwrap(),
             FrequencyPattern::Frequency(Frequency::Monthly)
         );
     }
@@ -149,7 +247,7 @@ mod integration_tests {
         let s = Schedule::new().every_nth_day(3, Days::SAT);
 
         assert_eq!(
-            get_frequency(&s).unwrap(),
+            s.frequency().unwrap(),
             FrequencyPattern::ByDay((Some(3), Days::SAT))
         );
     }
@@ -160,7 +258,7 @@ mod integration_tests {
         let s = Schedule::new().every_on_day(Days::SAT);
 
         assert_eq!(
-            get_frequency(&s).unwrap(),
+            s.frequency().unwrap(),
             FrequencyPattern::ByDay((None, Days::SAT))
         );
     }
@@ -171,10 +269,10 @@ mod integration_tests {
         let s = Schedule::new().every_on_day(Days::SAT).except_on_date(3);
 
         assert_eq!(
-            get_frequency(&s).unwrap(),
+            s.frequency().unwrap(),
             FrequencyPattern::ByDay((None, Days::SAT))
         );
-        assert_eq!(get_except(&s).unwrap(), Except::N(3));
+        assert_eq!(s.spec().except.unwrap(), Except::N(3));
     }
 
     // - Random intervals, eg: between 9-10 am
@@ -182,7 +280,7 @@ mod integration_tests {
     fn schedule_between() {
         let s = Schedule::new().between((9, 0), (10, 0));
         assert_eq!(
-            get_range(&s),
+            s.range(),
             Some((
                 Time { hour: 9, minute: 0 },
                 Time {
@@ -193,14 +291,39 @@ mod integration_tests {
         );
     }
 
+    #[test]
+    fn from_datetime_is_one_shot() {
+        let dt = DateTime::new(2025, 9, 20, 22, 0).unwrap();
+        let s = Schedule::from_datetime(dt);
+
+        assert_eq!(s.spec().year, Some(2025));
+        assert_eq!(s.frequency(), None);
+        let from = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        assert_eq!(s.upcoming(from).collect::<Vec<_>>(), vec![dt]);
+    }
+
+    #[test]
+    fn once_validates_the_instant() {
+        let s = Schedule::once(2028, 2, 29, 9, 30).unwrap();
+
+        assert!(!s.is_recurring());
+        assert!(Schedule::new().daily().is_recurring());
+        let from = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        assert_eq!(
+            s.upcoming(from).collect::<Vec<_>>(),
+            vec![DateTime::new(2028, 2, 29, 9, 30).unwrap()]
+        );
+        assert_eq!(Schedule::once(2027, 2, 29, 9, 30), None);
+        assert_eq!(Schedule::once(2027, 3, 1, 24, 0), None);
+    }
+
     // - Repetition: 10 times, until 3rd of March etc.
     #[test]
     fn until_sets_day_month() {
         let s = Schedule::new().repeat_until_date(10, 3, Month::MAR);
 
-        let repeat = get_repeat(&s).unwrap();
-        assert_eq!(repeat.total, 10);
-        assert_eq!(repeat.day, Some(3));
-        assert_eq!(repeat.month, Some(Month::MAR));
+        let repeat = s.spec().repeat;
+        assert_eq!(repeat.count(), Some(10));
+        assert_eq!(repeat.end_date(), Some(EndDate::on(3, Month::MAR)));
     }
 }
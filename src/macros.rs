@@ -0,0 +1,138 @@
+// Declarative DSL on top of the typestate builder.
+//
+//     schedule! { every weekly on SAT at 10:30 except month DEC }
+//
+// Each clause expands to one builder call, so unknown clauses and setting the
+// same slot twice are rejected at compile time.
+//
+// Clauses:
+//     every hourly | daily | weekly | monthly
+//     every weekly on <DAY>          every <DAY>          every <n> <DAY>
+//     on day <n>                     on <MONTH> <day>
+//     at <h>:<m>                     between <h>:<m> and <h>:<m>
+//     except month <MONTH> | day <DAY> | date <n> | <n> <DAY>
+//     repeat <n> times               until <day> <MONTH>
+#[macro_export]
+macro_rules! schedule {
+    ($($clauses:tt)*) => {
+        $crate::__schedule!(@build $crate::types::Schedule::builder(); $($clauses)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __schedule {
+    (@build $b:expr;) => {
+        $b.build()
+    };
+    (@build $b:expr; every weekly on $d:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.every_on_day($crate::types::Days::$d); $($rest)*)
+    };
+    (@build $b:expr; every hourly $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.hourly(); $($rest)*)
+    };
+    (@build $b:expr; every daily $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.daily(); $($rest)*)
+    };
+    (@build $b:expr; every weekly $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.weekly(); $($rest)*)
+    };
+    (@build $b:expr; every monthly $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.monthly(); $($rest)*)
+    };
+    (@build $b:expr; every $n:literal $d:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.every_nth_day($n, $crate::types::Days::$d); $($rest)*)
+    };
+    (@build $b:expr; every $d:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.every_on_day($crate::types::Days::$d); $($rest)*)
+    };
+    (@build $b:expr; on day $n:literal $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.on_day($n); $($rest)*)
+    };
+    (@build $b:expr; on $m:ident $d:literal $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.date($crate::types::Month::$m as u8 + 1, $d); $($rest)*)
+    };
+    (@build $b:expr; at $h:literal : $m:literal $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.at($h, $m); $($rest)*)
+    };
+    (@build $b:expr; between $h1:literal : $m1:literal and $h2:literal : $m2:literal $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.between(($h1, $m1), ($h2, $m2)); $($rest)*)
+    };
+    (@build $b:expr; except month $m:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.except($crate::types::Except::Month($crate::types::Month::$m)); $($rest)*)
+    };
+    (@build $b:expr; except day $d:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.except($crate::types::Except::Day($crate::types::Days::$d)); $($rest)*)
+    };
+    (@build $b:expr; except date $n:literal $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.except($crate::types::Except::N($n)); $($rest)*)
+    };
+    (@build $b:expr; except $n:literal $d:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.except($crate::types::Except::NthDay(($n, $crate::types::Days::$d))); $($rest)*)
+    };
+    (@build $b:expr; repeat $n:literal times $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.repeat($n); $($rest)*)
+    };
+    (@build $b:expr; until $d:literal $m:ident $($rest:tt)*) => {
+        $crate::__schedule!(@build $b.until(Some($d), Some($crate::types::Month::$m), None, None); $($rest)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::*;
+
+    #[test]
+    fn weekly_on_day_at_time_except_month() {
+        let s = schedule! { every weekly on SAT at 10:30 except month DEC };
+
+        assert_eq!(
+            s.frequency(),
+            Some(FrequencyPattern::ByDay((None, Days::SAT)))
+        );
+        assert_eq!(s.spec().hour, Some(10));
+        assert_eq!(s.spec().except, Some(Except::Month(Month::DEC)));
+    }
+
+    #[test]
+    fn nth_day_with_date_and_repeat() {
+        let s = schedule! { every 3 SAT at 22:00 repeat 10 times until 3 MAR };
+
+        assert_eq!(
+            s.frequency(),
+            Some(FrequencyPattern::ByDay((Some(3), Days::SAT)))
+        );
+        assert_eq!(
+            s.spec().repeat,
+            Repeat::CountOrUntil {
+                count: 10,
+                until: EndDate::on(3, Month::MAR),
+            }
+        );
+    }
+
+    #[test]
+    fn one_shot_date() {
+        let s = schedule! { on SEP 20 at 22:00 };
+
+        assert_eq!(s.spec().month, Some(Month::SEP));
+        assert_eq!(s.spec().day, Some(20));
+        assert_eq!(s.frequency(), None);
+    }
+
+    #[test]
+    fn time_window() {
+        let s = schedule! { every hourly between 9:00 and 17:00 };
+
+        assert_eq!(
+            s.range(),
+            Some((
+                Time { hour: 9, minute: 0 },
+                Time {
+                    hour: 17,
+                    minute: 0
+                }
+            ))
+        );
+    }
+}
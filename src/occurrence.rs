@@ -0,0 +1,1502 @@
+// Occurrence engine: walks the calendar one day at a time from a starting
+// instant and yields every minute a Schedule fires at.
+//
+// Unset fields coarser than the frequency are wildcards, finer ones default
+// to their first value (cron's @hourly/@daily/@weekly/@monthly), so `weekly()`
+// fires Sundays at 00:00 and `monthly()` the 1st at 00:00. A schedule without
+// a frequency is a one-shot and fires once.
+use crate::calendar::{iso_week, weekday_ordinal};
+use crate::time::{DateTime, days_in_month, is_leap_year};
+use crate::types::*;
+
+// Nothing that fails to match within one 400 year Gregorian cycle ever will.
+const SEARCH_DAYS: u32 = 146_097;
+
+pub struct Occurrences<'a> {
+    schedule: &'a Schedule,
+    plan: Plan,
+    state: Progress,
+}
+
+// What a schedule expands to once, instead of on every step of a walk: the
+// times of day it fires at and the months it can fire in at all. Copy and
+// fixed-size, so walks never allocate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Plan {
+    times: TimesOfDay,
+    months: [bool; 12],
+    // Plain hourly/daily: every day matches, so no calendar search is needed.
+    every_day: bool,
+    // Replaces `hours` and `minute` with a time worked out per day.
+    #[cfg(feature = "astro")]
+    solar: Option<Solar>,
+}
+
+// The minutes of the day a schedule fires at, one bit each.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TimesOfDay([u64; 23]);
+
+impl TimesOfDay {
+    fn insert(&mut self, t: Time) {
+        let n = t.hour as usize * 60 + t.minute as usize;
+        self.0[n / 64] |= 1 << (n % 64);
+    }
+
+    // The first time at or after `n` minutes past midnight.
+    fn first_from(&self, n: usize) -> Option<Time> {
+        let mut word = n / 64;
+        let mut bits = *self.0.get(word)? & (!0 << (n % 64));
+        while bits == 0 {
+            word += 1;
+            bits = *self.0.get(word)?;
+        }
+        let n = word * 64 + bits.trailing_zeros() as usize;
+        Time::new((n / 60) as u8, (n % 60) as u8)
+    }
+
+    // Earliest first.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Time> + '_ {
+        let mut from = 0;
+        std::iter::from_fn(move || {
+            let t = self.first_from(from)?;
+            from = t.hour as usize * 60 + t.minute as usize + 1;
+            Some(t)
+        })
+    }
+}
+
+// Where an occurrence walk stands, kept apart from the borrowed schedule so
+// long-lived owners (the Scheduler) can store it and resume later.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Progress {
+    cursor: Option<DateTime>,
+    remaining: Option<u32>,
+    end: Option<DateTime>,
+    // The cursor walks the unshifted pattern; this is added on the way out.
+    offset: i64,
+    // Set for relative one-shots, which fire here instead of on a pattern.
+    pinned: Option<DateTime>,
+    // The starting instant, until `and_run_immediately` has fired there.
+    immediate: Option<DateTime>,
+}
+
+// How many more times a schedule fires.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Remaining {
+    Unbounded,
+    Exactly(u64),
+}
+
+impl Occurrences<'_> {
+    pub(crate) fn progress(&self) -> Progress {
+        self.state
+    }
+
+    // What is left of the walk. A repeat count on a pattern that never runs
+    // out is the answer as it stands; the occurrences are only walked when an
+    // end date, a year or a one-shot bounds them.
+    pub fn remaining(&self) -> Remaining {
+        let st = self.state;
+        let endless = self.schedule.frequency_or_daily().is_some()
+            && self.schedule.year.is_none()
+            && self.schedule.years.is_empty()
+            && st.end.is_none();
+        match (endless, st.remaining) {
+            _ if st.cursor.is_none() && st.immediate.is_none() => Remaining::Exactly(0),
+            (true, None) => Remaining::Unbounded,
+            (true, Some(n)) => Remaining::Exactly(n as u64),
+            (false, _) => Remaining::Exactly(
+                Occurrences {
+                    schedule: self.schedule,
+                    plan: self.plan,
+                    state: st,
+                }
+                .count() as u64,
+            ),
+        }
+    }
+}
+
+impl Progress {
+    // Moves the walk on to `from`, as if what lies before had never been
+    // there: `unused`, the last occurrence taken from the walk, is handed
+    // back to the repeat count if it is skipped too.
+    pub(crate) fn skip_to(&mut self, from: DateTime, unused: Option<DateTime>) {
+        if unused.is_some_and(|at| at < from)
+            && let Some(r) = self.remaining.as_mut()
+        {
+            *r += 1;
+        }
+        let from = from.add_minutes(-self.offset);
+        self.cursor = self.cursor.map(|c| c.max(from));
+        self.immediate = self
+            .immediate
+            .filter(|at| at.add_minutes(-self.offset) >= from);
+    }
+
+    // Takes a run made outside the pattern off the repeat count. False when
+    // the count had nothing left beyond the occurrence already produced,
+    // which that run then stands in for.
+    pub(crate) fn use_one(&mut self) -> bool {
+        match self.remaining.as_mut() {
+            Some(0) => false,
+            Some(n) => {
+                *n -= 1;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        let st = &mut self.state;
+        if st.remaining == Some(0) {
+            return None;
+        }
+        if let Some(at) = st.immediate.take() {
+            let unshifted = at.add_minutes(-st.offset);
+            if st.end.is_some_and(|end| unshifted > end) {
+                st.cursor = None;
+                return None;
+            }
+            // The pattern must not fire a second time at the same instant.
+            st.cursor = Some(unshifted.next_minute());
+            if let Some(r) = st.remaining.as_mut() {
+                *r -= 1;
+            }
+            return Some(at);
+        }
+        let found = st
+            .cursor
+            .and_then(|from| match st.pinned {
+                Some(at) => (from <= at).then_some(at),
+                None => self.schedule.first_match(&self.plan, from),
+            })
+            .filter(|at| st.end.is_none_or(|end| *at <= end));
+        match found {
+            Some(at) => {
+                st.cursor = Some(at.next_minute());
+                if let Some(r) = st.remaining.as_mut() {
+                    *r -= 1;
+                }
+                // Pass over the matches in between now, so a saved Progress
+                // resumes in step.
+                for _ in 1..self.schedule.stride.unwrap_or(1) {
+                    st.cursor = st
+                        .cursor
+                        .and_then(|from| self.schedule.first_match(&self.plan, from))
+                        .map(|at| at.next_minute());
+                }
+            }
+            None => st.cursor = None,
+        }
+        found.map(|at| at.add_minutes(st.offset))
+    }
+}
+
+impl Schedule {
+    // Occurrences at or after `from`. Repeat counts are counted from `from`,
+    // and so is the delay of a `Schedule::after` one-shot.
+    pub fn upcoming(&self, from: DateTime) -> Occurrences<'_> {
+        self.resume(self.start(from))
+    }
+
+    // How many times the schedule fires from `now` on, with repeat counts
+    // starting at `now` as in `upcoming`.
+    pub fn remaining_occurrences(&self, now: DateTime) -> Remaining {
+        self.upcoming(now).remaining()
+    }
+
+    // Occurrences in [from, to), counted the same way as `upcoming`.
+    pub fn occurrences_between(
+        &self,
+        from: DateTime,
+        to: DateTime,
+    ) -> impl Iterator<Item = DateTime> + '_ {
+        self.upcoming(from).take_while(move |at| *at < to)
+    }
+
+    pub(crate) fn start(&self, from: DateTime) -> Progress {
+        let offset = self.offset.unwrap_or(0) as i64;
+        let from = self.not_before.map_or(from, |start| from.max(start));
+        let from = from.add_minutes(-offset);
+        let mut remaining = self.repeat.count();
+        if self.frequency_or_daily().is_none() {
+            remaining = Some(remaining.map_or(1, |r| r.min(1)));
+        }
+        Progress {
+            cursor: Some(from),
+            remaining,
+            end: self.repeat.end_date().and_then(|d| until_bound(&d, from)),
+            offset,
+            pinned: self.delay.map(|d| from.add_minutes(d as i64)),
+            immediate: self.immediate.then(|| from.add_minutes(offset)),
+        }
+    }
+
+    // Picks a walk back up where `Occurrences::progress` left it.
+    pub(crate) fn resume(&self, state: Progress) -> Occurrences<'_> {
+        Occurrences {
+            schedule: self,
+            plan: self.plan(),
+            state,
+        }
+    }
+
+    // Same as `resume`, reusing a plan made earlier for this schedule.
+    pub(crate) fn resume_with(&self, plan: Plan, state: Progress) -> Occurrences<'_> {
+        Occurrences {
+            schedule: self,
+            plan,
+            state,
+        }
+    }
+
+    pub(crate) fn plan(&self) -> Plan {
+        let mut months = [true; 12];
+        for (i, allowed) in months.iter_mut().enumerate() {
+            let month = Month::from_u8(i as u8 + 1);
+            *allowed = self.month.is_none_or(|m| Some(m) == month)
+                && (self.dates.is_empty() || self.dates.iter().any(|(m, _)| Some(*m) == month))
+                && self.in_month_step(i as u8 + 1)
+                && !matches!(self.recurring.except, Some(Except::Month(m)) if Some(m) == month);
+        }
+        if let Some(period) = self.fiscal {
+            let fiscal = self.fiscal_year.months(period);
+            for (allowed, fiscal) in months.iter_mut().zip(fiscal) {
+                *allowed &= fiscal;
+            }
+        }
+        if self.leap_day_policy() == Some(LeapDay::Mar1) {
+            months[2] = months[1];
+        }
+        if self.business_days.is_some() {
+            // Rolling and offsetting can cross into any month.
+            months = [true; 12];
+        }
+        let every_day = matches!(
+            self.frequency_or_daily(),
+            Some(FrequencyPattern::Frequency(
+                Frequency::Hourly | Frequency::Daily
+            ))
+        ) && self.year.is_none()
+            && self.years.is_empty()
+            && self.month.is_none()
+            && !self.pins_day()
+            && self.recurring.except.is_none()
+            && self.weekdays.is_empty()
+            && self.business_days.is_none()
+            && self.week_parity.is_none()
+            && !self.follows_sun();
+        Plan {
+            times: self.times_of_day(),
+            months,
+            every_day,
+            #[cfg(feature = "astro")]
+            solar: self.solar,
+        }
+    }
+
+    pub fn next_after(&self, after: DateTime) -> Option<DateTime> {
+        self.upcoming(after.next_minute()).next()
+    }
+
+    fn first_match(&self, plan: &Plan, from: DateTime) -> Option<DateTime> {
+        let mut day = DateTime {
+            hour: 0,
+            minute: 0,
+            ..from
+        };
+        if plan.every_day {
+            // Later today, or else the first time tomorrow.
+            return plan
+                .time_on(day, from)
+                .or_else(|| plan.time_on(day.next_day(), from));
+        }
+        for _ in 0..SEARCH_DAYS {
+            if let Some(y) = self.year {
+                if day.year > y {
+                    return None;
+                }
+                if day.year < y {
+                    day = DateTime::new(y, 1, 1, 0, 0)?;
+                }
+            }
+            if !self.years.is_empty() && !self.years.contains(&day.year) {
+                let y = self.years.iter().find(|y| **y > day.year)?;
+                day = DateTime::new(*y, 1, 1, 0, 0)?;
+            }
+            if !plan.months[day.month as usize - 1] {
+                // Skip the rest of a month that can never fire.
+                day = DateTime {
+                    day: days_in_month(day.year, day.month),
+                    ..day
+                }
+                .next_day();
+                continue;
+            }
+            if self.day_matches(&day, day.weekday())
+                && let Some(at) = plan.time_on(day, from)
+            {
+                return Some(at);
+            }
+            day = day.next_day();
+        }
+        None
+    }
+
+    fn day_matches(&self, date: &DateTime, weekday: Days) -> bool {
+        match self.business_days {
+            Some(n) => self.business_day_matches(date.date(), n),
+            None => self.pattern_matches(date, weekday),
+        }
+    }
+
+    // Whether the pattern picks `date`, before any business-day offset.
+    pub(crate) fn pattern_matches(&self, date: &DateTime, weekday: Days) -> bool {
+        let nth = weekday_ordinal(date.date());
+        let on_date = match self.leap_day_policy() {
+            Some(policy) if !is_leap_year(date.year) => match policy {
+                LeapDay::Mar1 => (date.month, date.day) == (3, 1),
+                _ => (date.month, date.day) == (2, 28),
+            },
+            _ => {
+                self.month.is_none_or(|m| date.month == m as u8 + 1)
+                    && self.day.is_none_or(|d| match self.month_step {
+                        Some(_) => date.day == d.min(days_in_month(date.year, date.month)),
+                        None => date.day == d,
+                    })
+                    && (self.dates.is_empty()
+                        || self
+                            .dates
+                            .iter()
+                            .any(|(m, d)| (*m as u8 + 1, *d) == (date.month, date.day)))
+                    && (self.weekdays.is_empty() || self.weekdays.contains(weekday))
+                    && self
+                        .week_parity
+                        .is_none_or(|p| Parity::of(iso_week(date.date()).1) == p)
+                    && self.day_of_year.is_none_or(|n| date.date().ordinal() == n)
+                    && self
+                        .fiscal
+                        .is_none_or(|p| self.fiscal_year.contains(p, date.date()))
+                    && self.on_lunar_day(date.date())
+            }
+        };
+        if !on_date {
+            return false;
+        }
+        let fires = match self.recurring.frequency {
+            Some(FrequencyPattern::Frequency(Frequency::Weekly)) => {
+                !self.weekdays.is_empty() || weekday == Days::SUN
+            }
+            Some(FrequencyPattern::Frequency(Frequency::Monthly)) => {
+                self.pins_day() || date.day == 1
+            }
+            Some(FrequencyPattern::ByDay((n, d))) => weekday == d && n.is_none_or(|n| n == nth),
+            _ => true,
+        };
+        let excluded = match self.recurring.except {
+            Some(Except::Day(d)) => weekday == d,
+            Some(Except::N(n)) => date.day == n,
+            Some(Except::NthDay((n, d))) => weekday == d && nth == n,
+            Some(Except::Month(m)) => date.month == m as u8 + 1,
+            None => false,
+        };
+        fires && !excluded
+    }
+
+    #[cfg(feature = "panchang")]
+    fn on_lunar_day(&self, date: Date) -> bool {
+        self.lunar.is_none_or(|l| l.falls_on(date))
+    }
+
+    #[cfg(not(feature = "panchang"))]
+    fn on_lunar_day(&self, _date: Date) -> bool {
+        true
+    }
+
+    // Times of day this schedule fires at on a matching day, before the
+    // window is applied: a mask of hours and a mask of the minutes past each.
+    pub(crate) fn fire_times(&self) -> (u32, u64) {
+        let (default_hour, default_minute) = match self.range {
+            Some((start, _)) if self.hour.is_none() && self.minute.is_none() => {
+                (start.hour, start.minute)
+            }
+            _ => (0, 0),
+        };
+        let minutes = match self.minute_step {
+            Some(n) => (0..60)
+                .step_by(n as usize)
+                .fold(0, |mask, m| mask | (1 << m)),
+            None => 1 << self.minute.unwrap_or(default_minute),
+        };
+        let hourly =
+            self.recurring.frequency == Some(FrequencyPattern::Frequency(Frequency::Hourly));
+        let hours = match (self.hour_step, self.hour) {
+            (Some(n), _) => (0..24)
+                .step_by(n as usize)
+                .fold(0, |mask, h| mask | (1 << h)),
+            (None, None) if hourly || self.minute_step.is_some() => (1 << 24) - 1,
+            (None, h) => 1 << h.unwrap_or(default_hour),
+        };
+        (hours, minutes)
+    }
+
+    // Every time of day it fires at on a matching day, inside the window.
+    pub(crate) fn times_of_day(&self) -> TimesOfDay {
+        let mut times = TimesOfDay::default();
+        if let Some(n) = self.interval {
+            let start = self.range.map_or(Time::MIDNIGHT, |(start, _)| start);
+            for k in 0..(24 * 60u16).div_ceil(n) {
+                let t = start.add_minutes((k * n) as i64);
+                if self.in_range((t.hour, t.minute)) {
+                    times.insert(t);
+                }
+            }
+        } else if self.times.is_empty() {
+            let (hours, minutes) = self.fire_times();
+            for hour in (0..24).filter(|h| hours & (1 << h) != 0) {
+                for minute in (0..60).filter(|m| minutes & (1 << m) != 0) {
+                    if self.in_range((hour, minute)) {
+                        times.insert(Time { hour, minute });
+                    }
+                }
+            }
+        } else {
+            for t in self
+                .times
+                .iter()
+                .filter(|t| self.in_range((t.hour, t.minute)))
+            {
+                times.insert(*t);
+            }
+        }
+        times
+    }
+
+    // Windows that wrap past midnight (22:00–02:00) are supported.
+    fn in_range(&self, t: (u8, u8)) -> bool {
+        match self.range {
+            Some((start, end)) => {
+                let (start, end) = ((start.hour, start.minute), (end.hour, end.minute));
+                if start <= end {
+                    start <= t && t <= end
+                } else {
+                    t >= start || t <= end
+                }
+            }
+            None => true,
+        }
+    }
+}
+
+impl Plan {
+    // The first time this fires on `day` at or after `from`.
+    fn time_on(&self, day: DateTime, from: DateTime) -> Option<DateTime> {
+        #[cfg(feature = "astro")]
+        if let Some(solar) = self.solar {
+            let date = day.date();
+            return solar
+                .time_on(date)
+                .map(|t| date.at(t))
+                .filter(|at| *at >= from);
+        }
+        let after = match day.date() == from.date() {
+            true => from.hour as usize * 60 + from.minute as usize,
+            false if day < from => return None,
+            false => 0,
+        };
+        self.times.first_from(after).map(|t| DateTime {
+            hour: t.hour,
+            minute: t.minute,
+            ..day
+        })
+    }
+}
+
+// next_after for many schedules at once: the calendar is walked a single
+// time, with each day's weekday worked out once for all of them. Answers
+// come back in the order of `schedules`.
+pub fn next_occurrences(schedules: &[Schedule], after: DateTime) -> Vec<Option<DateTime>> {
+    let from = after.next_minute();
+    let mut found = vec![None; schedules.len()];
+    // Schedules still looking, with their plan and until bound.
+    let mut pending = Vec::new();
+    for (i, s) in schedules.iter().enumerate() {
+        let start = s.start(from);
+        let own_walk = s.offset.is_some() || s.delay.is_some() || s.immediate;
+        let later_year = s.year.is_some_and(|y| y > from.year) || !s.years.is_empty();
+        if own_walk || later_year || s.stride.is_some() || s.not_before.is_some() {
+            // Shifted, anchored on the query or jumping ahead to a later
+            // year or between listed ones; walked on their own.
+            found[i] = s.next_after(after);
+        } else if start.remaining != Some(0) {
+            pending.push((i, s.plan(), start.end));
+        }
+    }
+    let mut day = DateTime {
+        hour: 0,
+        minute: 0,
+        ..from
+    };
+    for _ in 0..SEARCH_DAYS {
+        if pending.is_empty() {
+            break;
+        }
+        let weekday = day.weekday();
+        pending.retain(|(i, plan, end)| {
+            let s = &schedules[*i];
+            let year = s.year;
+            if end.is_some_and(|end| day > end) || year.is_some_and(|y| day.year > y) {
+                return false;
+            }
+            if year.is_some_and(|y| day.year != y)
+                || !plan.months[day.month as usize - 1]
+                || !s.day_matches(&day, weekday)
+            {
+                return true;
+            }
+            match plan.time_on(day, from) {
+                Some(at) => {
+                    found[*i] = Some(at).filter(|at| end.is_none_or(|end| *at <= end));
+                    false
+                }
+                None => true,
+            }
+        });
+        day = day.next_day();
+    }
+    found
+}
+
+// Without a year the bound is the first matching instant at or after `from`.
+// A missing time means the end of that day, a missing day the end of the
+// month.
+fn until_bound(until: &EndDate, from: DateTime) -> Option<DateTime> {
+    let hour = until.hour.unwrap_or(23);
+    let minute = until.minute.unwrap_or(59);
+    if let Some(year) = until.year {
+        let month = until.month.map_or(12, |m| m as u8 + 1);
+        let day = until.day.unwrap_or(31).min(days_in_month(year, month));
+        return DateTime::new(year, month, day, hour, minute);
+    }
+    if until.day.is_none() && until.month.is_none() {
+        if until.hour.is_none() && until.minute.is_none() {
+            return None;
+        }
+        let today = DateTime {
+            hour,
+            minute,
+            ..from
+        };
+        return Some(if today >= from {
+            today
+        } else {
+            DateTime {
+                hour,
+                minute,
+                ..from.next_day()
+            }
+        });
+    }
+    let (mut year, mut month) = (from.year, from.month);
+    // Eight years of months covers a Feb 29 bound.
+    for _ in 0..12 * 8 {
+        if until.month.is_none_or(|m| m as u8 + 1 == month) {
+            let day = until.day.unwrap_or(days_in_month(year, month));
+            if let Some(at) = DateTime::new(year, month, day, hour, minute)
+                && at >= from
+            {
+                return Some(at);
+            }
+        }
+        month += 1;
+        if month > 12 {
+            month = 1;
+            year += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn daily_at_time() {
+        let s = Schedule::new().at(9, 0).daily();
+        let next: Vec<_> = s.upcoming(dt(2025, 2, 27, 10, 0)).take(3).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 2, 28, 9, 0),
+                dt(2025, 3, 1, 9, 0),
+                dt(2025, 3, 2, 9, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn one_shot_fires_once() {
+        let s = Schedule::new().date_with_time(9, 20, 22, 0);
+        let next: Vec<_> = s.upcoming(dt(2025, 1, 1, 0, 0)).collect();
+
+        assert_eq!(next, vec![dt(2025, 9, 20, 22, 0)]);
+    }
+
+    #[test]
+    fn monthly_on_day() {
+        let s = Schedule::new().day_with_time(20, 22, 30).monthly();
+        let next: Vec<_> = s.upcoming(dt(2025, 1, 25, 0, 0)).take(2).collect();
+
+        assert_eq!(next, vec![dt(2025, 2, 20, 22, 30), dt(2025, 3, 20, 22, 30)]);
+    }
+
+    #[test]
+    fn every_third_saturday_except_month() {
+        let s = Schedule::new()
+            .every_nth_day(3, Days::SAT)
+            .except_on_month(Month::OCT);
+        let next: Vec<_> = s.upcoming(dt(2025, 9, 1, 0, 0)).take(2).collect();
+
+        assert_eq!(next, vec![dt(2025, 9, 20, 0, 0), dt(2025, 11, 15, 0, 0)]);
+    }
+
+    #[test]
+    fn weekly_defaults_to_sunday() {
+        let s = Schedule::new().weekly();
+        assert_eq!(
+            s.next_after(dt(2025, 9, 17, 12, 0)),
+            Some(dt(2025, 9, 21, 0, 0))
+        );
+    }
+
+    #[test]
+    fn hourly_inside_window() {
+        let s = Schedule::new().hourly().between((9, 30), (11, 30));
+        let next: Vec<_> = s.upcoming(dt(2025, 9, 1, 0, 0)).take(4).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 9, 1, 9, 30),
+                dt(2025, 9, 1, 10, 30),
+                dt(2025, 9, 1, 11, 30),
+                dt(2025, 9, 2, 9, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn repeat_count_and_until() {
+        let counted = Schedule::new().daily().repeat(3);
+        assert_eq!(counted.upcoming(dt(2025, 1, 1, 0, 0)).count(), 3);
+
+        let backfill = Schedule::new().hourly().repeat(1000);
+        let last = backfill.upcoming(dt(2025, 1, 1, 0, 0)).last();
+        assert_eq!(last, Some(dt(2025, 2, 11, 15, 0)));
+
+        let bounded =
+            Schedule::new()
+                .daily()
+                .repeat(100)
+                .until(Some(3), Some(Month::MAR), None, None);
+        let last = bounded.upcoming(dt(2025, 2, 25, 0, 0)).last();
+        assert_eq!(last, Some(dt(2025, 3, 3, 0, 0)));
+    }
+
+    #[test]
+    fn offset_shifts_after_matching() {
+        // 15 minutes after every hour boundary, across midnight.
+        let s = Schedule::new()
+            .hourly()
+            .offset_by(std::time::Duration::from_secs(15 * 60));
+        let next: Vec<_> = s.upcoming(dt(2025, 12, 31, 23, 20)).take(2).collect();
+
+        assert_eq!(next, vec![dt(2026, 1, 1, 0, 15), dt(2026, 1, 1, 1, 15)]);
+    }
+
+    #[test]
+    fn offset_one_shot_lands_on_next_day() {
+        let s = Schedule::new()
+            .date_with_time(3, 1, 23, 0)
+            .offset_by(std::time::Duration::from_secs(2 * 3600));
+        assert_eq!(
+            s.next_after(dt(2025, 1, 1, 0, 0)),
+            Some(dt(2025, 3, 2, 1, 0))
+        );
+    }
+
+    #[test]
+    fn after_fires_once_relative_to_from() {
+        let s = Schedule::after(std::time::Duration::from_secs(2 * 3600));
+        let next: Vec<_> = s.upcoming(dt(2025, 12, 31, 23, 30)).collect();
+
+        assert_eq!(next, vec![dt(2026, 1, 1, 1, 30)]);
+        assert_eq!(
+            s.resolve(dt(2025, 12, 31, 23, 30)),
+            Schedule::once(2026, 1, 1, 1, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn count_or_end_date_whichever_comes_first() {
+        let from = dt(2025, 3, 1, 0, 0);
+        let s = |n| {
+            Schedule::new()
+                .at(9, 0)
+                .daily()
+                .until(Some(5), Some(Month::MAR), None, None)
+                .repeat(n)
+        };
+        assert_eq!(s(2).upcoming(from).last(), Some(dt(2025, 3, 2, 9, 0)));
+        assert_eq!(s(10).upcoming(from).last(), Some(dt(2025, 3, 5, 9, 0)));
+
+        // An immediate run past the end date doesn't happen either.
+        let late = Schedule::new()
+            .daily()
+            .until_datetime(dt(2025, 3, 5, 0, 0))
+            .and_run_immediately();
+        assert_eq!(late.upcoming(dt(2025, 3, 6, 0, 0)).next(), None);
+    }
+
+    #[test]
+    fn an_end_date_with_a_year_spans_years() {
+        let s = Schedule::new()
+            .at(9, 0)
+            .day(1)
+            .monthly()
+            .until_datetime(dt(2027, 3, 3, 23, 59));
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 10, 0, 0)).collect();
+
+        assert_eq!(next.len(), 24);
+        assert_eq!(next.last(), Some(&dt(2027, 3, 1, 9, 0)));
+        assert_eq!(s.upcoming(dt(2027, 3, 4, 0, 0)).next(), None);
+    }
+
+    #[test]
+    fn an_end_date_alone_stops_the_schedule() {
+        let s = Schedule::new()
+            .at(9, 0)
+            .daily()
+            .ending(Repeat::until(EndDate::on(4, Month::MAR)));
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 1, 0, 0)).collect();
+
+        assert_eq!(next.len(), 4);
+        assert_eq!(next.last(), Some(&dt(2025, 3, 4, 9, 0)));
+    }
+
+    #[test]
+    fn every_other_working_day() {
+        let s = Schedule::new()
+            .at(9, 0)
+            .daily()
+            .except_on_day(Days::SUN)
+            .every_nth_occurrence(2)
+            .repeat(3);
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 7, 0, 0)).collect();
+
+        // Fri, Mon, Wed: Sunday never matches, so it isn't one of the skips.
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 3, 7, 9, 0),
+                dt(2025, 3, 10, 9, 0),
+                dt(2025, 3, 12, 9, 0)
+            ]
+        );
+
+        // Resuming from a saved Progress stays in step.
+        let mut walk = s.upcoming(dt(2025, 3, 7, 0, 0));
+        walk.next();
+        let resumed: Vec<_> = s.resume(walk.progress()).collect();
+        assert_eq!(resumed, next[1..]);
+    }
+
+    #[test]
+    fn nothing_fires_before_the_start_date() {
+        let s = Schedule::new()
+            .at(9, 0)
+            .daily()
+            .repeat(2)
+            .starting_from(dt(2025, 3, 10, 12, 0));
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 1, 0, 0)).collect();
+
+        assert_eq!(next, vec![dt(2025, 3, 11, 9, 0), dt(2025, 3, 12, 9, 0)]);
+        assert_eq!(
+            next_occurrences(&[s], dt(2025, 3, 1, 0, 0)),
+            vec![Some(dt(2025, 3, 11, 9, 0))]
+        );
+    }
+
+    #[test]
+    fn remaining_occurrences_without_walking_unbounded_ones() {
+        let now = dt(2025, 3, 1, 0, 0);
+        let daily = Schedule::new().at(9, 0).daily();
+        assert_eq!(daily.remaining_occurrences(now), Remaining::Unbounded);
+        assert_eq!(
+            daily.clone().repeat(1_000_000).remaining_occurrences(now),
+            Remaining::Exactly(1_000_000)
+        );
+        assert_eq!(
+            daily
+                .clone()
+                .repeat(10)
+                .ending_on(EndDate::on(3, Month::MAR))
+                .remaining_occurrences(now),
+            Remaining::Exactly(3)
+        );
+        assert_eq!(
+            Schedule::new()
+                .year(2025)
+                .day(1)
+                .monthly()
+                .remaining_occurrences(now),
+            Remaining::Exactly(10)
+        );
+        assert_eq!(
+            Schedule::once(2025, 2, 1, 9, 0)
+                .unwrap()
+                .remaining_occurrences(now),
+            Remaining::Exactly(0)
+        );
+
+        let thrice = daily.repeat(3);
+        let mut walk = thrice.upcoming(now);
+        walk.next();
+        assert_eq!(walk.remaining(), Remaining::Exactly(2));
+    }
+
+    #[test]
+    fn run_immediately_counts_towards_repeat() {
+        let s = Schedule::new()
+            .at(9, 0)
+            .daily()
+            .repeat(3)
+            .and_run_immediately();
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 1, 14, 5)).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 3, 1, 14, 5),
+                dt(2025, 3, 2, 9, 0),
+                dt(2025, 3, 3, 9, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn run_immediately_on_a_matching_instant_fires_once() {
+        let s = Schedule::new().hourly().and_run_immediately();
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 1, 9, 0)).take(2).collect();
+
+        assert_eq!(next, vec![dt(2025, 3, 1, 9, 0), dt(2025, 3, 1, 10, 0)]);
+    }
+
+    #[test]
+    fn occurrences_between_is_half_open() {
+        let s = Schedule::new().minute(30).hourly();
+        let from = dt(2024, 1, 1, 0, 0);
+        let to = dt(2025, 1, 1, 0, 30);
+
+        assert_eq!(s.occurrences_between(from, to).count(), 366 * 24);
+        assert_eq!(s.occurrences_between(to, to).count(), 0);
+    }
+
+    #[test]
+    fn batch_agrees_with_one_at_a_time() {
+        let schedules = vec![
+            Schedule::new().at(9, 0).daily(),
+            Schedule::new()
+                .every_nth_day(3, Days::SAT)
+                .except_on_month(Month::OCT),
+            Schedule::new().date(2, 29),
+            Schedule::new().year(2020).date(1, 1),
+            Schedule::new()
+                .daily()
+                .repeat(5)
+                .until(Some(3), Some(Month::MAR), None, None),
+            Schedule::new().hourly().between((22, 0), (2, 0)),
+            Schedule::new().month(12).on_day(25).monthly(),
+            Schedule::new()
+                .hourly()
+                .offset_by(std::time::Duration::from_secs(15 * 60)),
+            Schedule::new().daily().repeat(0),
+        ];
+        for after in [
+            dt(2025, 3, 3, 12, 0),
+            dt(2025, 9, 20, 22, 0),
+            dt(2027, 12, 31, 23, 59),
+        ] {
+            let one_by_one: Vec<_> = schedules.iter().map(|s| s.next_after(after)).collect();
+            assert_eq!(next_occurrences(&schedules, after), one_by_one);
+        }
+    }
+
+    #[test]
+    fn fast_path_agrees_with_calendar_search() {
+        let schedules = [
+            Schedule::new().at(9, 30).daily(),
+            Schedule::new().minute(45).hourly(),
+            Schedule::new().hourly().between((22, 0), (2, 0)),
+            Schedule::new().hourly().between((3, 0), (2, 0)).hour(5),
+        ];
+        for s in &schedules {
+            let fast = s.plan();
+            assert!(fast.every_day);
+            let slow = Plan {
+                every_day: false,
+                ..fast
+            };
+            for from in [
+                dt(2025, 12, 31, 23, 59),
+                dt(2024, 2, 28, 9, 30),
+                dt(2025, 6, 1, 2, 1),
+            ] {
+                assert_eq!(s.first_match(&fast, from), s.first_match(&slow, from));
+            }
+        }
+    }
+
+    #[test]
+    fn feb_29_waits_for_leap_year() {
+        let s = Schedule::new().date(2, 29);
+        assert_eq!(
+            s.next_after(dt(2025, 1, 1, 0, 0)),
+            Some(dt(2028, 2, 29, 0, 0))
+        );
+    }
+
+    #[test]
+    fn leap_day_policies() {
+        let s = Schedule::new().date_with_time(2, 29, 9, 0).daily();
+        let from = dt(2025, 1, 1, 0, 0);
+        let years = |s: &Schedule| s.upcoming(from).take(4).collect::<Vec<_>>();
+
+        assert_eq!(
+            years(&s.clone().on_leap_day(LeapDay::Feb28)),
+            vec![
+                dt(2025, 2, 28, 9, 0),
+                dt(2026, 2, 28, 9, 0),
+                dt(2027, 2, 28, 9, 0),
+                dt(2028, 2, 29, 9, 0),
+            ]
+        );
+        assert_eq!(
+            years(&s.clone().on_leap_day(LeapDay::Mar1)),
+            vec![
+                dt(2025, 3, 1, 9, 0),
+                dt(2026, 3, 1, 9, 0),
+                dt(2027, 3, 1, 9, 0),
+                dt(2028, 2, 29, 9, 0),
+            ]
+        );
+        assert_eq!(
+            years(&s)[..2],
+            [dt(2028, 2, 29, 9, 0), dt(2032, 2, 29, 9, 0)]
+        );
+
+        let s = s.on_leap_day(LeapDay::Mar1);
+        assert_eq!(
+            next_occurrences(std::slice::from_ref(&s), from),
+            vec![s.next_after(from)]
+        );
+    }
+
+    #[test]
+    fn day_of_year_shifts_in_leap_years() {
+        let s = Schedule::new().on_day_of_year(100).at(6, 0).daily();
+        let from = dt(2023, 1, 1, 0, 0);
+        assert_eq!(
+            s.upcoming(from).take(3).collect::<Vec<_>>(),
+            vec![
+                dt(2023, 4, 10, 6, 0),
+                dt(2024, 4, 9, 6, 0),
+                dt(2025, 4, 10, 6, 0),
+            ]
+        );
+
+        let last = Schedule::new().on_day_of_year(366).daily();
+        assert_eq!(last.next_after(from), Some(dt(2024, 12, 31, 0, 0)));
+        assert_eq!(
+            next_occurrences(std::slice::from_ref(&last), from),
+            vec![last.next_after(from)]
+        );
+    }
+
+    #[test]
+    fn fiscal_quarters() {
+        let s = Schedule::new()
+            .fiscal_year_starting(Month::APR)
+            .on_fiscal_quarter_start()
+            .at(8, 0)
+            .daily();
+        let from = dt(2025, 2, 1, 0, 0);
+        assert_eq!(
+            s.upcoming(from).take(4).collect::<Vec<_>>(),
+            vec![
+                dt(2025, 4, 1, 8, 0),
+                dt(2025, 7, 1, 8, 0),
+                dt(2025, 10, 1, 8, 0),
+                dt(2026, 1, 1, 8, 0),
+            ]
+        );
+
+        let s = Schedule::new()
+            .fiscal_year_starting(Month::JUL)
+            .on_fiscal_year_end()
+            .monthly();
+        assert_eq!(s.next_after(from), Some(dt(2025, 6, 30, 0, 0)));
+        assert_eq!(
+            next_occurrences(std::slice::from_ref(&s), from),
+            vec![s.next_after(from)]
+        );
+    }
+
+    #[cfg(feature = "astro")]
+    #[test]
+    fn follows_sunset() {
+        use std::time::Duration;
+
+        let equator = Location::new(0.0, 0.0).unwrap();
+        let s = Schedule::new()
+            .daily()
+            .at_solar(Solar::sunset(equator).before(Duration::from_secs(1800)));
+        let from = dt(2025, 3, 20, 0, 0);
+        let times: Vec<_> = s.upcoming(from).take(3).collect();
+        for (i, at) in times.iter().enumerate() {
+            assert_eq!(at.day, 20 + i as u8);
+            assert!((17..=18).contains(&at.hour));
+        }
+        assert_eq!(
+            Schedule::new().at(9, 0).at_solar(Solar::sunset(equator)),
+            Schedule::new().at(9, 0)
+        );
+
+        let tromso = Location::new(69.6496, 18.956).unwrap();
+        let s = Schedule::new().daily().at_solar(Solar::sunrise(tromso));
+        assert!(s.next_after(dt(2025, 12, 1, 0, 0)).unwrap() > dt(2026, 1, 10, 0, 0));
+    }
+
+    #[cfg(feature = "panchang")]
+    #[test]
+    fn ekadashi_twice_a_lunar_month() {
+        let delhi = Location::new(28.6139, 77.209).unwrap().with_utc_offset(330);
+        let s = Schedule::new()
+            .at(6, 0)
+            .daily()
+            .on_lunar(Lunar::ekadashi(delhi));
+        let days: Vec<_> = s.upcoming(dt(2025, 1, 1, 0, 0)).take(6).collect();
+        for pair in days.windows(2) {
+            let gap = pair[0].date().days_until(pair[1].date());
+            assert!((13..=17).contains(&gap), "{:?}", pair);
+        }
+        assert_eq!(
+            next_occurrences(std::slice::from_ref(&s), days[0]),
+            vec![Some(days[1])]
+        );
+    }
+
+    #[test]
+    fn leap_day_policy_only_applies_to_feb_29() {
+        let s = Schedule::new()
+            .date_with_time(2, 28, 9, 0)
+            .daily()
+            .on_leap_day(LeapDay::Mar1);
+        assert_eq!(
+            s.next_after(dt(2025, 3, 1, 0, 0)),
+            Some(dt(2026, 2, 28, 9, 0))
+        );
+    }
+
+    #[test]
+    fn one_shots_centuries_away() {
+        let from = dt(2025, 1, 1, 0, 0);
+        let far = Schedule::once(2600, 7, 4, 12, 0).unwrap();
+        assert_eq!(far.next_after(from), Some(dt(2600, 7, 4, 12, 0)));
+        assert_eq!(
+            next_occurrences(&[far, Schedule::new().at(9, 0).daily()], from),
+            vec![Some(dt(2600, 7, 4, 12, 0)), Some(dt(2025, 1, 1, 9, 0))]
+        );
+
+        let past = Schedule::once(-44, 3, 15, 12, 0).unwrap();
+        assert_eq!(
+            past.next_after(dt(-100, 1, 1, 0, 0)),
+            Some(dt(-44, 3, 15, 12, 0))
+        );
+        assert_eq!(past.next_after(from), None);
+    }
+
+    #[test]
+    fn several_days_and_times() {
+        let s = Schedule::new().on_days_at(&[Days::MON, Days::WED], &[(9, 0), (17, 0)]);
+        // Tuesday afternoon.
+        let next: Vec<_> = s.upcoming(dt(2025, 9, 16, 12, 0)).take(5).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 9, 17, 9, 0),
+                dt(2025, 9, 17, 17, 0),
+                dt(2025, 9, 22, 9, 0),
+                dt(2025, 9, 22, 17, 0),
+                dt(2025, 9, 24, 9, 0),
+            ]
+        );
+        assert_eq!(s.next_after(dt(2025, 9, 17, 9, 0)), Some(next[1]));
+        assert_eq!(
+            next_occurrences(std::slice::from_ref(&s), dt(2025, 9, 17, 9, 0)),
+            vec![Some(next[1])]
+        );
+
+        let morning = s.between((8, 0), (12, 0));
+        assert_eq!(
+            morning.next_after(dt(2025, 9, 17, 9, 0)),
+            Some(dt(2025, 9, 22, 9, 0))
+        );
+    }
+
+    #[test]
+    fn several_dates_every_year() {
+        let holidays = Schedule::new()
+            .on_dates(&[(Month::JAN, 1), (Month::JUL, 4), (Month::DEC, 25)])
+            .at(9, 0)
+            .daily();
+        let next: Vec<_> = holidays.upcoming(dt(2025, 7, 4, 12, 0)).take(4).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 12, 25, 9, 0),
+                dt(2026, 1, 1, 9, 0),
+                dt(2026, 7, 4, 9, 0),
+                dt(2026, 12, 25, 9, 0),
+            ]
+        );
+        assert_eq!(
+            next_occurrences(&[holidays], dt(2025, 7, 4, 12, 0)),
+            vec![Some(next[0])]
+        );
+
+        let once = Schedule::new().on_dates(&[(Month::MAR, 1), (Month::FEB, 29)]);
+        let next: Vec<_> = once.upcoming(dt(2025, 1, 1, 0, 0)).collect();
+        assert_eq!(next, vec![dt(2025, 3, 1, 0, 0)]);
+        let leap = once.upcoming(dt(2028, 1, 1, 0, 0)).next();
+        assert_eq!(leap, Some(dt(2028, 2, 29, 0, 0)));
+    }
+
+    #[test]
+    fn business_days_around_quarters() {
+        let third = Schedule::new()
+            .on_quarter_start()
+            .offset_business_days(2)
+            .at(9, 0)
+            .monthly();
+        let next: Vec<_> = third.upcoming(dt(2025, 1, 1, 0, 0)).take(5).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 1, 3, 9, 0),
+                dt(2025, 4, 3, 9, 0),
+                dt(2025, 7, 3, 9, 0),
+                dt(2025, 10, 3, 9, 0),
+                dt(2026, 1, 5, 9, 0),
+            ]
+        );
+
+        // Mar 31 and Jun 30 2024 are Sundays.
+        let last = Schedule::new()
+            .on_quarter_end()
+            .offset_business_days(0)
+            .monthly();
+        let next: Vec<_> = last.upcoming(dt(2024, 1, 1, 0, 0)).take(4).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2024, 3, 29, 0, 0),
+                dt(2024, 6, 28, 0, 0),
+                dt(2024, 9, 30, 0, 0),
+                dt(2024, 12, 31, 0, 0),
+            ]
+        );
+        let before_last = Schedule::new()
+            .on_quarter_end()
+            .offset_business_days(-1)
+            .monthly();
+        assert_eq!(
+            next_occurrences(&[before_last, third], dt(2024, 3, 1, 0, 0)),
+            vec![Some(dt(2024, 3, 28, 0, 0)), Some(dt(2024, 4, 3, 9, 0))]
+        );
+
+        // Saturday Jan 1 2022 rolls onto Monday.
+        let first = Schedule::new()
+            .on_quarter_start()
+            .offset_business_days(0)
+            .monthly();
+        assert_eq!(
+            first.next_after(dt(2021, 12, 1, 0, 0)),
+            Some(dt(2022, 1, 3, 0, 0))
+        );
+        let weekdays = Schedule::new().daily().offset_business_days(0);
+        assert_eq!(
+            weekdays.next_after(dt(2025, 1, 3, 0, 0)),
+            Some(dt(2025, 1, 6, 0, 0))
+        );
+    }
+
+    #[test]
+    fn first_business_day_of_the_month() {
+        let s = Schedule::new().on_first_business_day().at(9, 0);
+        // Mar 1 2025 is a Saturday, Jun 1 a Sunday.
+        let next: Vec<_> = s.upcoming(dt(2025, 2, 15, 0, 0)).take(4).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 3, 3, 9, 0),
+                dt(2025, 4, 1, 9, 0),
+                dt(2025, 5, 1, 9, 0),
+                dt(2025, 6, 2, 9, 0),
+            ]
+        );
+
+        let holidays = Holidays::new()
+            .every_year(Month::JAN, 1)
+            .on(Date::new(2026, 1, 2).unwrap());
+        let s = s.holiday_calendar(holidays);
+        assert_eq!(
+            s.next_after(dt(2025, 12, 15, 0, 0)),
+            Some(dt(2026, 1, 5, 9, 0))
+        );
+        assert_eq!(
+            next_occurrences(&[s], dt(2026, 12, 15, 0, 0)),
+            vec![Some(dt(2027, 1, 4, 9, 0))]
+        );
+    }
+
+    #[test]
+    fn last_business_day_of_the_month() {
+        let payday = Schedule::new().on_last_business_day().at(12, 0);
+        // May 31 2025 is a Saturday, Aug 31 a Sunday.
+        let next: Vec<_> = payday
+            .upcoming(dt(2025, 1, 1, 0, 0))
+            .filter(|at| matches!(at.month, 1 | 2 | 5 | 8))
+            .take(4)
+            .collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 1, 31, 12, 0),
+                dt(2025, 2, 28, 12, 0),
+                dt(2025, 5, 30, 12, 0),
+                dt(2025, 8, 29, 12, 0),
+            ]
+        );
+
+        let payday = payday.holiday_calendar(Holidays::new().every_year(Month::DEC, 31));
+        assert_eq!(
+            payday.next_after(dt(2025, 12, 1, 0, 0)),
+            Some(dt(2025, 12, 30, 12, 0))
+        );
+        assert_eq!(
+            next_occurrences(&[payday], dt(2024, 2, 1, 0, 0)),
+            vec![Some(dt(2024, 2, 29, 12, 0))]
+        );
+    }
+
+    #[test]
+    fn every_few_minutes_inside_the_window() {
+        let s = Schedule::new().every_minutes(30).between((9, 0), (17, 0));
+        let day: Vec<_> = s
+            .occurrences_between(dt(2025, 3, 3, 0, 0), dt(2025, 3, 4, 0, 0))
+            .collect();
+        assert_eq!(day.len(), 17);
+        assert_eq!(day[1], dt(2025, 3, 3, 9, 30));
+        assert_eq!(day[16], dt(2025, 3, 3, 17, 0));
+        assert_eq!(
+            s.upcoming(dt(2025, 3, 3, 17, 1)).next(),
+            Some(dt(2025, 3, 4, 9, 0))
+        );
+
+        // Slots count from the window's start, not from the hour.
+        let s = Schedule::new()
+            .every_minutes(45)
+            .between((8, 15), (10, 0))
+            .every_on_day(Days::MON);
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 1, 0, 0)).take(4).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 3, 3, 8, 15),
+                dt(2025, 3, 3, 9, 0),
+                dt(2025, 3, 3, 9, 45),
+                dt(2025, 3, 10, 8, 15),
+            ]
+        );
+        let all_day = Schedule::new().every_minutes(90);
+        assert_eq!(
+            all_day.upcoming(dt(2025, 3, 3, 23, 0)).next(),
+            Some(dt(2025, 3, 4, 0, 0))
+        );
+    }
+
+    #[test]
+    fn steps_like_cron() {
+        let s = Schedule::new().minutes_step(20).hours_step(6);
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 3, 5, 0)).take(4).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 3, 3, 6, 0),
+                dt(2025, 3, 3, 6, 20),
+                dt(2025, 3, 3, 6, 40),
+                dt(2025, 3, 3, 12, 0),
+            ]
+        );
+        // Steps go by the clock, not by the window's start.
+        let s = Schedule::new().minutes_step(25).between((9, 10), (10, 0));
+        let next: Vec<_> = s.upcoming(dt(2025, 3, 3, 0, 0)).take(3).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 3, 3, 9, 25),
+                dt(2025, 3, 3, 9, 50),
+                dt(2025, 3, 3, 10, 0),
+            ]
+        );
+        // Any frequency, set before or after, only picks the days.
+        let s = Schedule::new().hours_step(6).every_on_day(Days::MON);
+        assert_eq!(
+            s.upcoming(dt(2025, 3, 3, 18, 1)).next(),
+            Some(dt(2025, 3, 10, 0, 0))
+        );
+        let s = Schedule::new().minutes_step(15).daily();
+        assert_eq!(
+            s.upcoming(dt(2025, 3, 3, 13, 1)).next(),
+            Some(dt(2025, 3, 3, 13, 15))
+        );
+        let s = Schedule::new().hours_step(5).minute(30);
+        assert_eq!(
+            s.upcoming(dt(2025, 3, 3, 20, 31)).next(),
+            Some(dt(2025, 3, 4, 0, 30))
+        );
+    }
+
+    #[test]
+    fn alternate_weeks_follow_iso_parity() {
+        let pickup = Schedule::new()
+            .every_on_day(Days::TUE)
+            .at(7, 0)
+            .on_alternate_weeks(Parity::Even);
+        // Tue Sep 23 2025 is in week 39, Sep 30 in week 40.
+        let next: Vec<_> = pickup.upcoming(dt(2025, 9, 22, 0, 0)).take(3).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 9, 30, 7, 0),
+                dt(2025, 10, 14, 7, 0),
+                dt(2025, 10, 28, 7, 0),
+            ]
+        );
+        // Week 53 of 2026 and week 1 of 2027 are both odd.
+        let odd = Schedule::new()
+            .every_on_day(Days::MON)
+            .on_alternate_weeks(Parity::Odd);
+        let next: Vec<_> = odd.upcoming(dt(2026, 12, 20, 0, 0)).take(3).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2026, 12, 28, 0, 0),
+                dt(2027, 1, 4, 0, 0),
+                dt(2027, 1, 18, 0, 0),
+            ]
+        );
+        assert_eq!(
+            next_occurrences(&[pickup, odd], dt(2025, 9, 22, 0, 0)),
+            vec![Some(dt(2025, 9, 30, 7, 0)), Some(dt(2025, 10, 6, 0, 0))]
+        );
+    }
+
+    #[test]
+    fn every_few_months_clamps_the_day() {
+        let s = Schedule::new()
+            .every_months_from(3, Month::FEB)
+            .on_day(15)
+            .at(9, 0);
+        let next: Vec<_> = s.upcoming(dt(2025, 1, 1, 0, 0)).take(5).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 2, 15, 9, 0),
+                dt(2025, 5, 15, 9, 0),
+                dt(2025, 8, 15, 9, 0),
+                dt(2025, 11, 15, 9, 0),
+                dt(2026, 2, 15, 9, 0),
+            ]
+        );
+
+        let s = Schedule::new().every_months(1).on_day(31);
+        let next: Vec<_> = s.upcoming(dt(2024, 1, 1, 0, 0)).take(4).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2024, 1, 31, 0, 0),
+                dt(2024, 2, 29, 0, 0),
+                dt(2024, 3, 31, 0, 0),
+                dt(2024, 4, 30, 0, 0),
+            ]
+        );
+        let quarterly = Schedule::new().every_months(3).on_day(31);
+        assert_eq!(
+            next_occurrences(&[quarterly.clone(), s], dt(2025, 2, 1, 0, 0)),
+            vec![Some(dt(2025, 4, 30, 0, 0)), Some(dt(2025, 2, 28, 0, 0))]
+        );
+        assert_eq!(
+            quarterly.next_after(dt(2025, 2, 1, 0, 0)),
+            Some(dt(2025, 4, 30, 0, 0))
+        );
+    }
+
+    #[test]
+    fn only_in_listed_years() {
+        let s = Schedule::new()
+            .on_dates(&[(Month::JAN, 1), (Month::JUL, 1)])
+            .daily()
+            .in_years(&[2025, 2027]);
+        let next: Vec<_> = s.upcoming(dt(2024, 6, 1, 0, 0)).collect();
+        assert_eq!(
+            next,
+            vec![
+                dt(2025, 1, 1, 0, 0),
+                dt(2025, 7, 1, 0, 0),
+                dt(2027, 1, 1, 0, 0),
+                dt(2027, 7, 1, 0, 0),
+            ]
+        );
+        assert_eq!(
+            s.remaining_occurrences(dt(2025, 3, 1, 0, 0)),
+            Remaining::Exactly(3)
+        );
+        assert_eq!(
+            next_occurrences(&[s], dt(2025, 7, 1, 0, 0)),
+            vec![Some(dt(2027, 1, 1, 0, 0))]
+        );
+
+        let daily = Schedule::new().at(9, 0).daily().in_years(&[2025]);
+        assert_eq!(daily.next_after(dt(2025, 12, 31, 9, 0)), None);
+    }
+
+    #[test]
+    fn past_fixed_year_never_fires() {
+        let s = Schedule::new().year(2020).date(1, 1);
+        assert_eq!(s.next_after(dt(2025, 1, 1, 0, 0)), None);
+    }
+}
@@ -0,0 +1,187 @@
+// Lunar days (tithis) of the Hindu calendar, for schedules such as "every
+// Ekadashi" that follow the moon instead of the Gregorian month.
+//
+// A tithi is the time the moon takes to gain another 12° on the sun, so a
+// lunar month has 30 of them: 1–15 in the waxing fortnight (Shukla paksha,
+// ending at Purnima) and 16–30 in the waning one (Krishna paksha, ending at
+// Amavasya). A day takes the tithi current at its local sunrise. A tithi that
+// starts and ends between two sunrises is kept on the day it falls in, and
+// one current at two sunrises only on the first, as panchangs list them.
+//
+// Positions use low-precision series good to about 0.3° for the moon, so a
+// tithi boundary can be off by up to an hour; days whose sunrise falls that
+// close to a boundary may differ from a published panchang.
+use crate::astro::{Location, SolarEvent};
+use crate::time::{Date, DateTime};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LunarEvent {
+    // 1–30, counted from the day after Amavasya.
+    Tithi(u8),
+    // The 11th tithi of either fortnight.
+    Ekadashi,
+    Purnima,
+    Amavasya,
+}
+
+// A lunar event observed at a location, whose sunrise decides each day's
+// tithi.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Lunar {
+    pub event: LunarEvent,
+    pub location: Location,
+}
+
+impl Lunar {
+    // None unless `n` is 1–30.
+    pub fn tithi(n: u8, location: Location) -> Option<Lunar> {
+        (1..=30).contains(&n).then_some(Lunar {
+            event: LunarEvent::Tithi(n),
+            location,
+        })
+    }
+
+    pub fn ekadashi(location: Location) -> Lunar {
+        Lunar {
+            event: LunarEvent::Ekadashi,
+            location,
+        }
+    }
+
+    pub fn purnima(location: Location) -> Lunar {
+        Lunar {
+            event: LunarEvent::Purnima,
+            location,
+        }
+    }
+
+    pub fn amavasya(location: Location) -> Lunar {
+        Lunar {
+            event: LunarEvent::Amavasya,
+            location,
+        }
+    }
+
+    pub(crate) fn is_valid(&self) -> bool {
+        let tithi_ok = match self.event {
+            LunarEvent::Tithi(n) => (1..=30).contains(&n),
+            _ => true,
+        };
+        tithi_ok && self.location.is_valid()
+    }
+
+    // Whether `date` is observed as this event.
+    pub(crate) fn falls_on(&self, date: Date) -> bool {
+        let tithis: &[u8] = match self.event {
+            LunarEvent::Tithi(n) => &[n],
+            LunarEvent::Ekadashi => &[11, 26],
+            LunarEvent::Purnima => &[15],
+            LunarEvent::Amavasya => &[30],
+        };
+        let Some(today) = tithi_on(self.location, date) else {
+            return false;
+        };
+        tithis.iter().any(|&t| {
+            let (prev, next) = ((t + 28) % 30 + 1, t % 30 + 1);
+            if today == t {
+                // Current at two sunrises: kept on the first.
+                tithi_on(self.location, date.add_days(-1)) != Some(t)
+            } else {
+                // Skipped over between two sunrises: kept on the day it
+                // falls in.
+                today == prev && tithi_on(self.location, date.add_days(1)) == Some(next)
+            }
+        })
+    }
+}
+
+// The tithi current at sunrise on `date` at `location`. None without a
+// sunrise (polar day or night).
+pub fn tithi_on(location: Location, date: Date) -> Option<u8> {
+    let sunrise = location.solar_time(SolarEvent::Sunrise, date)?;
+    Some(tithi_at(sunrise.add_minutes(-(location.utc_offset as i64))))
+}
+
+// The tithi current at a UTC instant, 1–30.
+pub fn tithi_at(utc: DateTime) -> u8 {
+    let elongation = (moon_longitude(utc) - sun_longitude(utc)).rem_euclid(360.0);
+    (elongation / 12.0) as u8 % 30 + 1
+}
+
+// Julian centuries since J2000.
+fn centuries(utc: DateTime) -> f64 {
+    (utc.unix() as f64 / 86_400.0 + 2_440_587.5 - 2_451_545.0) / 36_525.0
+}
+
+fn sun_longitude(utc: DateTime) -> f64 {
+    let t = centuries(utc);
+    let anomaly = (357.528 + 35_999.050 * t).to_radians();
+    280.460 + 36_000.770 * t + 1.915 * anomaly.sin() + 0.020 * (2.0 * anomaly).sin()
+}
+
+fn moon_longitude(utc: DateTime) -> f64 {
+    let t = centuries(utc);
+    let terms = [
+        (6.29, 135.0, 477_198.87),
+        (-1.27, 259.3, -413_335.36),
+        (0.66, 235.7, 890_534.22),
+        (0.21, 269.9, 954_397.74),
+        (-0.19, 357.5, 35_999.05),
+        (-0.11, 186.5, 966_404.03),
+    ];
+    terms.iter().fold(218.32 + 481_267.881 * t, |l, (a, b, c)| {
+        l + a * (b + c * t).to_radians().sin()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn full_and_new_moons() {
+        // Full moon 2025-03-14 06:55 UTC, new moon 2025-03-29 10:58 UTC.
+        let full = DateTime::new(2025, 3, 14, 7, 30).unwrap();
+        let new = DateTime::new(2025, 3, 29, 11, 30).unwrap();
+        assert_eq!(tithi_at(full), 16);
+        assert_eq!(tithi_at(full.add_minutes(-120)), 15);
+        assert_eq!(tithi_at(new), 1);
+        assert_eq!(tithi_at(new.add_minutes(-120)), 30);
+    }
+
+    #[test]
+    fn purnimas_of_2025_in_delhi() {
+        let delhi = Location::new(28.6139, 77.209).unwrap().with_utc_offset(330);
+        let purnima = Lunar::purnima(delhi);
+        let start = date(2025, 1, 1);
+        let full_moons: Vec<_> = (0..365)
+            .map(|i| start.add_days(i))
+            .filter(|d| purnima.falls_on(*d))
+            .map(|d| (d.month, d.day))
+            .collect();
+        assert_eq!(
+            full_moons,
+            [
+                (1, 13),
+                (2, 12),
+                (3, 14),
+                (4, 12),
+                (5, 12),
+                (6, 11),
+                (7, 10),
+                (8, 9),
+                (9, 7),
+                (10, 7),
+                (11, 5),
+                (12, 4),
+            ]
+        );
+        assert!(Lunar::tithi(31, delhi).is_none());
+    }
+}
@@ -0,0 +1,354 @@
+// Five-field cron expressions ("min hour day-of-month month day-of-week") and
+// the @hourly/@daily/@weekly/@monthly shorthands.
+//
+// Each field must be `*` or a single value. Minute and hour also take `*/n`
+// steps, and day-of-week Quartz's `DAY#n` for the n-th weekday of the
+// month. `to_cron` goes the other way.
+use super::ParseError;
+use crate::time::is_valid_day_for_month;
+use crate::types::*;
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+pub fn from_cron(expr: &str) -> Result<Schedule, ParseError> {
+    let expr = expr.trim();
+    match expr.to_ascii_lowercase().as_str() {
+        "@hourly" => return from_cron("0 * * * *"),
+        "@daily" | "@midnight" => return from_cron("0 0 * * *"),
+        "@weekly" => return from_cron("0 0 * * 0"),
+        "@monthly" => return from_cron("0 0 1 * *"),
+        "@yearly" | "@annually" => {
+            return Err(ParseError::new("yearly schedules are not supported"));
+        }
+        e if e.starts_with('@') => {
+            return Err(ParseError::new(format!("unknown shorthand {}", expr)));
+        }
+        _ => {}
+    }
+
+    let fields: Vec<&str> = expr.split_whitespace().collect();
+    let [minute, hour, dom, month, dow] = fields[..] else {
+        return Err(ParseError::new(format!(
+            "expected 5 cron fields, found {}",
+            fields.len()
+        )));
+    };
+    let minute_step = step(minute, "minute", 59)?;
+    let minute = match minute_step {
+        Some(_) => None,
+        None => Some(
+            value(minute, "minute", 0, 59, &[])?
+                .ok_or_else(|| ParseError::new("every-minute schedules are not supported"))?,
+        ),
+    };
+    let hour_step = step(hour, "hour", 23)?;
+    let hour = match hour_step {
+        Some(_) => None,
+        None => value(hour, "hour", 0, 23, &[])?,
+    };
+    let dom = value(dom, "day-of-month", 1, 31, &[])?;
+    let month = value(month, "month", 1, 12, &MONTHS)?;
+    let dow = weekday(dow)?;
+
+    if let (Some(m), Some(d)) = (month, dom)
+        && !is_valid_day_for_month(m - 1, d)
+    {
+        return Err(ParseError::new(format!(
+            "day {} does not exist in month {}",
+            d, m
+        )));
+    }
+
+    let mut s = Schedule::new();
+    if let Some(m) = month {
+        s = s.month(m);
+    }
+    if let Some(d) = dom {
+        s = s.day(d);
+    }
+    // A minute step already covers every hour of the weekday.
+    let hours = hour.or(hour_step).or(minute_step.filter(|_| dow.is_some()));
+    let frequency = match (hours, dom, month, dow) {
+        (None, _, _, Some(_)) => {
+            return Err(ParseError::new(
+                "an hourly schedule can't also be restricted to a weekday",
+            ));
+        }
+        (None, ..) => FrequencyPattern::Frequency(Frequency::Hourly),
+        (Some(_), Some(_), _, Some(_)) => {
+            return Err(ParseError::new(
+                "day-of-month and day-of-week together are not supported",
+            ));
+        }
+        (Some(_), None, _, Some(by_day)) => FrequencyPattern::ByDay(by_day),
+        (Some(_), Some(_), Some(_), None) => {
+            return Err(ParseError::new("yearly schedules are not supported"));
+        }
+        (Some(_), Some(_), None, None) => FrequencyPattern::Frequency(Frequency::Monthly),
+        (Some(_), None, _, None) => FrequencyPattern::Frequency(Frequency::Daily),
+    };
+    s = s.every(frequency);
+    if let Some(h) = hour {
+        s = s.hour(h);
+    }
+    if let Some(n) = hour_step {
+        s = s.hours_step(n);
+    }
+    Ok(match minute_step {
+        Some(n) => s.minutes_step(n),
+        None => s.minute(minute.unwrap_or(0)),
+    })
+}
+
+// The five-field expression `from_cron` reads back into a schedule with the
+// same occurrences. Years, exceptions, bounds, windows and shifts have no
+// cron equivalent and are errors.
+pub fn to_cron(s: &Schedule) -> Result<String, ParseError> {
+    let unsupported = [
+        (s.year.is_some(), "a year"),
+        (!s.years.is_empty(), "a list of years"),
+        (s.month_step.is_some(), "a month interval"),
+        (s.business_days.is_some(), "a business-day offset"),
+        (s.week_parity.is_some(), "alternate weeks"),
+        (s.recurring.except.is_some(), "an exception"),
+        (!s.repeat.is_forever(), "a repeat count"),
+        (s.range.is_some(), "a time window"),
+        (s.offset.is_some(), "an offset"),
+        (s.delay.is_some(), "a relative start"),
+        (s.immediate, "an immediate run"),
+        (s.stride.is_some(), "a stride"),
+        (s.not_before.is_some(), "a start date"),
+        (s.leap_day_policy().is_some(), "a leap-day policy"),
+        (s.day_of_year.is_some(), "a day of the year"),
+        (s.fiscal.is_some(), "a fiscal period"),
+        (s.follows_sun(), "a solar time"),
+        (s.follows_moon(), "a lunar day"),
+        (!s.times.is_empty(), "several times of day"),
+        (s.interval.is_some(), "a minute interval"),
+        (!s.weekdays.is_empty(), "a set of weekdays"),
+        (!s.dates.is_empty(), "several dates"),
+    ];
+    if let Some((_, what)) = unsupported.iter().find(|(set, _)| *set) {
+        return Err(ParseError::new(format!(
+            "{} can't be expressed in cron",
+            what
+        )));
+    }
+    let field = |v: Option<u8>| v.map_or("*".to_string(), |v| v.to_string());
+    let frequency = s
+        .frequency_or_daily()
+        .ok_or_else(|| ParseError::new("one-shot schedules can't be expressed in cron"))?;
+    let (hour, day, dow) = match frequency {
+        FrequencyPattern::Frequency(Frequency::Hourly) => (field(s.hour), field(s.day), None),
+        FrequencyPattern::Frequency(Frequency::Daily) => {
+            (field(s.hour.or(Some(0))), field(s.day), None)
+        }
+        FrequencyPattern::Frequency(Frequency::Weekly) => (
+            field(s.hour.or(Some(0))),
+            field(s.day),
+            Some(DAYS[0].to_string()),
+        ),
+        FrequencyPattern::Frequency(Frequency::Monthly) => {
+            (field(s.hour.or(Some(0))), field(s.day.or(Some(1))), None)
+        }
+        FrequencyPattern::ByDay((n, d)) => {
+            let name = DAYS[d as usize];
+            let dow = n.map_or(name.to_string(), |n| format!("{}#{}", name, n));
+            (field(s.hour.or(Some(0))), field(s.day), Some(dow))
+        }
+    };
+    let stepped = |n: Option<u8>| n.map(|n| format!("*/{}", n));
+    let hour = match s.hour_step {
+        // Every hour, whatever the frequency says about days.
+        None if s.minute_step.is_some() && s.hour.is_none() => "*".to_string(),
+        step => stepped(step).unwrap_or(hour),
+    };
+    let minute = stepped(s.minute_step).unwrap_or(s.minute.unwrap_or(0).to_string());
+    if dow.is_some() && s.day.is_some() {
+        // Cron fires when *either* day field matches.
+        return Err(ParseError::new(
+            "a day-of-month on a weekday schedule can't be expressed in cron",
+        ));
+    }
+    if s.day.is_some() && s.month.is_some() && hour != "*" {
+        return Err(ParseError::new("yearly schedules are not supported"));
+    }
+    Ok(format!(
+        "{} {} {} {} {}",
+        minute,
+        hour,
+        day,
+        field(s.month.map(|m| m as u8 + 1)),
+        dow.unwrap_or("*".to_string())
+    ))
+}
+
+// The `n` of a `*/n` step, which must be within 1..=max.
+fn step(field: &str, name: &str, max: u8) -> Result<Option<u8>, ParseError> {
+    let Some(n) = field.strip_prefix("*/") else {
+        return Ok(None);
+    };
+    n.parse::<u8>()
+        .ok()
+        .filter(|n| (1..=max).contains(n))
+        .map(Some)
+        .ok_or_else(|| ParseError::new(format!("invalid {} step {:?}", name, field)))
+}
+
+// `*`/`?` is None, otherwise a single number (or name) within min..=max.
+fn value(
+    field: &str,
+    name: &str,
+    min: u8,
+    max: u8,
+    names: &[&str],
+) -> Result<Option<u8>, ParseError> {
+    if field == "*" || field == "?" {
+        return Ok(None);
+    }
+    if field.contains([',', '-', '/']) {
+        return Err(ParseError::new(format!(
+            "lists, ranges and steps are not supported in the {} field",
+            name
+        )));
+    }
+    let upper = field.to_ascii_uppercase();
+    let n = match names.iter().position(|n| *n == upper) {
+        Some(i) => i as u8 + min,
+        None => field
+            .parse::<u8>()
+            .map_err(|_| ParseError::new(format!("invalid {} {:?}", name, field)))?,
+    };
+    if n < min || n > max {
+        return Err(ParseError::new(format!(
+            "{} {} out of range {}-{}",
+            name, n, min, max
+        )));
+    }
+    Ok(Some(n))
+}
+
+fn weekday(field: &str) -> Result<Option<(Option<u8>, Days)>, ParseError> {
+    let (day, nth) = match field.split_once('#') {
+        Some((day, nth)) => {
+            let nth = nth
+                .parse::<u8>()
+                .ok()
+                .filter(|n| (1..=5).contains(n))
+                .ok_or_else(|| ParseError::new(format!("invalid weekday occurrence {:?}", nth)))?;
+            (day, Some(nth))
+        }
+        None => (field, None),
+    };
+    let Some(n) = value(day, "day-of-week", 0, 7, &DAYS)? else {
+        return match nth {
+            Some(_) => Err(ParseError::new("`*#n` needs a weekday")),
+            None => Ok(None),
+        };
+    };
+    let day = match n % 7 {
+        0 => Days::SUN,
+        1 => Days::MON,
+        2 => Days::TUE,
+        3 => Days::WED,
+        4 => Days::THUR,
+        5 => Days::FRI,
+        _ => Days::SAT,
+    };
+    Ok(Some((nth, day)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shorthands() {
+        assert_eq!(from_cron("@hourly"), Ok(Schedule::new().minute(0).hourly()));
+        assert_eq!(from_cron("@daily"), Ok(Schedule::new().at(0, 0).daily()));
+        assert_eq!(
+            from_cron("@monthly"),
+            Ok(Schedule::new().on_day(1).at(0, 0).monthly())
+        );
+        assert!(from_cron("@yearly").is_err());
+    }
+
+    #[test]
+    fn fields_map_to_frequencies() {
+        assert_eq!(
+            from_cron("30 22 20 * *"),
+            Ok(Schedule::new().day_with_time(20, 22, 30).monthly())
+        );
+        assert_eq!(
+            from_cron("0 9 * * MON"),
+            Ok(Schedule::new().at(9, 0).every_on_day(Days::MON))
+        );
+        assert_eq!(
+            from_cron("15 * 1 * *"),
+            Ok(Schedule::new().minute(15).on_day(1).hourly())
+        );
+        assert_eq!(
+            from_cron("0 9 * mar *"),
+            Ok(Schedule::new().month(3).at(9, 0).daily())
+        );
+        assert_eq!(from_cron("0 0 * * 7"), from_cron("@weekly"));
+        assert_eq!(
+            from_cron("*/5 * * * *"),
+            Ok(Schedule::new().minutes_step(5).hourly())
+        );
+        assert_eq!(
+            from_cron("0 */2 * * MON"),
+            Ok(Schedule::new()
+                .every_on_day(Days::MON)
+                .hours_step(2)
+                .minute(0))
+        );
+    }
+
+    #[test]
+    fn unsupported_and_invalid() {
+        assert!(from_cron("*/60 * * * *").is_err());
+        assert!(from_cron("0 */0 * * *").is_err());
+        assert!(from_cron("5/15 * * * *").is_err());
+        assert!(from_cron("0 9 1,15 * *").is_err());
+        assert!(from_cron("0 9 31 2 *").is_err());
+        assert!(from_cron("0 24 * * *").is_err());
+        assert!(from_cron("0 9 * *").is_err());
+        assert!(from_cron("0 9 1 * 1").is_err());
+    }
+
+    #[test]
+    fn to_cron_round_trips() {
+        let from = crate::time::DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        let schedules = [
+            Schedule::new().minute(15).hourly(),
+            Schedule::new().at(9, 30).daily(),
+            Schedule::new().weekly(),
+            Schedule::new().monthly(),
+            Schedule::new().day_with_time(20, 22, 30).monthly(),
+            Schedule::new().at(22, 0).every_nth_day(3, Days::SAT),
+            Schedule::new().month(3).at(9, 0).daily(),
+            Schedule::new().on_day(1).hourly(),
+            Schedule::new().minutes_step(15).hours_step(2),
+            Schedule::new().daily().hour(9).minutes_step(10),
+            Schedule::new().minutes_step(20).weekly(),
+        ];
+        for s in &schedules {
+            let expr = to_cron(s).unwrap();
+            let back = from_cron(&expr).unwrap();
+            assert!(
+                s.upcoming(from).take(50).eq(back.upcoming(from).take(50)),
+                "{}",
+                expr
+            );
+        }
+        assert_eq!(to_cron(&schedules[5]), Ok("0 22 * * SAT#3".to_string()));
+        assert_eq!(to_cron(&schedules[8]), Ok("*/15 */2 * * *".to_string()));
+        assert!(to_cron(&Schedule::new().date(9, 20)).is_err());
+        assert!(to_cron(&Schedule::new().date(9, 20).daily()).is_err());
+        assert!(to_cron(&Schedule::new().daily().except_on_day(Days::SUN)).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Parsing schedules from text. `str::parse::<Schedule>()` picks the format:
+// anything starting with `@` or made of five cron fields is cron, anything
+// with `FREQ=` is an RRULE, and everything else goes to the natural-language
+// grammar (the same clauses as the schedule! macro).
+//
+// Only what a Schedule can represent is accepted; lists, ranges, steps other
+// than cron's `*/n` on minutes and hours, and yearly rules are reported as
+// errors rather than approximated. Input is untrusted: any string gets Ok or
+// Err, never a panic, which the targets in fuzz/ keep checking.
+mod cron;
+mod natural;
+mod rrule;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::types::Schedule;
+
+pub use cron::{from_cron, to_cron};
+pub use natural::from_natural;
+#[cfg(feature = "python")]
+pub(crate) use natural::weekday;
+pub use rrule::{from_rrule, to_rrule};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid schedule: {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Cron,
+    RRule,
+    Natural,
+}
+
+pub fn detect(input: &str) -> Format {
+    let input = input.trim();
+    if input.to_ascii_uppercase().contains("FREQ=") {
+        Format::RRule
+    } else if input.starts_with('@') || looks_like_cron(input) {
+        Format::Cron
+    } else {
+        Format::Natural
+    }
+}
+
+fn looks_like_cron(input: &str) -> bool {
+    let fields: Vec<&str> = input.split_whitespace().collect();
+    fields.len() == 5
+        && fields.iter().all(|f| {
+            f.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "*?,-/#".contains(c))
+        })
+        && fields
+            .first()
+            .is_some_and(|f| f.starts_with(|c: char| c.is_ascii_digit() || c == '*'))
+}
+
+impl FromStr for Schedule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match detect(s) {
+            Format::Cron => from_cron(s),
+            Format::RRule => from_rrule(s),
+            Format::Natural => from_natural(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    #[test]
+    fn detects_each_format() {
+        assert_eq!(detect("0 9 * * 1"), Format::Cron);
+        assert_eq!(detect("@daily"), Format::Cron);
+        assert_eq!(detect("FREQ=DAILY;BYHOUR=9"), Format::RRule);
+        assert_eq!(detect("RRULE:FREQ=WEEKLY"), Format::RRule);
+        assert_eq!(detect("every 3rd saturday at 22:00"), Format::Natural);
+    }
+
+    #[test]
+    fn all_formats_agree() {
+        let expected = Schedule::new().at(22, 0).every_nth_day(3, Days::SAT);
+
+        let cron: Schedule = "0 22 * * 6#3".parse().unwrap();
+        let rrule: Schedule = "FREQ=MONTHLY;BYDAY=3SA;BYHOUR=22;BYMINUTE=0"
+            .parse()
+            .unwrap();
+        let natural: Schedule = "every 3rd saturday at 22:00".parse().unwrap();
+
+        assert_eq!(cron, expected);
+        assert_eq!(rrule, expected);
+        assert_eq!(natural, expected);
+    }
+
+    // Parsers take user-supplied configuration: whatever comes in, they
+    // return Ok or Err, and what they accept is safe to use.
+    #[test]
+    fn arbitrary_input_never_panics() {
+        for seed in 0..20_000 {
+            let input = crate::testing::arbitrary::Gen::new(seed).input();
+            let ok = std::panic::catch_unwind(|| {
+                let parsed = [from_cron(&input), from_rrule(&input), from_natural(&input)];
+                for s in parsed.iter().flatten() {
+                    let _ = (to_cron(s), to_rrule(s), s.to_string());
+                    let from = crate::time::DateTime::new(2025, 1, 1, 0, 0).unwrap();
+                    s.upcoming(from).take(3).for_each(drop);
+                }
+            });
+            assert!(ok.is_ok(), "seed {}: {:?}", seed, input);
+        }
+    }
+
+    #[test]
+    fn error_names_the_problem() {
+        let err = "0 9 1,15 * *".parse::<Schedule>().unwrap_err();
+        assert!(err.to_string().starts_with("invalid schedule:"));
+    }
+}
@@ -0,0 +1,438 @@
+// The natural-language grammar, e.g. "every 3rd saturday at 22:00 except
+// month dec". It accepts the schedule! macro's clauses at runtime, with
+// full or abbreviated names in any case and a few friendlier spellings:
+//
+//     every hour | day | week | month      hourly | daily | weekly | monthly
+//     every <n> minutes
+//     every [weekly on] <day>              every <nth> <day> [of the month]
+//     on day <n>   on <month> <n>   on <n> <month>
+//     at <h>[:<mm>][am|pm]                 between <h>:<mm> and <h>:<mm>
+//     except month <month> | day <day> | date <n> | <nth> <day> | <day>s | <month>
+//     repeat <n> times | <n> times         until <n> <month> | <month> <n>
+//
+// Display's wording, e.g. "of the month" or "except in December", reads back
+// too, so `brahma convert --to text` output can be parsed again.
+use std::iter::Peekable;
+use std::str::{FromStr, SplitWhitespace};
+
+use super::ParseError;
+use crate::types::*;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+const DAYS: [(&str, &str, Days); 7] = [
+    ("sun", "sunday", Days::SUN),
+    ("mon", "monday", Days::MON),
+    ("tue", "tuesday", Days::TUE),
+    ("wed", "wednesday", Days::WED),
+    ("thu", "thursday", Days::THUR),
+    ("fri", "friday", Days::FRI),
+    ("sat", "saturday", Days::SAT),
+];
+
+struct Parser<'a> {
+    words: Peekable<SplitWhitespace<'a>>,
+    schedule: Schedule,
+}
+
+pub fn from_natural(input: &str) -> Result<Schedule, ParseError> {
+    let lower = input.trim().to_lowercase();
+    if lower.is_empty() {
+        return Err(ParseError::new("empty schedule"));
+    }
+    let mut p = Parser {
+        words: lower.split_whitespace().peekable(),
+        schedule: Schedule::new(),
+    };
+    while let Some(word) = p.words.next() {
+        p.clause(word)?;
+    }
+    Ok(p.schedule)
+}
+
+impl<'a> Parser<'a> {
+    fn clause(&mut self, word: &'a str) -> Result<(), ParseError> {
+        match word {
+            "every" => self.every(),
+            "hourly" => self.frequency(FrequencyPattern::Frequency(Frequency::Hourly)),
+            "daily" => self.frequency(FrequencyPattern::Frequency(Frequency::Daily)),
+            "weekly" => self.frequency(FrequencyPattern::Frequency(Frequency::Weekly)),
+            "monthly" => self.frequency(FrequencyPattern::Frequency(Frequency::Monthly)),
+            "on" => self.on(),
+            "at" => {
+                let word = self.next("a time after `at`")?;
+                let (h, m) = self.time(word)?;
+                self.at(h, m)
+            }
+            "between" => self.between(),
+            "except" => self.except(),
+            "repeat" => {
+                let n = self.next("a count after `repeat`")?;
+                let n = number(n, 0, u32::MAX)?;
+                self.expect("times")?;
+                self.repeat(n)
+            }
+            "until" => self.until(),
+            w => match w.parse::<u32>() {
+                Ok(n) => {
+                    self.expect("times")?;
+                    self.repeat(n)
+                }
+                Err(_) => Err(ParseError::new(format!("unexpected {:?}", w))),
+            },
+        }
+    }
+
+    fn every(&mut self) -> Result<(), ParseError> {
+        let word = self.next("something after `every`")?;
+        if word.parse::<u16>().is_ok() {
+            let n = number(word, 1, 24 * 60 - 1)?;
+            self.expect("minutes")?;
+            return self.minutes(n);
+        }
+        let pattern = match word {
+            "hour" | "hourly" => FrequencyPattern::Frequency(Frequency::Hourly),
+            "day" | "daily" => FrequencyPattern::Frequency(Frequency::Daily),
+            "week" | "weekly" if self.words.next_if_eq(&"on").is_some() => {
+                let day = self.next("a weekday after `on`")?;
+                FrequencyPattern::ByDay((None, weekday(day)?))
+            }
+            "week" | "weekly" => FrequencyPattern::Frequency(Frequency::Weekly),
+            "month" | "monthly" => FrequencyPattern::Frequency(Frequency::Monthly),
+            w => match ordinal(w) {
+                Some(n) => {
+                    let day = self.next("a weekday after the ordinal")?;
+                    // As Display writes it, so `convert --to text` reads back.
+                    if self.words.next_if_eq(&"of").is_some() {
+                        self.expect("the")?;
+                        self.expect("month")?;
+                    }
+                    FrequencyPattern::ByDay((Some(n), weekday(day)?))
+                }
+                None => FrequencyPattern::ByDay((None, weekday(w)?)),
+            },
+        };
+        self.frequency(pattern)
+    }
+
+    fn on(&mut self) -> Result<(), ParseError> {
+        let word = self.next("a date after `on`")?;
+        if word == "day" {
+            let d = number(self.next("a day after `on day`")?, 1, 31)?;
+            return self.date(None, d);
+        }
+        let (month, day) = self.month_and_day(word)?;
+        self.date(Some(month), day)
+    }
+
+    fn between(&mut self) -> Result<(), ParseError> {
+        let start = self.next("a start time after `between`")?;
+        let start = self.time(start)?;
+        self.expect("and")?;
+        let end = self.next("an end time after `and`")?;
+        let end = self.time(end)?;
+        if self.schedule.range.is_some() {
+            return Err(ParseError::new("`between` given twice"));
+        }
+        self.schedule = self.schedule.clone().between(start, end);
+        Ok(())
+    }
+
+    fn except(&mut self) -> Result<(), ParseError> {
+        let mut word = self.next("something after `except`")?;
+        // Display's "except in December", "except on Tuesdays", "except the
+        // 1st Monday".
+        if matches!(word, "in" | "on" | "the") {
+            word = self.next("something after `except`")?;
+        }
+        let except = match word {
+            "month" => Except::Month(month(self.next("a month")?)?),
+            // "except day tue", or Display's "except on day 5".
+            "day" => match self.next("a weekday")? {
+                w if w.parse::<u8>().is_ok() => Except::N(number(w, 1, 31)?),
+                w => Except::Day(weekday(w)?),
+            },
+            "date" => Except::N(number(self.next("a day of month")?, 1, 31)?),
+            w => match (ordinal(w), month(w)) {
+                (Some(n), _) => Except::NthDay((n, weekday(self.next("a weekday")?)?)),
+                (None, Ok(m)) => Except::Month(m),
+                (None, Err(_)) => Except::Day(weekday(w.strip_suffix('s').unwrap_or(w))?),
+            },
+        };
+        if self.schedule.recurring.except.is_some() {
+            return Err(ParseError::new("`except` given twice"));
+        }
+        self.schedule = self.schedule.clone().except(except);
+        Ok(())
+    }
+
+    fn until(&mut self) -> Result<(), ParseError> {
+        let word = self.next("a date after `until`")?;
+        let (m, d) = self.month_and_day(word)?;
+        if self.schedule.repeat.end_date().is_some() {
+            return Err(ParseError::new("`until` given twice"));
+        }
+        self.schedule = self.schedule.clone().ending_on(EndDate::on(d, m));
+        Ok(())
+    }
+
+    // "sep 20" or "20 sep", starting from the word already taken.
+    fn month_and_day(&mut self, word: &'a str) -> Result<(Month, u8), ParseError> {
+        let other = self.next("a date")?;
+        let (m, d) = match word.parse::<u8>() {
+            Ok(d) => (month(other)?, d),
+            Err(_) => (month(word)?, number(other, 1, 31)?),
+        };
+        if !crate::time::is_valid_day_for_month(m as u8, d) || d == 0 {
+            return Err(ParseError::new(format!(
+                "day {} does not exist in {:?}",
+                d, m
+            )));
+        }
+        Ok((m, d))
+    }
+
+    // "22:00", "9", "10pm", "10:30pm" or "10:30 pm".
+    fn time(&mut self, word: &'a str) -> Result<(u8, u8), ParseError> {
+        let invalid = || ParseError::new(format!("invalid time {:?}", word));
+        let (clock, suffix) = match word.strip_suffix("am").or_else(|| word.strip_suffix("pm")) {
+            Some(clock) => (clock, word.get(clock.len()..)),
+            None => (word, self.words.next_if(|w| *w == "am" || *w == "pm")),
+        };
+        let (h, m) = match clock.split_once(':') {
+            Some((h, m)) => (h, m),
+            None => (clock, "0"),
+        };
+        let (mut h, m) = (
+            number(h, 0, 23).map_err(|_| invalid())?,
+            number(m, 0, 59).map_err(|_| invalid())?,
+        );
+        match suffix {
+            Some(_) if h == 0 || h > 12 => return Err(invalid()),
+            Some("am") if h == 12 => h = 0,
+            Some("pm") if h < 12 => h += 12,
+            _ => {}
+        }
+        Ok((h, m))
+    }
+
+    fn frequency(&mut self, f: FrequencyPattern) -> Result<(), ParseError> {
+        if self.schedule.recurring.frequency.is_some() {
+            return Err(ParseError::new("frequency given twice"));
+        }
+        if f == FrequencyPattern::Frequency(Frequency::Hourly) && self.schedule.interval.is_some() {
+            return Err(ParseError::new("`every hour` can't run every few minutes"));
+        }
+        self.schedule = self.schedule.clone().every(f);
+        Ok(())
+    }
+
+    fn date(&mut self, month: Option<Month>, day: u8) -> Result<(), ParseError> {
+        if self.schedule.day.is_some() {
+            return Err(ParseError::new("date given twice"));
+        }
+        let mut s = self.schedule.clone();
+        if let Some(m) = month {
+            s = s.month(m as u8 + 1);
+        }
+        self.schedule = s.day(day);
+        Ok(())
+    }
+
+    fn minutes(&mut self, n: u16) -> Result<(), ParseError> {
+        if self.schedule.hour.is_some() || self.schedule.interval.is_some() {
+            return Err(ParseError::new("time of day given twice"));
+        }
+        if self.schedule.recurring.frequency == Some(FrequencyPattern::Frequency(Frequency::Hourly))
+        {
+            return Err(ParseError::new("`every hour` can't run every few minutes"));
+        }
+        self.schedule = self.schedule.clone().every_minutes(n);
+        Ok(())
+    }
+
+    fn at(&mut self, hour: u8, minute: u8) -> Result<(), ParseError> {
+        if self.schedule.interval.is_some() {
+            return Err(ParseError::new("time of day given twice"));
+        }
+        if self.schedule.hour.is_some() {
+            return Err(ParseError::new("`at` given twice"));
+        }
+        self.schedule = self.schedule.clone().hour(hour).minute(minute);
+        Ok(())
+    }
+
+    fn repeat(&mut self, n: u32) -> Result<(), ParseError> {
+        if self.schedule.repeat.count().is_some() {
+            return Err(ParseError::new("`repeat` given twice"));
+        }
+        self.schedule = self.schedule.clone().repeat(n);
+        Ok(())
+    }
+
+    fn next(&mut self, what: &str) -> Result<&'a str, ParseError> {
+        self.words
+            .next()
+            .ok_or_else(|| ParseError::new(format!("expected {}", what)))
+    }
+
+    fn expect(&mut self, word: &str) -> Result<(), ParseError> {
+        match self.words.next() {
+            Some(w) if w == word => Ok(()),
+            Some(w) => Err(ParseError::new(format!(
+                "expected `{}`, found {:?}",
+                word, w
+            ))),
+            None => Err(ParseError::new(format!("expected `{}`", word))),
+        }
+    }
+}
+
+fn number<T: FromStr + PartialOrd>(word: &str, min: T, max: T) -> Result<T, ParseError> {
+    word.parse::<T>()
+        .ok()
+        .filter(|n| (min..=max).contains(n))
+        .ok_or_else(|| ParseError::new(format!("invalid number {:?}", word)))
+}
+
+fn ordinal(word: &str) -> Option<u8> {
+    let n = match word {
+        "first" => 1,
+        "second" => 2,
+        "third" => 3,
+        "fourth" => 4,
+        "fifth" => 5,
+        w => ["st", "nd", "rd", "th"]
+            .iter()
+            .find_map(|s| w.strip_suffix(s))
+            .and_then(|n| n.parse::<u8>().ok())?,
+    };
+    (1..=5).contains(&n).then_some(n)
+}
+
+fn month(word: &str) -> Result<Month, ParseError> {
+    let word = if word == "sept" { "sep" } else { word };
+    MONTHS
+        .iter()
+        .position(|m| *m == word)
+        .or_else(|| MONTH_NAMES.iter().position(|m| *m == word))
+        .and_then(|i| Month::from_u8(i as u8 + 1))
+        .ok_or_else(|| ParseError::new(format!("unknown month {:?}", word)))
+}
+
+pub(crate) fn weekday(word: &str) -> Result<Days, ParseError> {
+    let word = match word {
+        "thur" | "thurs" => "thu",
+        "tues" => "tue",
+        w => w,
+    };
+    DAYS.iter()
+        .find(|(short, long, _)| *short == word || *long == word)
+        .map(|(_, _, d)| *d)
+        .ok_or_else(|| ParseError::new(format!("unknown weekday {:?}", word)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macro_grammar_at_runtime() {
+        assert_eq!(
+            from_natural("every weekly on SAT at 10:30 except month DEC"),
+            Ok(Schedule::new()
+                .every_on_day(Days::SAT)
+                .at(10, 30)
+                .except_on_month(Month::DEC))
+        );
+    }
+
+    #[test]
+    fn friendlier_spellings() {
+        assert_eq!(
+            from_natural("on 20 September at 10:00 pm"),
+            Ok(Schedule::new().date_with_time(9, 20, 22, 0))
+        );
+        assert_eq!(
+            from_natural("every day at 9am except saturdays"),
+            Ok(Schedule::new().at(9, 0).daily().except_on_day(Days::SAT))
+        );
+        assert_eq!(
+            from_natural("every hour between 9:00 and 17:00 10 times until mar 3"),
+            Ok(Schedule::new()
+                .hourly()
+                .between((9, 0), (17, 0))
+                .repeat_until_date(10, 3, Month::MAR))
+        );
+        assert_eq!(
+            from_natural("every day at 9:00 until march 3"),
+            Ok(Schedule::new()
+                .at(9, 0)
+                .daily()
+                .ending_on(EndDate::on(3, Month::MAR)))
+        );
+        let mondays = Schedule::new()
+            .every_on_day(Days::MON)
+            .every_minutes(30)
+            .between((9, 0), (11, 0));
+        assert_eq!(
+            from_natural("every monday every 30 minutes between 9:00 and 11:00"),
+            Ok(mondays.clone())
+        );
+        assert_eq!(
+            from_natural("every 30 minutes between 9:00 and 11:00 every monday"),
+            Ok(mondays)
+        );
+        assert_eq!(
+            from_natural("every day at 2:00 repeat 1000 times"),
+            Ok(Schedule::new().at(2, 0).daily().repeat(1000))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(from_natural("").is_err());
+        assert!(from_natural("every fortnight").is_err());
+        assert!(from_natural("daily weekly").is_err());
+        assert!(from_natural("at 9:00 at 10:00").is_err());
+        assert!(from_natural("on feb 30").is_err());
+        assert!(from_natural("at 13pm").is_err());
+        assert!(from_natural("every day until 3 mar until 4 mar").is_err());
+        assert!(from_natural("every").is_err());
+        assert!(from_natural("every 0 minutes").is_err());
+        assert!(from_natural("every 5 hours").is_err());
+        assert!(from_natural("every 15 minutes at 9:00").is_err());
+        assert!(from_natural("every 15 minutes every hour").is_err());
+    }
+
+    #[test]
+    fn display_reads_back() {
+        for text in [
+            "every 3rd saturday at 22:00",
+            "every 2nd friday at 8:00 repeat 3 times",
+            "every day at 10:00 except month dec",
+            "every week at 8:00 except 1st monday",
+            "every day at 9:00 except saturdays",
+            "every day at 9:00 except date 5",
+            "every month on day 5 at 9:00 until 3 mar",
+        ] {
+            let s = from_natural(text).unwrap();
+            assert_eq!(from_natural(&s.to_string()), Ok(s), "{}", text);
+        }
+    }
+}
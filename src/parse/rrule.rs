@@ -0,0 +1,354 @@
+// iCalendar RRULEs (RFC 5545), e.g. "FREQ=MONTHLY;BYDAY=3SA;BYHOUR=22".
+//
+// Every BY* part takes a single value and INTERVAL must be 1. UNTIL drops its
+// seconds and the Z suffix. `to_rrule` goes the other way.
+use std::str::FromStr;
+
+use super::ParseError;
+use crate::types::*;
+
+const DAYS: [(&str, Days); 7] = [
+    ("SU", Days::SUN),
+    ("MO", Days::MON),
+    ("TU", Days::TUE),
+    ("WE", Days::WED),
+    ("TH", Days::THUR),
+    ("FR", Days::FRI),
+    ("SA", Days::SAT),
+];
+
+#[derive(Default)]
+struct Parts {
+    freq: Option<Frequency>,
+    by_day: Option<(Option<u8>, Days)>,
+    by_month: Option<u8>,
+    by_month_day: Option<u8>,
+    by_hour: Option<u8>,
+    by_minute: Option<u8>,
+    count: Option<u32>,
+    until: Option<EndDate>,
+}
+
+pub fn from_rrule(rule: &str) -> Result<Schedule, ParseError> {
+    let rule = rule.trim();
+    let body = match rule.get(..6) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &rule[6..],
+        _ => rule,
+    };
+
+    let mut p = Parts::default();
+    for part in body.split(';').filter(|part| !part.is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(format!("expected KEY=VALUE, found {:?}", part)))?;
+        let key = key.to_ascii_uppercase();
+        let value = value.to_ascii_uppercase();
+        if value.contains(',') {
+            return Err(ParseError::new(format!("{} takes a single value", key)));
+        }
+        let duplicate = match key.as_str() {
+            "FREQ" => p.freq.replace(freq(&value)?).is_some(),
+            "BYDAY" => p.by_day.replace(by_day(&value)?).is_some(),
+            "BYMONTH" => p.by_month.replace(number(&key, &value, 1, 12)?).is_some(),
+            "BYMONTHDAY" => p
+                .by_month_day
+                .replace(number(&key, &value, 1, 31)?)
+                .is_some(),
+            "BYHOUR" => p.by_hour.replace(number(&key, &value, 0, 23)?).is_some(),
+            "BYMINUTE" => p.by_minute.replace(number(&key, &value, 0, 59)?).is_some(),
+            "COUNT" => p
+                .count
+                .replace(number(&key, &value, 0, u32::MAX)?)
+                .is_some(),
+            "UNTIL" => p.until.replace(until(&value)?).is_some(),
+            "INTERVAL" if value == "1" => false,
+            "INTERVAL" => return Err(ParseError::new("INTERVAL other than 1 is not supported")),
+            "WKST" => false,
+            _ => return Err(ParseError::new(format!("unsupported rule part {}", key))),
+        };
+        if duplicate {
+            return Err(ParseError::new(format!("{} given twice", key)));
+        }
+    }
+
+    let freq = p.freq.ok_or_else(|| ParseError::new("FREQ is required"))?;
+    let frequency = match (freq, p.by_day) {
+        (Frequency::Weekly, Some((Some(_), _))) => {
+            return Err(ParseError::new("BYDAY with an ordinal needs FREQ=MONTHLY"));
+        }
+        (Frequency::Weekly | Frequency::Monthly, Some(by_day)) => FrequencyPattern::ByDay(by_day),
+        (_, Some(_)) => {
+            return Err(ParseError::new("BYDAY needs FREQ=WEEKLY or FREQ=MONTHLY"));
+        }
+        (f, None) => FrequencyPattern::Frequency(f),
+    };
+
+    let mut s = Schedule::new();
+    if let Some(m) = p.by_month {
+        s = s.month(m);
+    }
+    if let Some(d) = p.by_month_day {
+        s = s.day(d);
+        if s.day.is_none() {
+            return Err(ParseError::new(format!(
+                "BYMONTHDAY {} does not exist in BYMONTH",
+                d
+            )));
+        }
+    }
+    if let Some(h) = p.by_hour {
+        s = s.hour(h);
+    }
+    if let Some(m) = p.by_minute {
+        s = s.minute(m);
+    }
+    s = s.every(frequency);
+    match (p.count, p.until) {
+        (Some(n), Some(date)) => {
+            s = s.repeat(n);
+            s.repeat = s.repeat.or_until(date);
+        }
+        (Some(n), None) => s = s.repeat(n),
+        (None, Some(date)) => s = s.ending_on(date),
+        (None, None) => {}
+    }
+    Ok(s)
+}
+
+// The rule `from_rrule` reads back into an equal schedule.
+pub fn to_rrule(s: &Schedule) -> Result<String, ParseError> {
+    let unsupported = [
+        (s.year.is_some(), "a year"),
+        (!s.years.is_empty(), "a list of years"),
+        (s.month_step.is_some(), "a month interval"),
+        (s.business_days.is_some(), "a business-day offset"),
+        (s.week_parity.is_some(), "alternate weeks"),
+        (s.recurring.except.is_some(), "an exception"),
+        (s.range.is_some(), "a time window"),
+        (s.offset.is_some(), "an offset"),
+        (s.delay.is_some(), "a relative start"),
+        (s.immediate, "an immediate run"),
+        (s.stride.is_some(), "a stride"),
+        (s.not_before.is_some(), "a start date"),
+        (s.leap_day_policy().is_some(), "a leap-day policy"),
+        (s.day_of_year.is_some(), "a day of the year"),
+        (s.fiscal.is_some(), "a fiscal period"),
+        (s.follows_sun(), "a solar time"),
+        (s.follows_moon(), "a lunar day"),
+        (!s.times.is_empty(), "several times of day"),
+        (s.interval.is_some(), "a minute interval"),
+        (s.hour_step.is_some(), "an hour step"),
+        (s.minute_step.is_some(), "a minute step"),
+        (!s.weekdays.is_empty(), "a set of weekdays"),
+        (!s.dates.is_empty(), "several dates"),
+        (
+            s.repeat.end_date().is_some_and(|d| {
+                (d.year.is_some() || d.day.is_some() || d.month.is_some())
+                    && (d.year.is_none() || d.day.is_none() || d.month.is_none())
+            }),
+            "an until date without a year, month and day",
+        ),
+    ];
+    if let Some((_, what)) = unsupported.iter().find(|(set, _)| *set) {
+        return Err(ParseError::new(format!(
+            "{} can't be expressed in RRULE",
+            what
+        )));
+    }
+    let frequency = s
+        .recurring
+        .frequency
+        .ok_or_else(|| ParseError::new("one-shot schedules can't be expressed in RRULE"))?;
+    let code = |d: Days| {
+        DAYS.iter()
+            .find(|(_, day)| *day == d)
+            .map_or("", |(c, _)| c)
+    };
+    let mut parts = vec![match frequency {
+        FrequencyPattern::Frequency(Frequency::Hourly) => "FREQ=HOURLY".to_string(),
+        FrequencyPattern::Frequency(Frequency::Daily) => "FREQ=DAILY".to_string(),
+        FrequencyPattern::Frequency(Frequency::Weekly) => "FREQ=WEEKLY".to_string(),
+        FrequencyPattern::Frequency(Frequency::Monthly) => "FREQ=MONTHLY".to_string(),
+        FrequencyPattern::ByDay((None, d)) => format!("FREQ=WEEKLY;BYDAY={}", code(d)),
+        FrequencyPattern::ByDay((Some(n), d)) => format!("FREQ=MONTHLY;BYDAY={}{}", n, code(d)),
+    }];
+    if let Some(m) = s.month {
+        parts.push(format!("BYMONTH={}", m as u8 + 1));
+    }
+    if let Some(d) = s.day {
+        parts.push(format!("BYMONTHDAY={}", d));
+    }
+    if let Some(h) = s.hour {
+        parts.push(format!("BYHOUR={}", h));
+    }
+    if let Some(m) = s.minute {
+        parts.push(format!("BYMINUTE={}", m));
+    }
+    if let Some(n) = s.repeat.count() {
+        parts.push(format!("COUNT={}", n));
+    }
+    if let Some(EndDate {
+        year: Some(y),
+        month: Some(m),
+        day: Some(d),
+        hour,
+        minute,
+    }) = s.repeat.end_date()
+    {
+        let date = format!("UNTIL={:04}{:02}{:02}", y, m as u8 + 1, d);
+        parts.push(match (hour, minute) {
+            (Some(h), Some(min)) => format!("{}T{:02}{:02}00", date, h, min),
+            _ => date,
+        });
+    }
+    Ok(parts.join(";"))
+}
+
+fn freq(value: &str) -> Result<Frequency, ParseError> {
+    match value {
+        "HOURLY" => Ok(Frequency::Hourly),
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        _ => Err(ParseError::new(format!("unsupported FREQ {}", value))),
+    }
+}
+
+fn number<T: FromStr + PartialOrd>(
+    key: &str,
+    value: &str,
+    min: T,
+    max: T,
+) -> Result<T, ParseError> {
+    value
+        .parse::<T>()
+        .ok()
+        .filter(|n| (min..=max).contains(n))
+        .ok_or_else(|| ParseError::new(format!("invalid {} {:?}", key, value)))
+}
+
+// "SA" or "3SA"; negative ordinals (from the end of the month) aren't supported.
+fn by_day(value: &str) -> Result<(Option<u8>, Days), ParseError> {
+    let split = value.len().saturating_sub(2);
+    let (nth, day) = (value.get(..split), value.get(split..));
+    let day = DAYS
+        .iter()
+        .find(|(code, _)| Some(*code) == day)
+        .map(|(_, d)| *d)
+        .ok_or_else(|| ParseError::new(format!("invalid BYDAY {:?}", value)))?;
+    let nth = match nth {
+        Some("") | None => None,
+        Some(n) => Some(number("BYDAY", n, 1, 5)?),
+    };
+    Ok((nth, day))
+}
+
+// YYYYMMDD with an optional THHMMSS[Z].
+fn until(value: &str) -> Result<EndDate, ParseError> {
+    let invalid = || ParseError::new(format!("invalid UNTIL {:?}", value));
+    if !value.is_ascii() {
+        return Err(invalid());
+    }
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time.trim_end_matches('Z'))),
+        None => (value, None),
+    };
+    if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let year = number("UNTIL", &date[0..4], 0, 9999)?;
+    let month = number("UNTIL", &date[4..6], 1, 12)?;
+    let day = number("UNTIL", &date[6..8], 1, 31)?;
+    let time = match time {
+        Some(t) if t.len() == 6 && t.bytes().all(|b| b.is_ascii_digit()) => Some((
+            number("UNTIL", &t[0..2], 0, 23)?,
+            number("UNTIL", &t[2..4], 0, 59)?,
+        )),
+        Some(_) => return Err(invalid()),
+        None => None,
+    };
+    let (hour, minute) = time.unzip();
+    Ok(EndDate {
+        year: Some(year),
+        month: Month::from_u8(month),
+        day: Some(day),
+        hour,
+        minute,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::DateTime;
+
+    #[test]
+    fn frequencies_and_by_parts() {
+        assert_eq!(
+            from_rrule("FREQ=DAILY;BYHOUR=9;BYMINUTE=30"),
+            Ok(Schedule::new().at(9, 30).daily())
+        );
+        assert_eq!(
+            from_rrule("RRULE:FREQ=WEEKLY;BYDAY=SA"),
+            Ok(Schedule::new().every_on_day(Days::SAT))
+        );
+        assert_eq!(
+            from_rrule("freq=monthly;bymonthday=20;byhour=22;byminute=30"),
+            Ok(Schedule::new().day_with_time(20, 22, 30).monthly())
+        );
+    }
+
+    #[test]
+    fn count_and_until() {
+        let s = from_rrule("FREQ=DAILY;COUNT=10;UNTIL=20260303T100000Z").unwrap();
+        let repeat = s.spec().repeat;
+
+        assert_eq!(repeat.count(), Some(10));
+        assert_eq!(
+            repeat.end_date(),
+            Some(EndDate::on(3, Month::MAR).in_year(2026).at(10, 0))
+        );
+    }
+
+    #[test]
+    fn to_rrule_round_trips() {
+        let schedules = [
+            Schedule::new().minute(15).hourly(),
+            Schedule::new().at(9, 30).daily().repeat(10),
+            Schedule::new().weekly(),
+            Schedule::new().day_with_time(20, 22, 30).monthly(),
+            Schedule::new().at(22, 0).every_nth_day(3, Days::SAT),
+            Schedule::new().every_on_day(Days::THUR),
+            Schedule::new().month(3).at(9, 0).daily(),
+            Schedule::new()
+                .weekly()
+                .repeat(50)
+                .until_datetime(DateTime::new(2026, 6, 1, 12, 0).unwrap()),
+            Schedule::new()
+                .at(9, 0)
+                .daily()
+                .ending_on(EndDate::on(3, Month::MAR).in_year(2026)),
+        ];
+        for s in &schedules {
+            let rule = to_rrule(s).unwrap();
+            assert_eq!(from_rrule(&rule).as_ref(), Ok(s), "{}", rule);
+        }
+        assert_eq!(
+            to_rrule(&schedules[4]),
+            Ok("FREQ=MONTHLY;BYDAY=3SA;BYHOUR=22;BYMINUTE=0".to_string())
+        );
+        assert!(to_rrule(&Schedule::new().date(9, 20)).is_err());
+        assert!(to_rrule(&Schedule::new().daily().except_on_day(Days::SUN)).is_err());
+    }
+
+    #[test]
+    fn unsupported_and_invalid() {
+        assert!(from_rrule("BYHOUR=9").is_err());
+        assert!(from_rrule("FREQ=YEARLY").is_err());
+        assert!(from_rrule("FREQ=DAILY;INTERVAL=2").is_err());
+        assert!(from_rrule("FREQ=WEEKLY;BYDAY=MO,WE").is_err());
+        assert!(from_rrule("FREQ=MONTHLY;BYDAY=-1FR").is_err());
+        assert!(from_rrule("FREQ=DAILY;FREQ=HOURLY").is_err());
+        assert!(from_rrule("FREQ=MONTHLY;BYMONTH=2;BYMONTHDAY=31").is_err());
+    }
+}
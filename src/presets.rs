@@ -0,0 +1,64 @@
+// Ready-made schedules for common jobs. Each returns a plain Schedule, so
+// anything the preset leaves unset can still be added with the usual setters.
+use crate::types::{Days, Schedule};
+
+// Every day at 02:00.
+pub fn nightly() -> Schedule {
+    Schedule::new().at(2, 0).daily()
+}
+
+// Every hour on the hour from 09:00 to 17:00, Monday to Friday.
+pub fn business_hours_hourly() -> Schedule {
+    let hours: Vec<(u8, u8)> = (9..=17).map(|h| (h, 0)).collect();
+    Schedule::new().on_days_at(
+        &[Days::MON, Days::TUE, Days::WED, Days::THUR, Days::FRI],
+        &hours,
+    )
+}
+
+// The 1st of every month, 00:00 unless a time is added.
+pub fn first_of_month() -> Schedule {
+    Schedule::new().on_day(1).monthly()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    #[test]
+    fn nightly_at_two() {
+        let from = DateTime::new(2025, 9, 1, 3, 0).unwrap();
+        assert_eq!(nightly().next_after(from), DateTime::new(2025, 9, 2, 2, 0));
+    }
+
+    #[test]
+    fn business_hours_inside_window() {
+        let from = DateTime::new(2025, 9, 1, 16, 30).unwrap();
+        let next: Vec<_> = business_hours_hourly().upcoming(from).take(2).collect();
+        assert_eq!(
+            next,
+            vec![
+                DateTime::new(2025, 9, 1, 17, 0).unwrap(),
+                DateTime::new(2025, 9, 2, 9, 0).unwrap()
+            ]
+        );
+        // Friday afternoon carries over to Monday.
+        let from = DateTime::new(2025, 9, 5, 16, 30).unwrap();
+        let next: Vec<_> = business_hours_hourly().upcoming(from).take(2).collect();
+        assert_eq!(
+            next,
+            vec![
+                DateTime::new(2025, 9, 5, 17, 0).unwrap(),
+                DateTime::new(2025, 9, 8, 9, 0).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn first_of_month_can_be_customized() {
+        let s = first_of_month().at(9, 30);
+        let from = DateTime::new(2025, 9, 2, 0, 0).unwrap();
+        assert_eq!(s.next_after(from), DateTime::new(2025, 10, 1, 9, 30));
+    }
+}
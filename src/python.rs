@@ -0,0 +1,207 @@
+// The `brahma` Python module: Schedule construction, parsing and upcoming(),
+// with the same semantics as the Rust API. Schedules are immutable values on
+// the Python side too, so every setter returns a new one. Date-times are
+// naive `datetime`s, read as wall-clock time; seconds are ignored.
+//
+// Build with maturin: `maturin build --features python,pyo3/extension-module`.
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDateAccess, PyDateTime, PyTimeAccess};
+
+use crate::parse::{ParseError, to_cron, weekday};
+use crate::time::DateTime;
+use crate::types::*;
+
+#[pyclass(name = "Schedule", module = "brahma", frozen, eq, hash, from_py_object)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PySchedule(pub Schedule);
+
+impl From<ParseError> for PyErr {
+    fn from(e: ParseError) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+#[pymethods]
+impl PySchedule {
+    #[new]
+    fn new() -> Self {
+        Self(Schedule::new())
+    }
+
+    // Cron, RRULE or natural language, as with Rust's `str::parse`.
+    #[staticmethod]
+    fn parse(expr: &str) -> PyResult<Self> {
+        Ok(Self(expr.parse::<Schedule>()?))
+    }
+
+    fn hourly(&self) -> Self {
+        self.with(|s| s.hourly())
+    }
+
+    fn daily(&self) -> Self {
+        self.with(|s| s.daily())
+    }
+
+    fn weekly(&self) -> Self {
+        self.with(|s| s.weekly())
+    }
+
+    fn monthly(&self) -> Self {
+        self.with(|s| s.monthly())
+    }
+
+    fn year(&self, year: i32) -> Self {
+        self.with(|s| s.year(year))
+    }
+
+    fn date(&self, month: u8, day: u8) -> Self {
+        self.with(|s| s.date(month, day))
+    }
+
+    fn on_day(&self, day: u8) -> Self {
+        self.with(|s| s.on_day(day))
+    }
+
+    fn at(&self, hour: u8, minute: u8) -> Self {
+        self.with(|s| s.at(hour, minute))
+    }
+
+    fn minute(&self, minute: u8) -> Self {
+        self.with(|s| s.minute(minute))
+    }
+
+    // Weekdays by name: "mon", "monday", ...
+    fn every_on_day(&self, day: &str) -> PyResult<Self> {
+        let day = parse_weekday(day)?;
+        Ok(self.with(|s| s.every_on_day(day)))
+    }
+
+    fn every_nth_day(&self, n: u8, day: &str) -> PyResult<Self> {
+        let day = parse_weekday(day)?;
+        Ok(self.with(|s| s.every_nth_day(n, day)))
+    }
+
+    fn except_on_day(&self, day: &str) -> PyResult<Self> {
+        let day = parse_weekday(day)?;
+        Ok(self.with(|s| s.except_on_day(day)))
+    }
+
+    fn except_on_month(&self, month: u8) -> Self {
+        self.with(|s| s.except_on_month_in_number(month))
+    }
+
+    fn repeat(&self, n: u32) -> Self {
+        self.with(|s| s.repeat(n))
+    }
+
+    fn between(&self, start: (u8, u8), end: (u8, u8)) -> Self {
+        self.with(|s| s.between(start, end))
+    }
+
+    // The next `count` occurrences at or after `start`.
+    #[pyo3(signature = (start, count = 10))]
+    fn upcoming<'py>(
+        &self,
+        py: Python<'py>,
+        start: &Bound<'py, PyDateTime>,
+        count: usize,
+    ) -> PyResult<Vec<Bound<'py, PyDateTime>>> {
+        self.0
+            .upcoming(from_py(start)?)
+            .take(count)
+            .map(|at| to_py(py, at))
+            .collect()
+    }
+
+    fn next_after<'py>(
+        &self,
+        py: Python<'py>,
+        after: &Bound<'py, PyDateTime>,
+    ) -> PyResult<Option<Bound<'py, PyDateTime>>> {
+        let from = from_py(after)?.next_minute();
+        self.0
+            .upcoming(from)
+            .next()
+            .map(|at| to_py(py, at))
+            .transpose()
+    }
+
+    fn to_cron(&self) -> PyResult<String> {
+        Ok(to_cron(&self.0)?)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Schedule: {}>", self.0)
+    }
+}
+
+impl PySchedule {
+    fn with(&self, f: impl FnOnce(Schedule) -> Schedule) -> Self {
+        Self(f(self.0.clone()))
+    }
+}
+
+fn parse_weekday(day: &str) -> PyResult<Days> {
+    Ok(weekday(&day.to_ascii_lowercase())?)
+}
+
+fn from_py(dt: &Bound<'_, PyDateTime>) -> PyResult<DateTime> {
+    DateTime::new(
+        dt.get_year(),
+        dt.get_month(),
+        dt.get_day(),
+        dt.get_hour(),
+        dt.get_minute(),
+    )
+    .ok_or_else(|| PyValueError::new_err("date-time out of range"))
+}
+
+fn to_py(py: Python<'_>, at: DateTime) -> PyResult<Bound<'_, PyDateTime>> {
+    PyDateTime::new(
+        py, at.year, at.month, at.day, at.hour, at.minute, 0, 0, None,
+    )
+}
+
+#[pymodule]
+fn brahma(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySchedule>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_from_python() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "brahma").unwrap();
+            brahma(&module).unwrap();
+            let globals = pyo3::types::PyDict::new(py);
+            globals.set_item("brahma", module).unwrap();
+            py.run(
+                c"
+import datetime
+s = brahma.Schedule().at(22, 0).every_nth_day(3, 'sat')
+first = s.upcoming(datetime.datetime(2025, 1, 1), 2)
+assert first == [datetime.datetime(2025, 1, 18, 22), datetime.datetime(2025, 2, 15, 22)], first
+assert brahma.Schedule.parse('0 22 * * SAT#3') == s
+assert str(s) == 'every 3rd Saturday of the month at 22:00', str(s)
+try:
+    brahma.Schedule.parse('nonsense')
+    raise AssertionError('parsed')
+except ValueError:
+    pass
+",
+                Some(&globals),
+                None,
+            )
+            .unwrap();
+        });
+    }
+}
@@ -0,0 +1,54 @@
+// Common interface over anything that produces occurrences, so code driving
+// schedules doesn't care whether it holds a single Schedule or a combination.
+use crate::composite::CompositeSchedule;
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+pub trait Recurrence {
+    // Occurrences at or after `from`, in time order.
+    fn upcoming(&self, from: DateTime) -> Box<dyn Iterator<Item = DateTime> + '_>;
+
+    fn next_after(&self, after: DateTime) -> Option<DateTime> {
+        self.upcoming(after.next_minute()).next()
+    }
+}
+
+impl Recurrence for Schedule {
+    fn upcoming(&self, from: DateTime) -> Box<dyn Iterator<Item = DateTime> + '_> {
+        Box::new(Schedule::upcoming(self, from))
+    }
+}
+
+impl Recurrence for CompositeSchedule {
+    fn upcoming(&self, from: DateTime) -> Box<dyn Iterator<Item = DateTime> + '_> {
+        CompositeSchedule::upcoming(self, from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Days;
+
+    #[test]
+    fn simple_and_composite_schedules_mix() {
+        let from = DateTime::new(2025, 7, 20, 0, 0).unwrap();
+        let schedules: Vec<Box<dyn Recurrence>> = vec![
+            Box::new(Schedule::new().monthly()),
+            Box::new(
+                Schedule::new()
+                    .monthly()
+                    .or(Schedule::new().every_on_day(Days::FRI)),
+            ),
+        ];
+        let next: Vec<_> = schedules.iter().map(|s| s.next_after(from)).collect();
+
+        assert_eq!(
+            next,
+            vec![
+                DateTime::new(2025, 8, 1, 0, 0),
+                DateTime::new(2025, 7, 25, 0, 0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,71 @@
+// Changes made to a Runtime's jobs, for tracing who touched a production
+// schedule and when. Only changes that took effect are recorded: adding a
+// name that is taken or removing one that isn't there leaves no entry.
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    // The runtime clock's now when the change was made.
+    pub at: DateTime,
+    // Whatever label was set with Runtime::set_actor at the time.
+    pub actor: Option<String>,
+    pub name: String,
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added(Schedule),
+    // Added to run `delay` after each success of `after`; see
+    // Runtime::add_after.
+    AddedAfter { after: String, delay: Duration },
+    // The schedule it had before.
+    Rescheduled(Schedule),
+    Removed,
+    Paused,
+    Resumed,
+    // Its next fire, from when to when.
+    Deferred { from: DateTime, to: DateTime },
+    // Run out of schedule; see Runtime::trigger_now.
+    Triggered { counted: bool },
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct AuditLog {
+    entries: VecDeque<AuditEntry>,
+    // Most recent entries kept; 0 keeps none.
+    max: usize,
+    pub(crate) actor: Option<String>,
+}
+
+impl AuditLog {
+    pub(crate) fn new(max: usize) -> Self {
+        Self {
+            max,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn record(&mut self, at: DateTime, name: &str, change: Change) {
+        if self.max == 0 {
+            return;
+        }
+        if self.entries.len() == self.max {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(AuditEntry {
+            at,
+            actor: self.actor.clone(),
+            name: name.to_string(),
+            change,
+        });
+    }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.entries.iter()
+    }
+}
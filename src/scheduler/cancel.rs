@@ -0,0 +1,77 @@
+// Cooperative cancellation for long-running tasks. A task checks
+// `ctx.cancel.is_cancelled()` between steps and returns early; nothing is
+// interrupted for it. Each job's token is a child of its Runtime's, so
+// shutting the runtime down cancels every job, while cancelling one job's
+// token leaves the rest alone. Clones share the same state, so a token can be
+// handed to another thread, a signal handler say, to cancel from there.
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    parent: Option<CancellationToken>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Cancelled along with this token, or on its own.
+    pub fn child(&self) -> Self {
+        Self(Arc::new(Inner {
+            cancelled: AtomicBool::new(false),
+            parent: Some(self.clone()),
+        }))
+    }
+
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+            || self.0.parent.as_ref().is_some_and(|p| p.is_cancelled())
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CancellationToken")
+            .field(&self.is_cancelled())
+            .finish()
+    }
+}
+
+// Two tokens are equal when they are clones of each other.
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn children_follow_their_parent() {
+        let root = CancellationToken::new();
+        let (a, b) = (root.child(), root.child());
+        a.cancel();
+        assert!(a.is_cancelled());
+        assert!(!b.is_cancelled() && !root.is_cancelled());
+
+        root.clone().cancel();
+        assert!(b.is_cancelled());
+        assert_ne!(a, b);
+        assert_eq!(root, root.clone());
+    }
+}
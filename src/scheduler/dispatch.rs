@@ -0,0 +1,206 @@
+// Hand-off between the timer loop and worker threads. Fires are spread over
+// several independently locked shards by job name, so busy jobs only contend
+// with others on their shard, and every fire of one job stays in order.
+// Workers drain their own shard first and steal from the rest when idle.
+//
+// A bounded queue holds at most `capacity` fires; what happens past that is
+// up to its Overflow policy. Runtime::dispatch_due reports a full queue to
+// event subscribers as Saturated.
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use super::shard_for;
+use crate::time::DateTime;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Overflow {
+    // Backpressure: refuse new fires, and have Scheduler::dispatch_due keep
+    // them queued on its side until workers catch up.
+    #[default]
+    Wait,
+    // Make room by evicting the oldest fire on the same shard.
+    DropOldest,
+    // Keep what is queued and drop the new fire.
+    DropNewest,
+}
+
+#[derive(Debug)]
+pub struct DispatchQueue {
+    shards: Vec<Mutex<VecDeque<(String, DateTime)>>>,
+    len: AtomicUsize,
+    // Fires lost to DropOldest or DropNewest so far.
+    dropped: AtomicU64,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+impl DispatchQueue {
+    // At least one shard; one per worker is a good start.
+    pub fn new(shards: usize) -> Self {
+        Self::bounded(shards, usize::MAX, Overflow::Wait)
+    }
+
+    pub fn bounded(shards: usize, capacity: usize, overflow: Overflow) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(VecDeque::new()))
+                .collect(),
+            len: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+            capacity,
+            overflow,
+        }
+    }
+
+    // On a full queue the fire that did not make it is handed back: the new
+    // one when waiting or dropping the newest, the evicted one when dropping
+    // the oldest.
+    pub fn push(&self, name: String, at: DateTime) -> Result<(), (String, DateTime)> {
+        let shard = shard_for(&name, self.shards.len());
+        // Counted first so a racing pop can never take len below zero.
+        let reserved = self
+            .len
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.capacity).then_some(n + 1)
+            })
+            .is_ok();
+        if reserved {
+            self.lock(shard).push_back((name, at));
+            return Ok(());
+        }
+        match self.overflow {
+            Overflow::Wait => return Err((name, at)),
+            Overflow::DropNewest => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                return Err((name, at));
+            }
+            Overflow::DropOldest => self.dropped.fetch_add(1, Ordering::Relaxed),
+        };
+        let mut queue = self.lock(shard);
+        match queue.pop_front() {
+            Some(evicted) => {
+                queue.push_back((name, at));
+                Err(evicted)
+            }
+            // Full, but not on this shard; nothing of ours to evict.
+            None => Err((name, at)),
+        }
+    }
+
+    pub fn pop(&self, worker: usize) -> Option<(String, DateTime)> {
+        if self.is_empty() {
+            return None;
+        }
+        let n = self.shards.len();
+        (0..n).find_map(|i| {
+            let fire = self.lock((worker + i) % n).pop_front()?;
+            self.len.fetch_sub(1, Ordering::Release);
+            Some(fire)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    // How many more fires fit before the overflow policy kicks in.
+    pub fn room(&self) -> usize {
+        self.capacity.saturating_sub(self.len())
+    }
+
+    // A worker that panicked mid-push leaves a consistent VecDeque behind.
+    fn lock(&self, shard: usize) -> std::sync::MutexGuard<'_, VecDeque<(String, DateTime)>> {
+        self.shards[shard]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn every_fire_is_taken_exactly_once() {
+        let queue = Arc::new(DispatchQueue::new(4));
+        let at = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        for i in 0..1000 {
+            queue
+                .push(format!("job-{}", i % 37), at.add_minutes(i))
+                .unwrap();
+        }
+        let workers: Vec<_> = (0..4)
+            .map(|w| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || std::iter::from_fn(|| queue.pop(w)).count())
+            })
+            .collect();
+        let taken: usize = workers.into_iter().map(|w| w.join().unwrap()).sum();
+
+        assert_eq!(taken, 1000);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn one_job_stays_in_order() {
+        let queue = DispatchQueue::new(8);
+        let at = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        for i in 0..5 {
+            queue.push("sync".to_string(), at.add_minutes(i)).unwrap();
+        }
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop(3))
+            .map(|(_, t)| t)
+            .collect();
+
+        assert!(order.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(order.len(), 5);
+    }
+
+    #[test]
+    fn bounded_queue_applies_its_policy() {
+        let at = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        let fire = |i: i64| ("sync".to_string(), at.add_minutes(i));
+
+        let waiting = DispatchQueue::bounded(1, 2, Overflow::Wait);
+        assert_eq!(waiting.push(fire(0).0, fire(0).1), Ok(()));
+        assert_eq!(waiting.push(fire(1).0, fire(1).1), Ok(()));
+        assert_eq!(waiting.push(fire(2).0, fire(2).1), Err(fire(2)));
+        assert_eq!(waiting.room(), 0);
+
+        let dropping = DispatchQueue::bounded(1, 2, Overflow::DropOldest);
+        for i in 0..3 {
+            let (name, at) = fire(i);
+            let _ = dropping.push(name, at);
+        }
+        assert_eq!(dropping.len(), 2);
+        assert_eq!(dropping.pop(0), Some(fire(1)));
+        assert_eq!(dropping.dropped(), 1);
+
+        let refusing = DispatchQueue::bounded(1, 2, Overflow::DropNewest);
+        for i in 0..4 {
+            let (name, at) = fire(i);
+            let _ = refusing.push(name, at);
+        }
+        assert_eq!(refusing.pop(0), Some(fire(0)));
+        assert_eq!(refusing.dropped(), 2);
+    }
+}
@@ -0,0 +1,80 @@
+// What happens inside a Runtime, as events for monitoring and UIs. Every
+// subscriber gets its own channel and its own copy of each event; one that
+// hangs up is dropped on the next send.
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use super::supervisor::Escalation;
+use crate::time::DateTime;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    // Registered; `next` is its first fire, if it has one.
+    JobScheduled {
+        name: String,
+        next: Option<DateTime>,
+    },
+    JobStarted {
+        name: String,
+        at: DateTime,
+    },
+    JobFinished {
+        name: String,
+        at: DateTime,
+    },
+    // Returned an error or panicked.
+    JobFailed {
+        name: String,
+        at: DateTime,
+        error: String,
+    },
+    // Came due but was not run.
+    JobSkipped {
+        name: String,
+        at: DateTime,
+        reason: String,
+    },
+    // Failed often enough for its RestartPolicy to step in.
+    JobEscalated {
+        name: String,
+        escalation: Escalation,
+    },
+    // A dispatch left the worker queue full: `dropped` fires were lost to
+    // its overflow policy on the way, and `held` are due but still waiting
+    // on the timer side.
+    Saturated {
+        capacity: usize,
+        dropped: u64,
+        held: usize,
+    },
+    // Ran out of occurrences and was unregistered, or kept as completed.
+    JobCompleted {
+        name: String,
+    },
+    // This instance's LeadershipProvider changed its answer as of `at`.
+    LeadershipAcquired {
+        at: DateTime,
+    },
+    LeadershipLost {
+        at: DateTime,
+    },
+    SchedulerShutdown,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Subscribers(pub(crate) Vec<Sender<Event>>);
+
+impl Subscribers {
+    pub(crate) fn subscribe(&mut self) -> Receiver<Event> {
+        let (tx, rx) = mpsc::channel();
+        self.0.push(tx);
+        rx
+    }
+
+    pub(crate) fn emit(&mut self, event: Event) {
+        // Nobody listening is the common case; skip building the clones.
+        if self.0.is_empty() {
+            return;
+        }
+        self.0.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
@@ -0,0 +1,207 @@
+// A read-only status endpoint for small services that want a dashboard
+// without writing glue code. Bind a listener and hand it over on a thread of
+// its own; the runtime is only locked long enough to take a snapshot:
+//
+//     let listener = TcpListener::bind("127.0.0.1:9090")?;
+//     thread::spawn(move || serve_status(listener, runtime));
+//
+// GET /jobs lists every job with its next and last run and its stats,
+// GET /history the recent fires (see SchedulerConfig::max_history). Times
+// are "2025-09-20 22:00" strings and durations are in seconds.
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use super::{JobInfo, JobState, Outcome, Runtime};
+
+// A client gets this long in all, and this many bytes, to send its request
+// before it's dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_LIMIT: u64 = 8 * 1024;
+
+// Serves each connection on a thread of its own, so a slow client only holds
+// up itself, until accepting fails.
+pub fn serve_status(listener: TcpListener, runtime: Arc<Mutex<Runtime>>) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let runtime = Arc::clone(&runtime);
+        thread::spawn(move || {
+            if let Err(e) = respond(stream, &runtime) {
+                eprintln!("Status request failed: {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+// Reads until `by`, each read waiting only for what is left, so a client
+// trickling a byte at a time is cut off as well as one that goes quiet.
+struct Deadline {
+    stream: TcpStream,
+    by: Instant,
+}
+
+impl Read for Deadline {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.by.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "request took too long",
+            ));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+fn respond(stream: TcpStream, runtime: &Mutex<Runtime>) -> io::Result<()> {
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let deadline = Deadline {
+        stream,
+        by: Instant::now() + REQUEST_TIMEOUT,
+    };
+    let mut reader = BufReader::new(deadline.take(REQUEST_LIMIT));
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // The headers aren't needed, but are read so the client sees a clean close.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let (status, body) = {
+        let runtime = runtime.lock().unwrap_or_else(PoisonError::into_inner);
+        route(&request, &runtime)
+    };
+    let body = body.to_string();
+    write!(
+        &mut reader.get_mut().get_mut().stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn route(request: &str, runtime: &Runtime) -> (&'static str, Value) {
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    if method != "GET" {
+        return ("405 Method Not Allowed", json!({ "error": "read-only" }));
+    }
+    match path.split('?').next().unwrap_or("") {
+        "/jobs" => (
+            "200 OK",
+            Value::Array(
+                runtime
+                    .jobs()
+                    .iter()
+                    .map(|job| job_json(job, runtime))
+                    .collect(),
+            ),
+        ),
+        "/history" => (
+            "200 OK",
+            runtime
+                .scheduler()
+                .history()
+                .map(|fire| json!({ "name": fire.name, "at": fire.at.to_string() }))
+                .collect(),
+        ),
+        _ => ("404 Not Found", json!({ "error": "not found" })),
+    }
+}
+
+fn job_json(job: &JobInfo, runtime: &Runtime) -> Value {
+    let stats = runtime.stats(&job.name).unwrap_or_default();
+    json!({
+        "name": job.name,
+        "description": job.description,
+        "state": match job.state {
+            JobState::Scheduled => "scheduled",
+            JobState::Paused => "paused",
+            JobState::Completed => "completed",
+        },
+        "next": job.next.map(|at| at.to_string()),
+        "tags": job.tags,
+        "last_run": job.last_run.map(|at| at.to_string()),
+        "last_outcome": job.last_outcome.as_ref().map(|outcome| match outcome {
+            Outcome::Succeeded => json!({ "status": "succeeded" }),
+            Outcome::Failed(e) => json!({ "status": "failed", "error": e }),
+            Outcome::Panicked(e) => json!({ "status": "panicked", "error": e }),
+        }),
+        "stats": {
+            "runs": stats.runs,
+            "failures": stats.failures,
+            "average_seconds": stats.average_duration().as_secs_f64(),
+            "max_seconds": stats.max_duration.as_secs_f64(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{ManualClock, SchedulerConfig};
+    use crate::time::DateTime;
+    use crate::types::Schedule;
+
+    fn get(addr: std::net::SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_jobs_and_history() {
+        let clock = ManualClock::new(DateTime::new(2025, 1, 1, 0, 0).unwrap());
+        let config = SchedulerConfig {
+            max_history: 10,
+            ..SchedulerConfig::default()
+        };
+        let mut rt = Runtime::with_config(clock.clone(), config);
+        rt.add("report", Schedule::new().at(1, 0).daily(), |_| {
+            Err::<(), _>("no data")
+        })
+        .unwrap();
+        clock.advance(60);
+        rt.run_pending();
+        let runtime = Arc::new(Mutex::new(rt));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shared = Arc::clone(&runtime);
+        thread::spawn(move || serve_status(listener, shared));
+
+        let response = get(addr, "GET /jobs HTTP/1.1\r\nHost: x\r\n\r\n");
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK"));
+        let jobs: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(jobs[0]["name"], "report");
+        assert_eq!(jobs[0]["state"], "scheduled");
+        assert_eq!(jobs[0]["next"], "2025-01-02 01:00");
+        assert_eq!(jobs[0]["last_outcome"]["error"], "no data");
+        assert_eq!(jobs[0]["stats"]["failures"], 1);
+
+        let response = get(addr, "GET /history HTTP/1.1\r\n\r\n");
+        let body = response.split_once("\r\n\r\n").unwrap().1;
+        assert_eq!(
+            serde_json::from_str::<Value>(body).unwrap(),
+            json!([{ "name": "report", "at": "2025-01-01 01:00" }])
+        );
+
+        assert!(get(addr, "DELETE /jobs HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 405"));
+        assert!(get(addr, "GET /nope HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404"));
+
+        // A client that connects and says nothing doesn't hold up the next.
+        let _quiet = TcpStream::connect(addr).unwrap();
+        assert!(get(addr, "GET /jobs HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 200"));
+    }
+}
@@ -0,0 +1,128 @@
+// Leader election for running the same jobs on several instances, where only
+// one of them should act. A Runtime given a LeadershipProvider asks it before
+// every batch and only runs tasks while it leads. A follower still moves its
+// schedules past what comes due, reporting each fire as JobSkipped, so a new
+// leader carries on from the time it took over instead of replaying what the
+// old one already ran. Changes either way are sent as LeadershipAcquired and
+// LeadershipLost events.
+//
+// The election itself stays with the deployment: a Kubernetes lease, an etcd
+// key, a database lock. Wrap the client in a provider, or have its watcher
+// set a LeaderFlag.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::time::DateTime;
+
+pub trait LeadershipProvider: Send {
+    // Whether this instance leads as of `now`. Asked at the start of every
+    // run_pending and dispatch_due, so it should answer from what it already
+    // knows and renew its lease elsewhere.
+    fn is_leader(&mut self, now: DateTime) -> bool;
+}
+
+impl<F: FnMut(DateTime) -> bool + Send> LeadershipProvider for F {
+    fn is_leader(&mut self, now: DateTime) -> bool {
+        self(now)
+    }
+}
+
+// Leadership decided on another thread and handed over through a shared
+// flag. Clones share it; it starts out following.
+#[derive(Debug, Clone, Default)]
+pub struct LeaderFlag(Arc<AtomicBool>);
+
+impl LeaderFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, leading: bool) {
+        self.0.store(leading, Ordering::Release);
+    }
+
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+impl LeadershipProvider for LeaderFlag {
+    fn is_leader(&mut self, _now: DateTime) -> bool {
+        self.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{Event, ManualClock, Runtime};
+    use crate::types::Schedule;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn only_the_leader_runs() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let flag = LeaderFlag::new();
+        rt.set_leadership(flag.clone());
+        rt.add("sync", Schedule::new().minute(0).hourly(), |_| ())
+            .unwrap();
+        let events = rt.subscribe();
+
+        clock.set(dt(2025, 1, 1, 2, 0));
+        assert_eq!(rt.run_pending(), 0);
+        assert!(!rt.is_leader());
+        flag.set(true);
+        clock.set(dt(2025, 1, 1, 3, 0));
+        // Only the 03:00 fire; the follower moved past the earlier ones.
+        assert_eq!(rt.run_pending(), 1);
+        assert!(rt.is_leader());
+        flag.set(false);
+        clock.set(dt(2025, 1, 1, 4, 0));
+        assert_eq!(rt.run_pending(), 0);
+
+        let events: Vec<Event> = events.try_iter().collect();
+        let skipped = |hour| Event::JobSkipped {
+            name: "sync".to_string(),
+            at: dt(2025, 1, 1, hour, 0),
+            reason: "not the leader".to_string(),
+        };
+        assert_eq!(events[..3], [skipped(0), skipped(1), skipped(2)]);
+        assert_eq!(
+            events[3],
+            Event::LeadershipAcquired {
+                at: dt(2025, 1, 1, 3, 0)
+            }
+        );
+        assert_eq!(
+            events[6..],
+            [
+                Event::LeadershipLost {
+                    at: dt(2025, 1, 1, 4, 0)
+                },
+                skipped(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn providers_see_the_clock() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        assert!(rt.is_leader());
+        // Leads in even hours only.
+        rt.set_leadership(|now: DateTime| now.hour.is_multiple_of(2));
+        rt.add("sync", Schedule::new().minute(0).hourly(), |_| ())
+            .unwrap();
+        let ran: Vec<usize> = (0..4)
+            .map(|hour| {
+                clock.set(dt(2025, 1, 1, hour, 0));
+                rt.run_pending()
+            })
+            .collect();
+        assert_eq!(ran, [1, 0, 1, 0]);
+    }
+}
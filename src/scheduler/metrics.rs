@@ -0,0 +1,139 @@
+// Metric names, all through the `metrics` facade so any exporter works:
+//
+//   brahma_job_runs_started_total{job}      counter
+//   brahma_job_runs_succeeded_total{job}    counter
+//   brahma_job_runs_failed_total{job}       counter, panics included
+//   brahma_job_run_duration_seconds{job}    histogram
+//   brahma_schedule_lag_seconds{job}        histogram, due time to start
+//   brahma_queue_depth                      gauge, fires due but not started
+//   brahma_jobs                             gauge, registered jobs
+//
+// The `otel` feature records the same through OpenTelemetry; see otel.rs.
+// Without either feature these are no-ops the compiler removes.
+use super::runtime::Outcome;
+use crate::time::DateTime;
+
+#[cfg(feature = "metrics")]
+use ::metrics::{counter, gauge, histogram};
+
+// Measures one run, from just before the task starts.
+pub(crate) struct RunProbe {
+    #[cfg(any(feature = "metrics", feature = "otel"))]
+    job: String,
+    #[cfg(any(feature = "metrics", feature = "otel"))]
+    started: std::time::Instant,
+}
+
+#[cfg(any(feature = "metrics", feature = "otel"))]
+impl RunProbe {
+    pub(crate) fn start(job: &str, at: DateTime, now: DateTime, queued: usize) -> Self {
+        let lag = (now.unix() - at.unix()).max(0) as f64;
+        #[cfg(feature = "metrics")]
+        {
+            gauge!("brahma_queue_depth").set(queued as f64);
+            counter!("brahma_job_runs_started_total", "job" => job.to_string()).increment(1);
+            histogram!("brahma_schedule_lag_seconds", "job" => job.to_string()).record(lag);
+        }
+        #[cfg(feature = "otel")]
+        super::otel::run_started(job, lag, queued);
+        Self {
+            job: job.to_string(),
+            started: std::time::Instant::now(),
+        }
+    }
+
+    pub(crate) fn finish(self, outcome: &Outcome) {
+        let took = self.started.elapsed().as_secs_f64();
+        #[cfg(feature = "metrics")]
+        {
+            let name = if outcome.is_success() {
+                "brahma_job_runs_succeeded_total"
+            } else {
+                "brahma_job_runs_failed_total"
+            };
+            counter!(name, "job" => self.job.clone()).increment(1);
+            histogram!("brahma_job_run_duration_seconds", "job" => self.job.clone()).record(took);
+        }
+        #[cfg(feature = "otel")]
+        super::otel::run_finished(&self.job, outcome, took);
+    }
+}
+
+#[cfg(not(any(feature = "metrics", feature = "otel")))]
+impl RunProbe {
+    pub(crate) fn start(_: &str, _: DateTime, _: DateTime, _: usize) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn finish(self, _: &Outcome) {}
+}
+
+pub(crate) fn batch_done(_jobs: usize) {
+    #[cfg(feature = "metrics")]
+    {
+        gauge!("brahma_queue_depth").set(0.0);
+        gauge!("brahma_jobs").set(_jobs as f64);
+    }
+    #[cfg(feature = "otel")]
+    super::otel::batch_done(_jobs);
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::super::{ManualClock, Runtime};
+    use crate::time::DateTime;
+    use crate::types::Schedule;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[test]
+    fn runs_are_counted_and_timed() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            let clock = ManualClock::new(DateTime::new(2025, 1, 1, 0, 0).unwrap());
+            let mut rt = Runtime::new(clock.clone());
+            rt.add("ok", Schedule::new().minute(0).hourly(), |_| {})
+                .unwrap();
+            rt.add("bad", Schedule::new().minute(0).hourly(), |_| {
+                Err::<(), _>("no")
+            })
+            .unwrap();
+            clock.advance(90);
+            rt.run_pending();
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let value = |name: &str, job: Option<&str>| {
+            snapshot
+                .iter()
+                .find(|(key, ..)| {
+                    let key = key.key();
+                    key.name() == name
+                        && job.is_none_or(|job| key.labels().any(|l| l.value() == job))
+                })
+                .map(|(.., v)| v)
+        };
+        assert_eq!(
+            value("brahma_job_runs_started_total", Some("ok")),
+            Some(&DebugValue::Counter(2))
+        );
+        assert_eq!(
+            value("brahma_job_runs_failed_total", Some("bad")),
+            Some(&DebugValue::Counter(2))
+        );
+        assert_eq!(value("brahma_job_runs_failed_total", Some("ok")), None);
+        match value("brahma_schedule_lag_seconds", Some("ok")) {
+            Some(DebugValue::Histogram(lags)) => {
+                assert_eq!(
+                    lags.iter().map(|l| l.into_inner()).collect::<Vec<_>>(),
+                    [5400.0, 1800.0]
+                )
+            }
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(
+            value("brahma_jobs", None),
+            Some(&DebugValue::Gauge(2.0.into()))
+        );
+    }
+}
@@ -0,0 +1,943 @@
+// Runtime core: named schedules queued by their next fire time, so a run
+// loop can sleep until the earliest one and only touch jobs that are
+// actually due. The default queue is a min-heap, where adding, removing and
+// firing a job are O(log n); see wheel.rs for the alternative.
+mod audit;
+mod cancel;
+mod dispatch;
+mod events;
+#[cfg(feature = "http")]
+mod http;
+mod leader;
+mod metrics;
+#[cfg(feature = "otel")]
+mod otel;
+mod runtime;
+mod sharded;
+mod supervisor;
+mod trace;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod web;
+mod wheel;
+
+use std::cmp::Reverse;
+use std::collections::binary_heap::PeekMut;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+pub use self::audit::{AuditEntry, Change};
+pub use self::cancel::CancellationToken;
+pub use self::dispatch::{DispatchQueue, Overflow};
+pub use self::events::Event;
+#[cfg(feature = "http")]
+pub use self::http::serve_status;
+pub use self::leader::{LeaderFlag, LeadershipProvider};
+#[cfg(feature = "otel")]
+pub use self::otel::otel_layer;
+#[cfg(any(test, feature = "proptest"))]
+pub(crate) use self::runtime::catch_panic;
+pub use self::runtime::{
+    Clock, IntoOutcome, JobContext, JobError, JobHandle, JobId, JobInfo, JobState, JobStats,
+    ManualClock, Outcome, Runtime, Streak, SystemClock,
+};
+pub use self::sharded::ShardedScheduler;
+pub use self::supervisor::{Escalation, RestartPolicy};
+pub use self::trace::{Divergence, Trace};
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use self::web::run_web;
+use self::wheel::TimerWheel;
+#[cfg(feature = "jiff")]
+pub use crate::jiff_support::ZonedClock;
+use crate::occurrence::{Plan, Progress, Remaining};
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+// Next fire time, the job's generation and its name.
+type Entry = (DateTime, u64, String);
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Backend {
+    #[default]
+    Heap,
+    // For tens of thousands of short-interval jobs: O(1) inserts and expiry,
+    // at the cost of turning the wheel a minute at a time and a full scan in
+    // next_wakeup.
+    Wheel,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct SchedulerConfig {
+    pub backend: Backend,
+    // Most recent fires kept for `history`; older ones are evicted. 0 keeps
+    // none.
+    pub max_history: usize,
+    // Most recent changes a Runtime keeps for `audit_log`; 0 keeps none.
+    pub max_audit: usize,
+    // Whether a Runtime keeps jobs that ran out, as JobState::Completed, until
+    // they are removed; by default they go as soon as they are done.
+    pub keep_completed: bool,
+    // How much of a backlog a Runtime replays after it was not driven for a
+    // while; all of it by default.
+    pub catch_up: CatchUp,
+}
+
+// Limits on replaying missed fires, applied per run_pending. What falls
+// outside is skipped with a JobSkipped event rather than run late; it still
+// counts towards repeat limits.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct CatchUp {
+    // Only the most recent this many fires of each job.
+    pub max_fires: Option<u32>,
+    // Only fires due within this long of now.
+    pub max_age: Option<Duration>,
+}
+
+impl CatchUp {
+    // Splits a tick's fires into those to run and those to skip, both
+    // oldest first.
+    pub(crate) fn split(&self, due: Vec<DueJob>, now: DateTime) -> (Vec<DueJob>, Vec<DueJob>) {
+        if *self == CatchUp::default() {
+            return (due, Vec::new());
+        }
+        let oldest = self
+            .max_age
+            .map(|age| now.add_minutes(-((age.as_secs() / 60) as i64)));
+        let mut seen: HashMap<&str, u32> = HashMap::new();
+        let mut keep = vec![false; due.len()];
+        for (i, job) in due.iter().enumerate().rev() {
+            let n = seen.entry(job.name.as_str()).or_default();
+            *n += 1;
+            keep[i] = self.max_fires.is_none_or(|max| *n <= max)
+                && oldest.is_none_or(|oldest| job.at >= oldest);
+        }
+        let (run, skip): (Vec<_>, Vec<_>) = due.into_iter().zip(keep).partition(|(_, k)| *k);
+        (
+            run.into_iter().map(|(job, _)| job).collect(),
+            skip.into_iter().map(|(job, _)| job).collect(),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    jobs: HashMap<String, Job>,
+    // Removed or rescheduled jobs leave their old entry behind; it is told
+    // apart by its generation and dropped when it comes up.
+    queue: Queue,
+    generation: u64,
+    last_tick: Option<DateTime>,
+    // Worst lag among the fires the last tick took.
+    tick_lag: Duration,
+    history: VecDeque<DueJob>,
+    // Runs asked for through `trigger`, by when they are due and then in the
+    // order they were asked for.
+    triggered: VecDeque<(String, DateTime)>,
+    max_history: usize,
+    // Set while recording; see trace.rs.
+    trace: Option<Trace>,
+}
+
+// One fire handed back by `Scheduler::tick`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DueJob {
+    pub name: String,
+    // When it was due, which may be before the tick that reported it.
+    pub at: DateTime,
+}
+
+// What `Scheduler::health` saw, for liveness and readiness probes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Health {
+    pub last_tick: Option<DateTime>,
+    // How long the oldest fire still waiting has been due. A loop that has
+    // stopped ticking shows up here once anything comes due.
+    pub lag: Duration,
+    pub last_tick_lag: Duration,
+    // Jobs whose next fire is due but not yet taken.
+    pub overdue: usize,
+}
+
+impl Health {
+    pub fn is_healthy(&self, max_lag: Duration) -> bool {
+        self.lag <= max_lag && self.last_tick_lag <= max_lag
+    }
+}
+
+#[derive(Debug)]
+enum Queue {
+    Heap(BinaryHeap<Reverse<Entry>>),
+    Wheel(TimerWheel),
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Queue::Heap(BinaryHeap::new())
+    }
+}
+
+#[derive(Debug)]
+struct Job {
+    schedule: Schedule,
+    plan: Plan,
+    progress: Progress,
+    generation: u64,
+    // The fire it is queued for.
+    next: Option<DateTime>,
+    // Out of the queue until resumed.
+    paused: bool,
+    // Only fires through `trigger`; see add_on_demand.
+    on_demand: bool,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: SchedulerConfig) -> Self {
+        Self {
+            queue: match config.backend {
+                Backend::Heap => Queue::Heap(BinaryHeap::new()),
+                Backend::Wheel => Queue::Wheel(TimerWheel::default()),
+            },
+            max_history: config.max_history,
+            ..Self::default()
+        }
+    }
+
+    // Registers `schedule` as of `now`: repeat counts and `Schedule::after`
+    // delays start from here.
+    pub fn add(&mut self, name: impl Into<String>, schedule: Schedule, now: DateTime) {
+        let name = name.into();
+        if self.jobs.contains_key(&name) {
+            eprintln!("Job {} already scheduled. Ignoring.", name);
+            return;
+        }
+        let schedule = schedule.resolve(now);
+        let progress = schedule.start(now);
+        self.generation += 1;
+        let mut job = Job {
+            plan: schedule.plan(),
+            schedule,
+            progress,
+            generation: self.generation,
+            next: None,
+            paused: false,
+            on_demand: false,
+        };
+        if let Some(at) = job.advance() {
+            self.queue.push((at, job.generation, name.clone()));
+        }
+        self.jobs.insert(name, job);
+    }
+
+    // Registers `name` with no schedule of its own, for runs that only come
+    // through `trigger`. It stays registered with nothing queued until it is
+    // removed, or given a schedule with `reschedule`.
+    pub fn add_on_demand(&mut self, name: impl Into<String>, now: DateTime) {
+        let name = name.into();
+        if self.jobs.contains_key(&name) {
+            eprintln!("Job {} already scheduled. Ignoring.", name);
+            return;
+        }
+        let schedule = Schedule::new();
+        self.generation += 1;
+        let job = Job {
+            plan: schedule.plan(),
+            progress: schedule.start(now),
+            schedule,
+            generation: self.generation,
+            next: None,
+            paused: false,
+            on_demand: true,
+        };
+        self.jobs.insert(name, job);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Schedule> {
+        self.triggered.retain(|(n, _)| n != name);
+        self.jobs.remove(name).map(|job| job.schedule)
+    }
+
+    // Swaps in `schedule` as of `now`, keeping the job's name and paused
+    // state, and returns the old one. Fires the last tick already took aren't
+    // handed out again.
+    pub fn reschedule(
+        &mut self,
+        name: &str,
+        schedule: Schedule,
+        now: DateTime,
+    ) -> Option<Schedule> {
+        let job = self.jobs.get_mut(name)?;
+        let from = match self.last_tick {
+            Some(last) if last >= now => last.next_minute(),
+            _ => now,
+        };
+        let schedule = schedule.resolve(from);
+        let old = std::mem::replace(&mut job.schedule, schedule);
+        job.plan = job.schedule.plan();
+        job.progress = job.schedule.start(from);
+        job.on_demand = false;
+        self.generation += 1;
+        job.generation = self.generation;
+        let next = job.advance();
+        if let Some(at) = next.filter(|_| !job.paused) {
+            self.queue.push((at, job.generation, name.to_string()));
+        }
+        Some(old)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Schedule> {
+        self.jobs.get(name).map(|job| &job.schedule)
+    }
+
+    // When `name` fires next; None while it is paused.
+    pub fn next_fire(&self, name: &str) -> Option<DateTime> {
+        self.jobs
+            .get(name)
+            .filter(|job| !job.paused)
+            .and_then(|job| job.next)
+    }
+
+    // Stops `name` firing until resumed. False if it isn't registered or is
+    // already paused.
+    pub fn pause(&mut self, name: &str) -> bool {
+        let Some(job) = self.jobs.get_mut(name).filter(|job| !job.paused) else {
+            return false;
+        };
+        // A new generation leaves its queue entry dead.
+        self.generation += 1;
+        job.generation = self.generation;
+        job.paused = true;
+        true
+    }
+
+    // Picks `name` back up from `now`. What it would have fired while paused
+    // is skipped and doesn't count towards its repeat limit.
+    pub fn resume(&mut self, name: &str, now: DateTime) -> bool {
+        let Some(job) = self.jobs.get_mut(name).filter(|job| job.paused) else {
+            return false;
+        };
+        job.paused = false;
+        if job.on_demand {
+            return true;
+        }
+        let next = match job.next {
+            Some(at) if at >= now => Some(at),
+            unused => {
+                job.progress.skip_to(now, unused);
+                job.advance()
+            }
+        };
+        match next {
+            Some(at) => self.queue.push((at, job.generation, name.to_string())),
+            None => {
+                self.jobs.remove(name);
+            }
+        }
+        true
+    }
+
+    pub fn is_on_demand(&self, name: &str) -> bool {
+        self.jobs.get(name).is_some_and(|job| job.on_demand)
+    }
+
+    // Lets an on-demand job go once the triggers already queued for it have
+    // gone out, or now if there are none.
+    pub fn release(&mut self, name: &str) {
+        let Some(job) = self.jobs.get_mut(name).filter(|job| job.on_demand) else {
+            return;
+        };
+        job.on_demand = false;
+        if !self.triggered.iter().any(|(n, _)| n == name) {
+            self.jobs.remove(name);
+        }
+    }
+
+    pub fn is_paused(&self, name: &str) -> bool {
+        self.jobs.get(name).is_some_and(|job| job.paused)
+    }
+
+    // Fires `name` has left by its schedule, the queued one included;
+    // out-of-schedule triggers aside.
+    pub fn remaining_occurrences(&self, name: &str) -> Option<Remaining> {
+        let job = self.jobs.get(name)?;
+        // Can always be triggered again.
+        if job.on_demand {
+            return Some(Remaining::Unbounded);
+        }
+        let rest = job.schedule.resume_with(job.plan, job.progress).remaining();
+        Some(match rest {
+            Remaining::Unbounded => Remaining::Unbounded,
+            Remaining::Exactly(n) => Remaining::Exactly(n + job.next.is_some() as u64),
+        })
+    }
+
+    // Registered jobs, paused ones included, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.jobs.keys().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    // When the earliest job fires, i.e. how long a run loop may sleep.
+    pub fn next_wakeup(&mut self) -> Option<DateTime> {
+        let jobs = &self.jobs;
+        let queued = self.queue.earliest(|e| is_live(jobs, e));
+        let triggered = self.triggered.iter().map(|(_, at)| *at).min();
+        queued.into_iter().chain(triggered).min()
+    }
+
+    // Pushes the job's next fire back by `by`, in whole minutes, and returns
+    // when it now is. Occurrences that would have come before it are skipped,
+    // so afterwards the pattern carries on as usual. None if the job isn't
+    // registered or has nothing queued.
+    pub fn defer(&mut self, name: &str, by: Duration) -> Option<DateTime> {
+        let job = self.jobs.get_mut(name)?;
+        let at = job.next?.add_minutes((by.as_secs() / 60) as i64);
+        job.progress.skip_to(at.next_minute(), None);
+        job.next = Some(at);
+        self.generation += 1;
+        job.generation = self.generation;
+        if !job.paused {
+            self.queue.push((at, job.generation, name.to_string()));
+        }
+        Some(at)
+    }
+
+    // Queues an extra run of `name` at `at`, outside its pattern, paused or
+    // not; pop_due hands it out with the rest. With `counted` it uses up one
+    // of the job's repeats, and if it was the last one it replaces the fire
+    // that was queued.
+    pub fn trigger(&mut self, name: &str, at: DateTime, counted: bool) -> bool {
+        let Some(job) = self.jobs.get_mut(name) else {
+            return false;
+        };
+        if counted && !job.progress.use_one() {
+            self.generation += 1;
+            job.generation = self.generation;
+            job.next = None;
+        }
+        let i = self.triggered.partition_point(|(_, t)| *t <= at);
+        self.triggered.insert(i, (name.to_string(), at));
+        true
+    }
+
+    // Everything due at or before `now`, oldest first, with each job moved on
+    // to its following occurrence. Jobs that have run out are unregistered.
+    pub fn pop_due(&mut self, now: DateTime) -> Vec<(String, DateTime)> {
+        self.pop_due_max(now, usize::MAX)
+    }
+
+    // The oldest `max` of pop_due; the rest stay queued, still due.
+    fn pop_due_max(&mut self, now: DateTime, max: usize) -> Vec<(String, DateTime)> {
+        let mut due = Vec::new();
+        while due.len() < max {
+            let jobs = &self.jobs;
+            let Some((at, generation, name)) = self.queue.pop(now, |e| is_live(jobs, e)) else {
+                break;
+            };
+            let Some(job) = self.jobs.get_mut(&name) else {
+                continue;
+            };
+            match job.advance() {
+                Some(next) => self.queue.push((next, generation, name.clone())),
+                None => {
+                    self.jobs.remove(&name);
+                }
+            }
+            self.remember(&name, at);
+            due.push((name, at));
+        }
+        while due.len() < max && self.triggered.front().is_some_and(|(_, at)| *at <= now) {
+            let Some((name, at)) = self.triggered.pop_front() else {
+                break;
+            };
+            let Some(job) = self.jobs.get(&name) else {
+                continue;
+            };
+            // A counted trigger that took the last repeat leaves nothing
+            // queued; the job is done once its triggers have gone out.
+            if job.next.is_none()
+                && !job.on_demand
+                && !self.triggered.iter().any(|(n, _)| *n == name)
+            {
+                self.jobs.remove(&name);
+            }
+            self.remember(&name, at);
+            due.push((name, at));
+        }
+        due.sort_by_key(|(_, at)| *at);
+        if let Some(trace) = &mut self.trace {
+            trace.fires.extend(due.iter().map(|(name, at)| DueJob {
+                name: name.clone(),
+                at: *at,
+            }));
+            trace.until = Some(now);
+        }
+        due
+    }
+
+    fn remember(&mut self, name: &str, at: DateTime) {
+        if self.max_history > 0 {
+            if self.history.len() == self.max_history {
+                self.history.pop_front();
+            }
+            self.history.push_back(DueJob {
+                name: name.to_string(),
+                at,
+            });
+        }
+    }
+
+    // For hosts with their own event loop: call this whenever convenient and
+    // get everything that came due since the previous call. Only due jobs
+    // are touched, so a tick costs O(due) on top of the queue operations.
+    pub fn tick(&mut self, now: DateTime) -> Vec<DueJob> {
+        if self.last_tick.is_some_and(|last| now < last) {
+            eprintln!("Tick at {:?} is before the previous one. Ignoring.", now);
+            return Vec::new();
+        }
+        self.last_tick = Some(now);
+        let due = self.pop_due(now);
+        self.tick_lag = due
+            .iter()
+            .map(|(_, at)| lag(*at, now))
+            .max()
+            .unwrap_or_default();
+        due.into_iter()
+            .map(|(name, at)| DueJob { name, at })
+            .collect()
+    }
+
+    pub fn health(&self, now: DateTime) -> Health {
+        let waiting = self
+            .jobs
+            .values()
+            .filter(|job| !job.paused)
+            .filter_map(|job| job.next)
+            .filter(|at| *at <= now);
+        Health {
+            last_tick: self.last_tick,
+            lag: waiting
+                .clone()
+                .map(|at| lag(at, now))
+                .max()
+                .unwrap_or_default(),
+            last_tick_lag: self.tick_lag,
+            overdue: waiting.count(),
+        }
+    }
+
+    pub fn last_tick(&self) -> Option<DateTime> {
+        self.last_tick
+    }
+
+    // pop_due straight onto a queue shared with worker threads.
+    // With a waiting queue only as many fires as fit are taken; the rest
+    // stay due here and go out on a later call.
+    pub fn dispatch_due(&mut self, now: DateTime, queue: &DispatchQueue) -> usize {
+        let room = match queue.overflow() {
+            Overflow::Wait => queue.room(),
+            Overflow::DropOldest | Overflow::DropNewest => usize::MAX,
+        };
+        let due = self.pop_due_max(now, room);
+        let n = due.len();
+        for (name, at) in due {
+            if let Err((name, at)) = queue.push(name, at) {
+                eprintln!("Dispatch queue full. Dropping {} at {:?}.", name, at);
+            }
+        }
+        n
+    }
+
+    // Most recent fires, oldest first, up to `SchedulerConfig::max_history`.
+    pub fn history(&self) -> impl Iterator<Item = &DueJob> {
+        self.history.iter()
+    }
+}
+
+// Which of `shards` a job name belongs to; stable for the life of the process.
+fn shard_for(name: &str, shards: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    hasher.finish() as usize % shards
+}
+
+fn lag(at: DateTime, now: DateTime) -> Duration {
+    Duration::from_secs((now.unix() - at.unix()).max(0) as u64)
+}
+
+fn is_live(jobs: &HashMap<String, Job>, (_, generation, name): &Entry) -> bool {
+    jobs.get(name)
+        .is_some_and(|job| job.generation == *generation)
+}
+
+impl Queue {
+    fn push(&mut self, e: Entry) {
+        match self {
+            Queue::Heap(heap) => heap.push(Reverse(e)),
+            Queue::Wheel(wheel) => wheel.push(e),
+        }
+    }
+
+    fn earliest(&mut self, live: impl Fn(&Entry) -> bool) -> Option<DateTime> {
+        match self {
+            Queue::Heap(heap) => {
+                drop_dead(heap, live);
+                heap.peek().map(|Reverse((at, _, _))| *at)
+            }
+            Queue::Wheel(wheel) => wheel.earliest(live).map(|(at, _, _)| *at),
+        }
+    }
+
+    fn pop(&mut self, now: DateTime, live: impl Fn(&Entry) -> bool) -> Option<Entry> {
+        match self {
+            Queue::Heap(heap) => {
+                drop_dead(heap, live);
+                let top = heap.peek_mut().filter(|top| top.0.0 <= now)?;
+                Some(PeekMut::pop(top).0)
+            }
+            Queue::Wheel(wheel) => wheel.pop(now.minutes_since_epoch(), live),
+        }
+    }
+}
+
+fn drop_dead(heap: &mut BinaryHeap<Reverse<Entry>>, live: impl Fn(&Entry) -> bool) {
+    while heap.peek().is_some_and(|Reverse(e)| !live(e)) {
+        heap.pop();
+    }
+}
+
+impl Job {
+    fn advance(&mut self) -> Option<DateTime> {
+        let mut occurrences = self.schedule.resume_with(self.plan, self.progress);
+        self.next = occurrences.next();
+        self.progress = occurrences.progress();
+        self.next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    fn each_backend() -> impl Iterator<Item = Scheduler> {
+        [Backend::Heap, Backend::Wheel].into_iter().map(|backend| {
+            Scheduler::with_config(SchedulerConfig {
+                backend,
+                ..SchedulerConfig::default()
+            })
+        })
+    }
+
+    #[test]
+    fn fires_in_time_order_and_reschedules() {
+        for mut s in each_backend() {
+            let now = dt(2025, 3, 1, 8, 30);
+            s.add("hourly", Schedule::new().minute(0).hourly(), now);
+            s.add("nine", Schedule::new().at(9, 0).daily(), now);
+            s.add("soon", Schedule::after(Duration::from_secs(10 * 60)), now);
+
+            assert_eq!(s.next_wakeup(), Some(dt(2025, 3, 1, 8, 40)));
+            let due = s.pop_due(dt(2025, 3, 1, 9, 0));
+            let names: Vec<_> = due.iter().map(|(n, _)| n.as_str()).collect();
+            assert_eq!(names[0], "soon");
+            assert_eq!(due.len(), 3);
+            assert!(due.windows(2).all(|w| w[0].1 <= w[1].1));
+
+            // The one-shot is gone, the others moved on.
+            assert_eq!(s.len(), 2);
+            assert_eq!(s.next_wakeup(), Some(dt(2025, 3, 1, 10, 0)));
+        }
+    }
+
+    #[test]
+    fn repeat_counts_from_registration() {
+        for mut s in each_backend() {
+            s.add(
+                "twice",
+                Schedule::new().daily().repeat(2),
+                dt(2025, 1, 1, 0, 0),
+            );
+            assert_eq!(
+                s.remaining_occurrences("twice"),
+                Some(Remaining::Exactly(2))
+            );
+            assert_eq!(s.pop_due(dt(2025, 1, 1, 0, 0)).len(), 1);
+            assert_eq!(
+                s.remaining_occurrences("twice"),
+                Some(Remaining::Exactly(1))
+            );
+
+            assert_eq!(s.pop_due(dt(2025, 1, 10, 0, 0)).len(), 1);
+            assert!(s.is_empty());
+            assert_eq!(s.next_wakeup(), None);
+        }
+    }
+
+    #[test]
+    fn paused_fires_are_skipped_not_counted() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add("a", Schedule::new().at(9, 0).daily().repeat(2), now);
+            assert!(s.pause("a"));
+            assert!(s.is_paused("a"));
+            assert_eq!(s.next_fire("a"), None);
+            assert!(s.pop_due(dt(2025, 1, 3, 0, 0)).is_empty());
+
+            assert!(s.resume("a", dt(2025, 1, 3, 0, 0)));
+            assert!(!s.resume("a", dt(2025, 1, 3, 0, 0)));
+            let due = s.pop_due(dt(2025, 1, 10, 0, 0));
+            let at: Vec<_> = due.into_iter().map(|(_, at)| at).collect();
+            assert_eq!(at, [dt(2025, 1, 3, 9, 0), dt(2025, 1, 4, 9, 0)]);
+            assert!(s.is_empty());
+        }
+    }
+
+    #[test]
+    fn health_reports_lag() {
+        let mut s = Scheduler::new();
+        let now = dt(2025, 1, 1, 0, 0);
+        s.add("a", Schedule::new().minute(0).hourly(), now);
+        s.add("b", Schedule::new().at(9, 0).daily(), now);
+        let health = s.health(now);
+        assert_eq!(health.last_tick, None);
+        assert_eq!(health.overdue, 1);
+        assert!(health.is_healthy(Duration::ZERO));
+
+        // Nobody ticked through the morning.
+        let health = s.health(dt(2025, 1, 1, 9, 30));
+        assert_eq!(health.overdue, 2);
+        assert_eq!(health.lag, Duration::from_secs(9 * 3600 + 30 * 60));
+        assert!(!health.is_healthy(Duration::from_secs(60)));
+
+        s.tick(dt(2025, 1, 1, 9, 30));
+        let health = s.health(dt(2025, 1, 1, 9, 30));
+        assert_eq!(health.overdue, 0);
+        assert_eq!(health.lag, Duration::ZERO);
+        // The 00:00 fire went out nine and a half hours late.
+        assert_eq!(
+            health.last_tick_lag,
+            Duration::from_secs(9 * 3600 + 30 * 60)
+        );
+    }
+
+    #[test]
+    fn rescheduling_replaces_the_queued_fire() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add("a", Schedule::new().at(9, 0).daily(), now);
+            s.tick(dt(2025, 1, 1, 9, 0));
+            let old = s.reschedule(
+                "a",
+                Schedule::new().minute(0).hourly(),
+                dt(2025, 1, 1, 9, 0),
+            );
+            assert_eq!(old, Some(Schedule::new().at(9, 0).daily()));
+
+            // 09:00 went out under the old schedule and isn't repeated.
+            assert_eq!(s.next_wakeup(), Some(dt(2025, 1, 1, 10, 0)));
+            assert_eq!(s.pop_due(dt(2025, 1, 1, 10, 0)).len(), 1);
+            assert_eq!(s.reschedule("b", Schedule::new().daily(), now), None);
+        }
+    }
+
+    #[test]
+    fn triggered_runs_go_out_with_the_next_pop() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add("a", Schedule::new().at(9, 0).daily().repeat(2), now);
+            assert!(s.trigger("a", dt(2025, 1, 1, 1, 0), false));
+            assert!(!s.trigger("b", now, false));
+            assert_eq!(s.next_wakeup(), Some(dt(2025, 1, 1, 1, 0)));
+            assert_eq!(
+                s.pop_due(dt(2025, 1, 1, 1, 0)),
+                [("a".to_string(), dt(2025, 1, 1, 1, 0))]
+            );
+
+            // Uncounted, both repeats are still there; counted, the second
+            // trigger stands in for the last of them.
+            assert!(s.trigger("a", dt(2025, 1, 1, 2, 0), true));
+            assert!(s.trigger("a", dt(2025, 1, 1, 2, 0), true));
+            let due = s.pop_due(dt(2025, 1, 2, 12, 0));
+            let at: Vec<_> = due.into_iter().map(|(_, at)| at).collect();
+            assert_eq!(at, [dt(2025, 1, 1, 2, 0), dt(2025, 1, 1, 2, 0)]);
+            assert!(s.is_empty());
+        }
+    }
+
+    #[test]
+    fn on_demand_jobs_wait_for_triggers() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add_on_demand("a", now);
+            assert_eq!(s.next_wakeup(), None);
+            // Later first: they still go out in time order.
+            assert!(s.trigger("a", dt(2025, 1, 1, 2, 0), false));
+            assert!(s.trigger("a", dt(2025, 1, 1, 1, 0), false));
+            assert_eq!(
+                s.pop_due(dt(2025, 1, 1, 1, 30)),
+                [("a".to_string(), dt(2025, 1, 1, 1, 0))]
+            );
+            assert_eq!(s.pop_due(dt(2025, 1, 1, 3, 0)).len(), 1);
+            assert_eq!(s.next_fire("a"), None);
+            assert!(!s.is_empty());
+            assert!(s.pause("a") && s.resume("a", dt(2025, 1, 1, 3, 0)));
+            assert_eq!(s.next_wakeup(), None);
+        }
+    }
+
+    #[test]
+    fn deferring_moves_only_the_next_fire() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add("a", Schedule::new().minute(0).hourly(), now);
+            assert_eq!(
+                s.defer("a", Duration::from_secs(90 * 60)),
+                Some(dt(2025, 1, 1, 1, 30))
+            );
+            let due = s.pop_due(dt(2025, 1, 1, 3, 0));
+            let at: Vec<_> = due.into_iter().map(|(_, at)| at).collect();
+            assert_eq!(
+                at,
+                [
+                    dt(2025, 1, 1, 1, 30),
+                    dt(2025, 1, 1, 2, 0),
+                    dt(2025, 1, 1, 3, 0)
+                ]
+            );
+            assert_eq!(s.defer("b", Duration::from_secs(60)), None);
+        }
+    }
+
+    #[test]
+    fn catch_up_keeps_the_recent_fires() {
+        let now = dt(2025, 1, 1, 12, 0);
+        let due: Vec<_> = (0..=12)
+            .flat_map(|h| {
+                let at = dt(2025, 1, 1, h, 0);
+                [("hourly", at), ("other", at)]
+            })
+            .filter(|(name, at)| *name == "hourly" || at.hour % 6 == 0)
+            .map(|(name, at)| DueJob {
+                name: name.to_string(),
+                at,
+            })
+            .collect();
+        let policy = CatchUp {
+            max_fires: Some(2),
+            max_age: Some(Duration::from_secs(8 * 3600)),
+        };
+        let (run, skip) = policy.split(due, now);
+        let run: Vec<_> = run.iter().map(|j| (j.name.as_str(), j.at.hour)).collect();
+        assert_eq!(
+            run,
+            [("other", 6), ("hourly", 11), ("hourly", 12), ("other", 12)]
+        );
+        assert_eq!(skip.len(), 12);
+    }
+
+    #[test]
+    fn removed_jobs_never_fire() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add("a", Schedule::new().at(1, 0).daily(), now);
+            s.add("b", Schedule::new().at(2, 0).daily(), now);
+
+            assert!(s.remove("a").is_some());
+            assert_eq!(s.next_wakeup(), Some(dt(2025, 1, 1, 2, 0)));
+
+            // Re-adding under the same name does not revive the stale entry.
+            s.add("a", Schedule::new().at(3, 0).daily(), now);
+            let due = s.pop_due(dt(2025, 1, 1, 3, 0));
+            assert_eq!(
+                due,
+                vec![
+                    ("b".to_string(), dt(2025, 1, 1, 2, 0)),
+                    ("a".to_string(), dt(2025, 1, 1, 3, 0))
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn wheel_cascades_far_ahead() {
+        let mut s = Scheduler::with_config(SchedulerConfig {
+            backend: Backend::Wheel,
+            ..SchedulerConfig::default()
+        });
+        let now = dt(2025, 1, 1, 0, 0);
+        s.add("decades", Schedule::new().year(2070).date(6, 1), now);
+        s.add("yearly", Schedule::new().date_with_time(3, 1, 12, 0), now);
+        s.add("thrice", Schedule::new().hourly().repeat(3), now);
+
+        assert_eq!(s.pop_due(dt(2025, 1, 1, 2, 0)).len(), 3);
+        assert_eq!(s.next_wakeup(), Some(dt(2025, 3, 1, 12, 0)));
+        assert_eq!(
+            s.pop_due(dt(2069, 1, 1, 0, 0)),
+            vec![("yearly".to_string(), dt(2025, 3, 1, 12, 0))]
+        );
+        assert_eq!(
+            s.pop_due(dt(2071, 1, 1, 0, 0)),
+            vec![("decades".to_string(), dt(2070, 6, 1, 0, 0))]
+        );
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn tick_reports_everything_since_the_last_tick() {
+        for mut s in each_backend() {
+            let now = dt(2025, 1, 1, 0, 0);
+            s.add("quarter", Schedule::new().minute(15).hourly(), now);
+            s.add("nine", Schedule::new().at(9, 0).daily(), now);
+
+            assert_eq!(s.tick(dt(2025, 1, 1, 0, 10)), vec![]);
+            // A host that was busy for a while catches up in one call.
+            let due = s.tick(dt(2025, 1, 1, 9, 0));
+            assert_eq!(due.len(), 10);
+            assert_eq!(
+                due.last(),
+                Some(&DueJob {
+                    name: "nine".to_string(),
+                    at: dt(2025, 1, 1, 9, 0)
+                })
+            );
+            assert_eq!(s.tick(dt(2025, 1, 1, 8, 0)), vec![]);
+            assert_eq!(s.last_tick(), Some(dt(2025, 1, 1, 9, 0)));
+        }
+    }
+
+    #[test]
+    fn stalled_workers_hold_fires_back() {
+        let mut s = Scheduler::with_config(SchedulerConfig {
+            max_history: 3,
+            ..SchedulerConfig::default()
+        });
+        let queue = DispatchQueue::bounded(2, 4, Overflow::Wait);
+        s.add("hourly", Schedule::new().hourly(), dt(2025, 1, 1, 0, 0));
+
+        // Ten hours due, but only four fit until a worker takes some.
+        assert_eq!(s.dispatch_due(dt(2025, 1, 1, 9, 0), &queue), 4);
+        assert_eq!(s.dispatch_due(dt(2025, 1, 1, 9, 0), &queue), 0);
+        assert!(queue.pop(0).is_some());
+        assert_eq!(s.dispatch_due(dt(2025, 1, 1, 9, 0), &queue), 1);
+        assert_eq!(s.next_wakeup(), Some(dt(2025, 1, 1, 5, 0)));
+
+        let kept: Vec<_> = s.history().map(|d| d.at.hour).collect();
+        assert_eq!(kept, vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,148 @@
+// OpenTelemetry export for services already on an OTLP pipeline. Install
+// tracer and meter providers as usual (opentelemetry_sdk plus an exporter),
+// then add the job spans to the tracing subscriber:
+//
+//     global::set_tracer_provider(tracer_provider);
+//     global::set_meter_provider(meter_provider);
+//     tracing_subscriber::registry().with(brahma::scheduler::otel_layer()).init();
+//
+// Every job run is then exported as a `job` span (see runtime::execute), and
+// these instruments are recorded on the "brahma" meter, with a `job`
+// attribute where it applies:
+//
+//   brahma.job.runs.started       counter
+//   brahma.job.runs.succeeded     counter
+//   brahma.job.runs.failed        counter, panics included
+//   brahma.job.run.duration       histogram, seconds
+//   brahma.schedule.lag           histogram, seconds from due to start
+//   brahma.queue.depth            gauge, fires due but not started
+//   brahma.jobs                   gauge, registered jobs
+//
+// Instruments are looked up on each use, so providers installed after the
+// runtime starts are still picked up.
+use opentelemetry::KeyValue;
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::metrics::Meter;
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+use super::runtime::Outcome;
+
+// Sends tracing spans, brahma's and any others, to the global tracer provider.
+pub fn otel_layer<S>() -> OpenTelemetryLayer<S, BoxedTracer>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(global::tracer("brahma"))
+}
+
+fn meter() -> Meter {
+    global::meter("brahma")
+}
+
+pub(crate) fn run_started(job: &str, lag: f64, queued: usize) {
+    let meter = meter();
+    let attrs = [KeyValue::new("job", job.to_string())];
+    meter
+        .u64_gauge("brahma.queue.depth")
+        .build()
+        .record(queued as u64, &[]);
+    meter
+        .u64_counter("brahma.job.runs.started")
+        .build()
+        .add(1, &attrs);
+    meter
+        .f64_histogram("brahma.schedule.lag")
+        .with_unit("s")
+        .build()
+        .record(lag, &attrs);
+}
+
+pub(crate) fn run_finished(job: &str, outcome: &Outcome, took: f64) {
+    let meter = meter();
+    let attrs = [KeyValue::new("job", job.to_string())];
+    let name = if outcome.is_success() {
+        "brahma.job.runs.succeeded"
+    } else {
+        "brahma.job.runs.failed"
+    };
+    meter.u64_counter(name).build().add(1, &attrs);
+    meter
+        .f64_histogram("brahma.job.run.duration")
+        .with_unit("s")
+        .build()
+        .record(took, &attrs);
+}
+
+pub(crate) fn batch_done(jobs: usize) {
+    let meter = meter();
+    meter.u64_gauge("brahma.queue.depth").build().record(0, &[]);
+    meter
+        .u64_gauge("brahma.jobs")
+        .build()
+        .record(jobs as u64, &[]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::{ManualClock, Runtime};
+    use crate::time::DateTime;
+    use crate::types::Schedule;
+    use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+    use tracing_subscriber::Registry;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn runs_are_exported() {
+        let spans = InMemorySpanExporter::default();
+        global::set_tracer_provider(
+            SdkTracerProvider::builder()
+                .with_simple_exporter(spans.clone())
+                .build(),
+        );
+        let metrics = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(metrics.clone()).build())
+            .build();
+        global::set_meter_provider(meter_provider.clone());
+
+        let subscriber = Registry::default().with(otel_layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let clock = ManualClock::new(DateTime::new(2025, 1, 1, 0, 0).unwrap());
+            let mut rt = Runtime::new(clock.clone());
+            rt.add("report", Schedule::new().at(1, 0).daily(), |_| {})
+                .unwrap();
+            clock.advance(60);
+            rt.run_pending();
+        });
+
+        let spans = spans.get_finished_spans().unwrap();
+        let job = spans.iter().find(|span| span.name == "job").unwrap();
+        assert!(
+            job.attributes
+                .iter()
+                .any(|kv| kv.key.as_str() == "job" && kv.value.as_str() == "report")
+        );
+
+        meter_provider.force_flush().unwrap();
+        let names: Vec<String> = metrics
+            .get_finished_metrics()
+            .unwrap()
+            .iter()
+            .flat_map(|rm| rm.scope_metrics())
+            .flat_map(|sm| sm.metrics())
+            .map(|m| m.name().to_string())
+            .collect();
+        for name in [
+            "brahma.job.runs.started",
+            "brahma.job.runs.succeeded",
+            "brahma.job.run.duration",
+            "brahma.jobs",
+        ] {
+            assert!(names.iter().any(|n| n == name), "{} in {:?}", name, names);
+        }
+    }
+}
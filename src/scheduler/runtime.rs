@@ -0,0 +1,1713 @@
+// Runs code on a schedule: a Scheduler for the timing, a task per job, and a
+// Clock saying what time it is. Tasks run on the thread driving the runtime,
+// either from the host's own loop through `run_pending` or from `run`, which
+// blocks and sleeps between fires.
+use std::borrow::Borrow;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::ops::Deref;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::audit::{AuditEntry, AuditLog, Change};
+use super::cancel::CancellationToken;
+use super::events::{Event, Subscribers};
+use super::leader::LeadershipProvider;
+use super::metrics::{self, RunProbe};
+use super::supervisor::{Escalation, RestartPolicy, Supervision};
+use super::{CatchUp, DispatchQueue, Health, Scheduler, SchedulerConfig, Trace};
+use crate::describe::{Locale, describe_follower, describe_in};
+use crate::occurrence::Remaining;
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+pub trait Clock: Send {
+    fn now(&self) -> DateTime;
+
+    // Wait until `at`, or less; callers check the time again afterwards.
+    fn sleep_until(&self, at: DateTime);
+}
+
+// Wall-clock UTC.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime {
+        DateTime::from_unix(unix_now().as_secs() as i64)
+    }
+
+    // At most a second at a time, so `run` notices a stop soon enough. The
+    // browser can't block; use scheduler::run_web there instead.
+    fn sleep_until(&self, at: DateTime) {
+        let at = Duration::from_secs(at.unix().max(0) as u64);
+        let wait = at.saturating_sub(unix_now());
+        std::thread::sleep(wait.min(Duration::from_secs(1)));
+    }
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+pub(crate) fn unix_now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+// SystemTime::now panics in the browser.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub(crate) fn unix_now() -> Duration {
+    Duration::from_millis(js_sys::Date::now() as u64)
+}
+
+// Time that only moves when told to, for tests and simulations. Clones share
+// the same time; sleeping jumps straight to the wakeup.
+#[derive(Debug, Clone)]
+pub struct ManualClock(Arc<Mutex<DateTime>>);
+
+impl ManualClock {
+    pub fn new(now: DateTime) -> Self {
+        Self(Arc::new(Mutex::new(now)))
+    }
+
+    pub fn set(&self, now: DateTime) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = now;
+    }
+
+    pub fn advance(&self, minutes: i64) {
+        self.set(self.now().add_minutes(minutes));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn sleep_until(&self, at: DateTime) {
+        if at > self.now() {
+            self.set(at);
+        }
+    }
+}
+
+// A job's name, unique within its Runtime: what spans, metrics, events and
+// the management calls know it by. Derefs to str, so it goes wherever a name
+// is asked for.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(String);
+
+impl JobId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for JobId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for JobId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for JobId {
+    fn from(name: String) -> Self {
+        JobId(name)
+    }
+}
+
+impl From<&str> for JobId {
+    fn from(name: &str) -> Self {
+        JobId(name.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobError {
+    // The name is taken by a job that is still registered.
+    AlreadyExists(JobId),
+    NotFound(JobId),
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobError::AlreadyExists(id) => write!(f, "job {} already exists", id),
+            JobError::NotFound(id) => write!(f, "no job {}", id),
+        }
+    }
+}
+
+impl std::error::Error for JobError {}
+
+// What a task is told about the fire it is running for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobContext {
+    pub name: String,
+    // When the fire was due, which may be before the clock's now.
+    pub at: DateTime,
+    // Worth checking now and then in a long run; see cancel.rs.
+    pub cancel: CancellationToken,
+}
+
+// How a run ended. Panics are caught so one bad job can't take the loop
+// down with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Succeeded,
+    Failed(String),
+    Panicked(String),
+}
+
+impl Outcome {
+    pub fn is_success(&self) -> bool {
+        *self == Outcome::Succeeded
+    }
+}
+
+// What a task may return: nothing, or a Result whose error becomes Failed.
+pub trait IntoOutcome {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl IntoOutcome for () {
+    fn into_outcome(self) -> Outcome {
+        Outcome::Succeeded
+    }
+}
+
+impl<E: fmt::Display> IntoOutcome for Result<(), E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(()) => Outcome::Succeeded,
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+}
+
+type Task = Box<dyn FnMut(&JobContext) -> Outcome + Send>;
+
+struct Registered {
+    task: Task,
+    // The fire it last ran for and how that went.
+    last: Option<(DateTime, Outcome)>,
+    stats: JobStats,
+    tags: BTreeSet<String>,
+    // As the Scheduler has it, kept for describing the job once completed.
+    schedule: Schedule,
+    // Out of occurrences and kept for inspection; see keep_completed.
+    completed: bool,
+    // A child of the runtime's; cancelled when the job is removed.
+    token: CancellationToken,
+    supervision: Option<Supervision>,
+    // The job it follows and how long after, for one added with add_after.
+    after: Option<(String, Duration)>,
+}
+
+impl Registered {
+    fn describe(&self, locale: Locale) -> String {
+        match &self.after {
+            Some((leader, delay)) => describe_follower(leader, *delay, locale),
+            None => describe_in(&self.schedule, locale),
+        }
+    }
+}
+
+// Running totals for one job since it was added.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct JobStats {
+    pub runs: u64,
+    // Panics included.
+    pub failures: u64,
+    pub total_duration: Duration,
+    pub max_duration: Duration,
+    // How the latest runs went, counting back to the last change.
+    pub streak: Option<Streak>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Streak {
+    Succeeded(u64),
+    Failed(u64),
+}
+
+impl JobStats {
+    pub fn average_duration(&self) -> Duration {
+        match self.runs {
+            0 => Duration::ZERO,
+            n => self.total_duration / n.min(u32::MAX as u64) as u32,
+        }
+    }
+
+    fn record(&mut self, outcome: &Outcome, took: Duration) {
+        self.runs += 1;
+        self.total_duration += took;
+        self.max_duration = self.max_duration.max(took);
+        self.streak = Some(match (self.streak, outcome.is_success()) {
+            (Some(Streak::Succeeded(n)), true) => Streak::Succeeded(n + 1),
+            (_, true) => Streak::Succeeded(1),
+            (Some(Streak::Failed(n)), false) => Streak::Failed(n + 1),
+            (_, false) => Streak::Failed(1),
+        });
+        if !outcome.is_success() {
+            self.failures += 1;
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum JobState {
+    Scheduled,
+    Paused,
+    // Ran out of occurrences; only listed with SchedulerConfig::keep_completed.
+    Completed,
+}
+
+// A job as `Runtime::jobs` saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobInfo {
+    pub name: String,
+    pub description: String,
+    // None while paused.
+    pub next: Option<DateTime>,
+    pub last_run: Option<DateTime>,
+    pub last_outcome: Option<Outcome>,
+    pub state: JobState,
+    // Sorted.
+    pub tags: Vec<String>,
+}
+
+pub struct Runtime {
+    scheduler: Scheduler,
+    jobs: HashMap<String, Registered>,
+    clock: Box<dyn Clock>,
+    events: Subscribers,
+    audit: AuditLog,
+    keep_completed: bool,
+    catch_up: CatchUp,
+    token: CancellationToken,
+    // With whether it led at the last ask; see leader.rs.
+    leadership: Option<(Box<dyn LeadershipProvider>, bool)>,
+}
+
+impl Runtime {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self::with_config(clock, SchedulerConfig::default())
+    }
+
+    pub fn with_config(clock: impl Clock + 'static, config: SchedulerConfig) -> Self {
+        Self {
+            scheduler: Scheduler::with_config(config),
+            audit: AuditLog::new(config.max_audit),
+            keep_completed: config.keep_completed,
+            catch_up: config.catch_up,
+            token: CancellationToken::new(),
+            jobs: HashMap::new(),
+            clock: Box::new(clock),
+            events: Subscribers::default(),
+            leadership: None,
+        }
+    }
+
+    // Registered as of the clock's now. Names stay taken until the job is
+    // removed, or until it runs out unless completed jobs are kept.
+    pub fn add<R: IntoOutcome>(
+        &mut self,
+        id: impl Into<JobId>,
+        schedule: Schedule,
+        mut task: impl FnMut(&JobContext) -> R + Send + 'static,
+    ) -> Result<JobId, JobError> {
+        let id = id.into();
+        if self.jobs.contains_key(id.as_str()) {
+            return Err(JobError::AlreadyExists(id));
+        }
+        let name = id.to_string();
+        let now = self.clock.now();
+        self.audit
+            .record(now, &name, Change::Added(schedule.clone()));
+        self.scheduler.add(name.clone(), schedule, now);
+        self.events.emit(Event::JobScheduled {
+            name: name.clone(),
+            next: self.scheduler.next_fire(&name),
+        });
+        let schedule = self.scheduler.get(&name).cloned().unwrap_or_default();
+        self.jobs.insert(
+            name,
+            Registered {
+                task: Box::new(move |ctx| task(ctx).into_outcome()),
+                last: None,
+                stats: JobStats::default(),
+                tags: BTreeSet::new(),
+                schedule,
+                completed: false,
+                token: self.token.child(),
+                supervision: None,
+                after: None,
+            },
+        );
+        Ok(id)
+    }
+
+    // Registers a job with no calendar of its own that runs `delay` after
+    // each success of the job named `after`, in whole minutes from when that
+    // run finished. Failed runs and the follower's own runs don't start it.
+    // Only run_pending sees outcomes, so dispatch_due never starts it either;
+    // while paused, successes in the meantime are let go. It completes once
+    // `after` is removed or runs out. `reschedule` gives it a calendar
+    // schedule in place of following.
+    pub fn add_after<R: IntoOutcome>(
+        &mut self,
+        id: impl Into<JobId>,
+        after: &str,
+        delay: Duration,
+        mut task: impl FnMut(&JobContext) -> R + Send + 'static,
+    ) -> Result<JobId, JobError> {
+        let id = id.into();
+        if self.jobs.contains_key(id.as_str()) {
+            return Err(JobError::AlreadyExists(id));
+        }
+        if !self.jobs.contains_key(after) {
+            return Err(JobError::NotFound(after.into()));
+        }
+        let name = id.to_string();
+        let now = self.clock.now();
+        self.audit.record(
+            now,
+            &name,
+            Change::AddedAfter {
+                after: after.to_string(),
+                delay,
+            },
+        );
+        self.scheduler.add_on_demand(name.clone(), now);
+        self.events.emit(Event::JobScheduled {
+            name: name.clone(),
+            next: None,
+        });
+        self.jobs.insert(
+            name,
+            Registered {
+                task: Box::new(move |ctx| task(ctx).into_outcome()),
+                last: None,
+                stats: JobStats::default(),
+                tags: BTreeSet::new(),
+                schedule: Schedule::new(),
+                completed: false,
+                token: self.token.child(),
+                supervision: None,
+                after: Some((after.to_string(), delay)),
+            },
+        );
+        Ok(id)
+    }
+
+    // A new channel receiving every event from now on.
+    pub fn subscribe(&mut self) -> Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Schedule> {
+        let entry = self.jobs.remove(name);
+        if let Some(job) = &entry {
+            job.token.cancel();
+        }
+        let removed = self
+            .scheduler
+            .remove(name)
+            .or_else(|| entry.filter(|job| job.completed).map(|job| job.schedule));
+        if removed.is_some() {
+            self.audit.record(self.clock.now(), name, Change::Removed);
+            self.retire_followers(name);
+        }
+        removed
+    }
+
+    // A handle for managing one job, or None if there is no such job.
+    pub fn job(&mut self, name: &str) -> Option<JobHandle<'_>> {
+        let id = JobId::from(name);
+        self.jobs
+            .contains_key(name)
+            .then_some(JobHandle { runtime: self, id })
+    }
+
+    // Swaps the job's schedule in place, as of the clock's now; its task,
+    // stats, tags and paused state carry over. Returns the old schedule.
+    pub fn reschedule(&mut self, name: &str, schedule: Schedule) -> Result<Schedule, JobError> {
+        let now = self.clock.now();
+        let old = self
+            .scheduler
+            .reschedule(name, schedule, now)
+            .ok_or_else(|| JobError::NotFound(name.into()))?;
+        if let (Some(job), Some(schedule)) = (self.jobs.get_mut(name), self.scheduler.get(name)) {
+            job.schedule = schedule.clone();
+            job.after = None;
+        }
+        self.audit
+            .record(now, name, Change::Rescheduled(old.clone()));
+        self.events.emit(Event::JobScheduled {
+            name: name.to_string(),
+            next: self.scheduler.next_fire(name),
+        });
+        Ok(old)
+    }
+
+    // See Scheduler::defer.
+    pub fn defer(&mut self, name: &str, by: Duration) -> Option<DateTime> {
+        let to = self.scheduler.defer(name, by)?;
+        let from = to.add_minutes(-((by.as_secs() / 60) as i64));
+        self.audit
+            .record(self.clock.now(), name, Change::Deferred { from, to });
+        Some(to)
+    }
+
+    // Runs the job on the next run_pending, whatever its schedule says; see
+    // Scheduler::trigger for `counted`.
+    pub fn trigger_now(&mut self, name: &str, counted: bool) -> bool {
+        let now = self.clock.now();
+        let triggered = self.scheduler.trigger(name, now, counted);
+        if triggered {
+            self.audit.record(now, name, Change::Triggered { counted });
+        }
+        triggered
+    }
+
+    // See Scheduler::pause and Scheduler::resume; resuming is as of the
+    // clock's now.
+    pub fn pause(&mut self, name: &str) -> bool {
+        let paused = self.scheduler.pause(name);
+        if paused {
+            self.audit.record(self.clock.now(), name, Change::Paused);
+        }
+        paused
+    }
+
+    pub fn resume(&mut self, name: &str) -> bool {
+        let now = self.clock.now();
+        let resumed = self.scheduler.resume(name, now);
+        if resumed {
+            self.audit.record(now, name, Change::Resumed);
+            self.retire_if_done(name);
+        }
+        resumed
+    }
+
+    // Tags group jobs for the *_tagged calls, e.g. "reporting" or
+    // "tenant:acme". False if there is no such job.
+    pub fn tag(&mut self, name: &str, tag: impl Into<String>) -> bool {
+        match self.jobs.get_mut(name) {
+            Some(job) => {
+                job.tags.insert(tag.into());
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn untag(&mut self, name: &str, tag: &str) -> bool {
+        self.jobs
+            .get_mut(name)
+            .is_some_and(|job| job.tags.remove(tag))
+    }
+
+    // Jobs carrying `tag`, sorted by name.
+    pub fn tagged(&self, tag: &str) -> Vec<JobId> {
+        let mut ids: Vec<JobId> = self
+            .jobs
+            .iter()
+            .filter(|(_, job)| job.tags.contains(tag))
+            .map(|(name, _)| JobId::from(name.as_str()))
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    // The bulk versions of pause, resume and remove, returning how many jobs
+    // they changed.
+    pub fn pause_tagged(&mut self, tag: &str) -> usize {
+        let ids = self.tagged(tag);
+        ids.iter().filter(|id| self.pause(id)).count()
+    }
+
+    pub fn resume_tagged(&mut self, tag: &str) -> usize {
+        let ids = self.tagged(tag);
+        ids.iter().filter(|id| self.resume(id)).count()
+    }
+
+    pub fn remove_tagged(&mut self, tag: &str) -> usize {
+        let ids = self.tagged(tag);
+        ids.iter().filter(|id| self.remove(id).is_some()).count()
+    }
+
+    // Labels the changes that follow in the audit log, e.g. with the user or
+    // deploy making them; None for unlabelled.
+    pub fn set_actor(&mut self, actor: Option<&str>) {
+        self.audit.actor = actor.map(str::to_string);
+    }
+
+    // Only runs tasks while `provider` says this instance leads; see
+    // leader.rs. Replaces any provider already set, and starts out following
+    // until the provider is first asked.
+    pub fn set_leadership(&mut self, provider: impl LeadershipProvider + 'static) {
+        self.leadership = Some((Box::new(provider), false));
+    }
+
+    // As of the last ask; always true without a provider.
+    pub fn is_leader(&self) -> bool {
+        self.leadership.as_ref().is_none_or(|(_, leading)| *leading)
+    }
+
+    // Asks the provider, telling subscribers when the answer changed.
+    fn leads(&mut self, now: DateTime) -> bool {
+        let Some((provider, leading)) = &mut self.leadership else {
+            return true;
+        };
+        let now_leading = provider.is_leader(now);
+        if now_leading != *leading {
+            *leading = now_leading;
+            self.events.emit(if now_leading {
+                Event::LeadershipAcquired { at: now }
+            } else {
+                Event::LeadershipLost { at: now }
+            });
+        }
+        now_leading
+    }
+
+    // What a follower does with its due fires: moves past them without
+    // running any.
+    fn follow(&mut self, now: DateTime) {
+        let due = self.scheduler.tick(now);
+        for job in &due {
+            self.events.emit(Event::JobSkipped {
+                name: job.name.clone(),
+                at: job.at,
+                reason: "not the leader".to_string(),
+            });
+        }
+        for job in &due {
+            self.retire_if_done(&job.name);
+        }
+    }
+
+    // Most recent changes, oldest first, up to `SchedulerConfig::max_audit`.
+    pub fn audit_log(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.audit.entries()
+    }
+
+    // Escalates when the job keeps failing; see supervisor.rs. Replaces any
+    // policy it had, and None drops it.
+    pub fn supervise(&mut self, name: &str, policy: Option<RestartPolicy>) -> bool {
+        match self.jobs.get_mut(name) {
+            Some(job) => {
+                job.supervision = policy.map(Supervision::new);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn stats(&self, name: &str) -> Option<JobStats> {
+        self.jobs.get(name).map(|job| job.stats)
+    }
+
+    // None for unknown jobs; completed ones that are kept have none left.
+    pub fn remaining_occurrences(&self, name: &str) -> Option<Remaining> {
+        match self.scheduler.remaining_occurrences(name) {
+            Some(rest) => Some(rest),
+            None => self
+                .jobs
+                .contains_key(name)
+                .then_some(Remaining::Exactly(0)),
+        }
+    }
+
+    // A snapshot of every registered job, sorted by name.
+    pub fn jobs(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self
+            .jobs
+            .iter()
+            .map(|(name, job)| JobInfo {
+                name: name.clone(),
+                description: job.describe(Locale::En),
+                next: self.scheduler.next_fire(name),
+                last_run: job.last.as_ref().map(|(at, _)| *at),
+                last_outcome: job.last.as_ref().map(|(_, outcome)| outcome.clone()),
+                state: if job.completed {
+                    JobState::Completed
+                } else if self.scheduler.is_paused(name) {
+                    JobState::Paused
+                } else {
+                    JobState::Scheduled
+                },
+                tags: job.tags.iter().cloned().collect(),
+            })
+            .collect();
+        jobs.sort_by(|a, b| a.name.cmp(&b.name));
+        jobs
+    }
+
+    // What `jobs` gives as the job's description, in `locale`.
+    #[cfg(feature = "i18n")]
+    pub fn describe_job(&self, name: &str, locale: Locale) -> Option<String> {
+        self.jobs.get(name).map(|job| job.describe(locale))
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    // See Scheduler::record_trace; fires skipped by the catch-up limits are
+    // in the trace too.
+    pub fn record_trace(&mut self) {
+        self.scheduler.record_trace();
+    }
+
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.scheduler.take_trace()
+    }
+
+    pub fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+
+    pub fn health(&self) -> Health {
+        self.scheduler.health(self.clock.now())
+    }
+
+    pub fn next_wakeup(&mut self) -> Option<DateTime> {
+        self.scheduler.next_wakeup()
+    }
+
+    // Runs everything due by the clock's now, oldest first, and returns how
+    // many tasks ran. Jobs with no occurrences left are dropped afterwards.
+    pub fn run_pending(&mut self) -> usize {
+        let now = self.clock.now();
+        if !self.leads(now) {
+            self.follow(now);
+            return 0;
+        }
+        let (due, missed) = self.catch_up.split(self.scheduler.tick(now), now);
+        for job in &missed {
+            self.events.emit(Event::JobSkipped {
+                name: job.name.clone(),
+                at: job.at,
+                reason: "missed, outside the catch-up limit".to_string(),
+            });
+        }
+        // Paused by their supervisor during this batch.
+        let mut halted = BTreeSet::new();
+        for (i, job) in due.iter().enumerate() {
+            if halted.contains(&job.name) {
+                self.events.emit(Event::JobSkipped {
+                    name: job.name.clone(),
+                    at: job.at,
+                    reason: "paused by its supervisor".to_string(),
+                });
+            } else if let Some(entry) = self.jobs.get_mut(&job.name) {
+                let ctx = JobContext {
+                    name: job.name.clone(),
+                    at: job.at,
+                    cancel: entry.token.clone(),
+                };
+                self.events.emit(Event::JobStarted {
+                    name: job.name.clone(),
+                    at: job.at,
+                });
+                let probe = RunProbe::start(&job.name, job.at, now, due.len() - i);
+                let started = unix_now();
+                let outcome = execute(&mut entry.task, &ctx);
+                probe.finish(&outcome);
+                entry
+                    .stats
+                    .record(&outcome, unix_now().saturating_sub(started));
+                entry.last = Some((job.at, outcome.clone()));
+                let escalation = match &mut entry.supervision {
+                    Some(supervision) if !outcome.is_success() => supervision.failed(now),
+                    _ => None,
+                };
+                let succeeded = outcome.is_success();
+                self.events.emit(match outcome {
+                    Outcome::Succeeded => Event::JobFinished {
+                        name: ctx.name,
+                        at: ctx.at,
+                    },
+                    Outcome::Failed(error) | Outcome::Panicked(error) => Event::JobFailed {
+                        name: ctx.name,
+                        at: ctx.at,
+                        error,
+                    },
+                });
+                if let Some(escalation) = escalation
+                    && self.escalate(&job.name, escalation)
+                {
+                    halted.insert(job.name.clone());
+                }
+                if succeeded {
+                    self.start_followers(&job.name);
+                }
+            } else {
+                self.events.emit(Event::JobSkipped {
+                    name: job.name.clone(),
+                    at: job.at,
+                    reason: "no task registered".to_string(),
+                });
+            }
+        }
+        // Only once the batch is through: a job can run out with more than
+        // one of its fires in it.
+        for job in due.iter().chain(&missed) {
+            self.retire_if_done(&job.name);
+        }
+        metrics::batch_done(self.scheduler.len());
+        due.len()
+    }
+
+    // Queues the jobs added with add_after to follow `name`, as of the
+    // clock's now.
+    fn start_followers(&mut self, name: &str) {
+        let now = self.clock.now();
+        let followers: Vec<(String, DateTime)> = self
+            .jobs
+            .iter()
+            .filter_map(|(follower, job)| {
+                let (after, delay) = job.after.as_ref()?;
+                (after == name
+                    && self.scheduler.is_on_demand(follower)
+                    && !self.scheduler.is_paused(follower))
+                .then(|| {
+                    (
+                        follower.clone(),
+                        now.add_minutes((delay.as_secs() / 60) as i64),
+                    )
+                })
+            })
+            .collect();
+        for (follower, at) in followers {
+            self.scheduler.trigger(&follower, at, false);
+            self.events.emit(Event::JobScheduled {
+                name: follower,
+                next: Some(at),
+            });
+        }
+    }
+
+    // True if the job was paused.
+    fn escalate(&mut self, name: &str, escalation: Escalation) -> bool {
+        let paused = match escalation {
+            Escalation::Pause => self.pause(name),
+            Escalation::Backoff(by) => {
+                self.defer(name, by);
+                false
+            }
+            Escalation::Notify => false,
+        };
+        self.events.emit(Event::JobEscalated {
+            name: name.to_string(),
+            escalation,
+        });
+        paused
+    }
+
+    // For running tasks on worker threads instead: hands what is due by the
+    // clock's now to `queue` (see Scheduler::dispatch_due) and returns how
+    // many went, emitting Saturated when the queue ends up full.
+    pub fn dispatch_due(&mut self, queue: &DispatchQueue) -> usize {
+        let now = self.clock.now();
+        if !self.leads(now) {
+            self.follow(now);
+            return 0;
+        }
+        let dropped = queue.dropped();
+        let sent = self.scheduler.dispatch_due(now, queue);
+        if queue.room() == 0 {
+            self.events.emit(Event::Saturated {
+                capacity: queue.capacity(),
+                dropped: queue.dropped() - dropped,
+                held: self.scheduler.health(now).overdue,
+            });
+        }
+        sent
+    }
+
+    // Drops, or with keep_completed marks, a job the Scheduler has let go of
+    // for running out.
+    fn retire_if_done(&mut self, name: &str) {
+        if self.scheduler.get(name).is_some() {
+            return;
+        }
+        let Some(job) = self.jobs.get_mut(name).filter(|job| !job.completed) else {
+            return;
+        };
+        if self.keep_completed {
+            job.completed = true;
+        } else {
+            self.jobs.remove(name);
+        }
+        self.events.emit(Event::JobCompleted {
+            name: name.to_string(),
+        });
+        self.retire_followers(name);
+    }
+
+    // Jobs added with add_after to follow `leader` have nothing left to
+    // wait for once it is gone: they complete with it, after any run its
+    // last success queued, and theirs with them. A job added later under the
+    // same name doesn't pick them up.
+    fn retire_followers(&mut self, leader: &str) {
+        let followers: Vec<String> = self
+            .jobs
+            .iter()
+            .filter(|(name, job)| {
+                job.after.as_ref().is_some_and(|(after, _)| after == leader)
+                    && self.scheduler.is_on_demand(name)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for follower in followers {
+            self.scheduler.release(&follower);
+            self.retire_if_done(&follower);
+        }
+    }
+
+    // Cancelled by shutdown, and cancelling it from elsewhere stops `run`
+    // and tells every task that asks. Clones share the state.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    // Runs tasks as they come due until `stop` is set, the runtime's token is
+    // cancelled or nothing is left.
+    pub fn run(&mut self, stop: &AtomicBool) {
+        while !stop.load(Ordering::Acquire) && !self.token.is_cancelled() {
+            self.run_pending();
+            match self.next_wakeup() {
+                Some(at) => self.clock.sleep_until(at),
+                None => break,
+            }
+        }
+        self.shutdown();
+    }
+
+    // Cancels the runtime's token and tells subscribers it is done; `run`
+    // does so on its way out.
+    pub fn shutdown(&mut self) {
+        self.token.cancel();
+        self.events.emit(Event::SchedulerShutdown);
+    }
+}
+
+// Runs `f`, turning a panic into Panicked with its message.
+pub(crate) fn catch_panic(f: impl FnOnce() -> Outcome) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(outcome) => outcome,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Outcome::Panicked(message)
+        }
+    }
+}
+
+// One run of a task, inside a `job` span when tracing is on.
+fn execute(task: &mut Task, ctx: &JobContext) -> Outcome {
+    #[cfg(feature = "tracing")]
+    let span = tracing::info_span!(
+        "job",
+        job = %ctx.name,
+        scheduled = %ctx.at,
+        // Runs aren't retried, so every run is a first attempt.
+        attempt = 1u32,
+        outcome = tracing::field::Empty,
+    )
+    .entered();
+
+    let outcome = catch_panic(|| task(ctx));
+
+    #[cfg(feature = "tracing")]
+    {
+        match &outcome {
+            Outcome::Succeeded => span.record("outcome", "succeeded"),
+            Outcome::Failed(e) => {
+                tracing::warn!(error = %e, "job failed");
+                span.record("outcome", "failed")
+            }
+            Outcome::Panicked(e) => {
+                tracing::error!(panic = %e, "job panicked");
+                span.record("outcome", "panicked")
+            }
+        };
+    }
+    outcome
+}
+
+// One job of a Runtime, borrowed from `Runtime::job`. The job can run out or
+// be removed while the handle lives, after which its calls report so.
+pub struct JobHandle<'a> {
+    runtime: &'a mut Runtime,
+    id: JobId,
+}
+
+impl JobHandle<'_> {
+    pub fn id(&self) -> &JobId {
+        &self.id
+    }
+
+    pub fn schedule(&self) -> Option<&Schedule> {
+        self.runtime.scheduler.get(&self.id)
+    }
+
+    pub fn stats(&self) -> Option<JobStats> {
+        self.runtime.stats(&self.id)
+    }
+
+    // The token this job's runs are handed, to cancel them from elsewhere.
+    pub fn cancellation_token(&self) -> Option<CancellationToken> {
+        self.runtime
+            .jobs
+            .get(self.id.as_str())
+            .map(|job| job.token.clone())
+    }
+
+    pub fn reschedule(&mut self, schedule: Schedule) -> Result<Schedule, JobError> {
+        self.runtime.reschedule(&self.id, schedule)
+    }
+
+    pub fn trigger_now(&mut self, counted: bool) -> bool {
+        self.runtime.trigger_now(&self.id, counted)
+    }
+
+    pub fn defer(&mut self, by: Duration) -> Option<DateTime> {
+        self.runtime.defer(&self.id, by)
+    }
+
+    pub fn pause(&mut self) -> bool {
+        self.runtime.pause(&self.id)
+    }
+
+    pub fn resume(&mut self) -> bool {
+        self.runtime.resume(&self.id)
+    }
+
+    pub fn tag(&mut self, tag: impl Into<String>) -> bool {
+        self.runtime.tag(&self.id, tag)
+    }
+
+    pub fn supervise(&mut self, policy: Option<RestartPolicy>) -> bool {
+        self.runtime.supervise(&self.id, policy)
+    }
+
+    pub fn remove(self) -> Option<Schedule> {
+        self.runtime.remove(&self.id)
+    }
+}
+
+impl fmt::Debug for JobHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobHandle").field("id", &self.id).finish()
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Runtime")
+            .field("scheduler", &self.scheduler)
+            .field("now", &self.clock.now())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::Overflow;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn tasks_run_when_due() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let fired = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&fired);
+        rt.add("report", Schedule::new().minute(30).hourly(), move |ctx| {
+            log.lock().unwrap().push(ctx.at)
+        })
+        .unwrap();
+
+        assert_eq!(rt.run_pending(), 0);
+        clock.advance(150);
+        assert_eq!(rt.run_pending(), 3);
+        assert_eq!(
+            *fired.lock().unwrap(),
+            [
+                dt(2025, 1, 1, 0, 30),
+                dt(2025, 1, 1, 1, 30),
+                dt(2025, 1, 1, 2, 30)
+            ]
+        );
+    }
+
+    #[test]
+    fn failures_and_panics_are_outcomes() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        rt.add("flaky", Schedule::new().minute(0).hourly(), |_| {
+            Err::<(), _>("disk full")
+        })
+        .unwrap();
+        rt.add("broken", Schedule::new().minute(0).hourly(), |ctx| {
+            assert!(ctx.name.is_empty(), "bug")
+        })
+        .unwrap();
+        let ctx = |name: &str| JobContext {
+            name: name.to_string(),
+            at: dt(2025, 1, 1, 1, 0),
+            cancel: CancellationToken::new(),
+        };
+        let flaky = &mut rt.jobs.get_mut("flaky").unwrap().task;
+        assert_eq!(
+            execute(flaky, &ctx("flaky")),
+            Outcome::Failed("disk full".to_string())
+        );
+        let broken = &mut rt.jobs.get_mut("broken").unwrap().task;
+        assert_eq!(
+            execute(broken, &ctx("broken")),
+            Outcome::Panicked("bug".to_string())
+        );
+
+        clock.advance(120);
+        assert_eq!(rt.run_pending(), 6);
+        assert_eq!(rt.scheduler().len(), 2);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn runs_are_wrapped_in_spans() {
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::{Layer, Registry};
+
+        #[derive(Clone, Default)]
+        struct Fields(Arc<Mutex<Vec<(String, String)>>>);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                let value = format!("{:?}", value).trim_matches('"').to_string();
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((field.name().to_string(), value));
+            }
+        }
+
+        impl<S: tracing::Subscriber> Layer<S> for Fields {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+                attrs.record(&mut self.clone());
+            }
+
+            fn on_record(&self, _: &Id, values: &Record<'_>, _: Context<'_, S>) {
+                values.record(&mut self.clone());
+            }
+        }
+
+        let fields = Fields::default();
+        let subscriber = Registry::default().with(fields.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+            let mut rt = Runtime::new(clock.clone());
+            rt.add("report", Schedule::new().at(1, 0).daily(), |_| {})
+                .unwrap();
+            clock.advance(60);
+            rt.run_pending();
+        });
+
+        let fields = fields.0.lock().unwrap();
+        let get = |name: &str| {
+            fields
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("job"), Some("report"));
+        assert_eq!(get("scheduled"), Some("2025-01-01 01:00"));
+        assert_eq!(get("attempt"), Some("1"));
+        assert_eq!(get("outcome"), Some("succeeded"));
+    }
+
+    #[test]
+    fn events_reach_every_subscriber() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let (first, second) = (rt.subscribe(), rt.subscribe());
+        rt.add("once", Schedule::after(Duration::from_secs(600)), |_| {
+            Err::<(), _>("boom")
+        })
+        .unwrap();
+        drop(second);
+        rt.run(&AtomicBool::new(false));
+
+        let at = dt(2025, 1, 1, 0, 10);
+        let name = "once".to_string();
+        assert_eq!(
+            first.try_iter().collect::<Vec<_>>(),
+            [
+                Event::JobScheduled {
+                    name: name.clone(),
+                    next: Some(at)
+                },
+                Event::JobStarted {
+                    name: name.clone(),
+                    at
+                },
+                Event::JobFailed {
+                    name: name.clone(),
+                    at,
+                    error: "boom".to_string()
+                },
+                Event::JobCompleted { name },
+                Event::SchedulerShutdown,
+            ]
+        );
+        assert_eq!(rt.events.0.len(), 1);
+    }
+
+    #[test]
+    fn run_stops_when_nothing_is_left() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let runs = Arc::new(Mutex::new(0));
+        let count = Arc::clone(&runs);
+        rt.add(
+            "twice",
+            Schedule::new().at(9, 0).daily().repeat(2),
+            move |_| *count.lock().unwrap() += 1,
+        )
+        .unwrap();
+        rt.run(&AtomicBool::new(false));
+
+        assert_eq!(*runs.lock().unwrap(), 2);
+        assert_eq!(clock.now(), dt(2025, 1, 2, 9, 0));
+        assert!(rt.scheduler().is_empty());
+        assert!(rt.jobs.is_empty());
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let daily = Schedule::new().at(9, 0).daily();
+        let id = rt.add("report", daily.clone(), |_| {}).unwrap();
+        assert_eq!(id.as_str(), "report");
+        assert_eq!(
+            rt.add("report", daily.clone(), |_| {}),
+            Err(JobError::AlreadyExists(id.clone()))
+        );
+
+        // Free again once removed; the id works wherever a name does.
+        rt.remove(&id);
+        assert!(rt.add(id, daily, |_| {}).is_ok());
+    }
+
+    #[test]
+    fn tagged_jobs_move_together() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let daily = Schedule::new().at(9, 0).daily();
+        for name in ["acme-report", "acme-sync", "globex-report"] {
+            rt.add(name, daily.clone(), |_| {}).unwrap();
+            rt.tag(name, format!("tenant:{}", &name[..name.find('-').unwrap()]));
+        }
+        rt.tag("acme-report", "reporting");
+        rt.tag("globex-report", "reporting");
+        assert!(!rt.tag("missing", "reporting"));
+
+        assert_eq!(rt.pause_tagged("reporting"), 2);
+        assert_eq!(
+            rt.scheduler().next_fire("acme-sync"),
+            Some(dt(2025, 1, 1, 9, 0))
+        );
+        assert_eq!(rt.resume_tagged("reporting"), 2);
+        assert_eq!(rt.jobs()[0].tags, ["reporting", "tenant:acme"]);
+
+        assert_eq!(rt.remove_tagged("tenant:acme"), 2);
+        assert_eq!(rt.tagged("reporting"), [JobId::from("globex-report")]);
+        assert!(rt.untag("globex-report", "reporting"));
+        assert!(rt.tagged("reporting").is_empty());
+    }
+
+    #[test]
+    fn reschedule_keeps_the_job() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&runs);
+        rt.add("report", Schedule::new().at(9, 0).daily(), move |ctx| {
+            log.lock().unwrap().push(ctx.at)
+        })
+        .unwrap();
+        clock.advance(9 * 60);
+        rt.run_pending();
+
+        let mut job = rt.job("report").unwrap();
+        job.tag("reporting");
+        let old = job.reschedule(Schedule::new().minute(0).hourly()).unwrap();
+        assert_eq!(old, Schedule::new().at(9, 0).daily());
+        assert_eq!(job.stats().unwrap().runs, 1);
+
+        clock.advance(60);
+        rt.run_pending();
+        assert_eq!(
+            *runs.lock().unwrap(),
+            [dt(2025, 1, 1, 9, 0), dt(2025, 1, 1, 10, 0)]
+        );
+        assert_eq!(rt.tagged("reporting").len(), 1);
+        assert_eq!(
+            rt.reschedule("missing", Schedule::new().daily()),
+            Err(JobError::NotFound("missing".into()))
+        );
+        assert!(rt.job("missing").is_none());
+    }
+
+    #[test]
+    fn completed_jobs_can_be_kept() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let config = SchedulerConfig {
+            keep_completed: true,
+            ..SchedulerConfig::default()
+        };
+        let mut rt = Runtime::with_config(clock.clone(), config);
+        let runs = Arc::new(Mutex::new(0));
+        let count = Arc::clone(&runs);
+        let twice = Schedule::new().minute(0).hourly().repeat(2);
+        rt.add("twice", twice, move |_| *count.lock().unwrap() += 1)
+            .unwrap();
+        let events = rt.subscribe();
+
+        // Both fires come due in the same batch.
+        clock.advance(3 * 60);
+        assert_eq!(rt.run_pending(), 2);
+        assert_eq!(*runs.lock().unwrap(), 2);
+        assert!(rt.scheduler().is_empty());
+        let info = &rt.jobs()[0];
+        assert_eq!(info.state, JobState::Completed);
+        assert_eq!(info.next, None);
+        assert_eq!(info.last_run, Some(dt(2025, 1, 1, 1, 0)));
+        assert!(events.try_iter().any(|e| e
+            == Event::JobCompleted {
+                name: "twice".to_string()
+            }));
+
+        assert!(rt.add("twice", Schedule::new().daily(), |_| {}).is_err());
+        assert!(rt.remove("twice").is_some());
+        assert!(rt.jobs().is_empty());
+    }
+
+    #[test]
+    fn trigger_now_runs_out_of_schedule() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&runs);
+        rt.add("report", Schedule::new().at(9, 0).daily(), move |ctx| {
+            log.lock().unwrap().push(ctx.at)
+        })
+        .unwrap();
+        clock.advance(30);
+        rt.pause("report");
+        assert!(rt.job("report").unwrap().trigger_now(false));
+        assert_eq!(rt.next_wakeup(), Some(dt(2025, 1, 1, 0, 30)));
+
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(*runs.lock().unwrap(), [dt(2025, 1, 1, 0, 30)]);
+        assert_eq!(rt.jobs()[0].state, JobState::Paused);
+        assert!(!rt.trigger_now("missing", false));
+    }
+
+    #[test]
+    fn follow_up_runs_after_each_success() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        // Fails on its second run.
+        let mut n = 0;
+        rt.add("extract", Schedule::new().minute(0).hourly(), move |_| {
+            n += 1;
+            if n == 2 { Err("source down") } else { Ok(()) }
+        })
+        .unwrap();
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&runs);
+        rt.add_after(
+            "load",
+            "extract",
+            Duration::from_secs(30 * 60),
+            move |ctx| log.lock().unwrap().push(ctx.at),
+        )
+        .unwrap();
+        assert_eq!(
+            rt.add_after("report", "missing", Duration::ZERO, |_| ()),
+            Err(JobError::NotFound("missing".into()))
+        );
+        assert_eq!(rt.next_wakeup(), Some(dt(2025, 1, 1, 0, 0)));
+
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(rt.next_wakeup(), Some(dt(2025, 1, 1, 0, 30)));
+        clock.set(dt(2025, 1, 1, 1, 0));
+        // The 00:30 follow-up, then the 01:00 run that fails.
+        assert_eq!(rt.run_pending(), 2);
+        clock.set(dt(2025, 1, 1, 2, 0));
+        assert_eq!(rt.run_pending(), 1);
+        clock.set(dt(2025, 1, 1, 2, 30));
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(
+            *runs.lock().unwrap(),
+            [dt(2025, 1, 1, 0, 30), dt(2025, 1, 1, 2, 30)]
+        );
+
+        let load = rt.jobs().remove(1);
+        assert_eq!(load.description, "30 minutes after extract succeeds");
+        assert_eq!(load.state, JobState::Scheduled);
+        assert_eq!(rt.remaining_occurrences("load"), Some(Remaining::Unbounded));
+        #[cfg(feature = "i18n")]
+        assert_eq!(
+            rt.describe_job("load", Locale::De).unwrap(),
+            "30 Minuten nachdem extract erfolgreich war"
+        );
+
+        // Paused followers let successes go by.
+        rt.pause("load");
+        clock.set(dt(2025, 1, 1, 3, 0));
+        rt.run_pending();
+        assert_eq!(rt.next_wakeup(), Some(dt(2025, 1, 1, 4, 0)));
+    }
+
+    #[test]
+    fn followers_go_with_their_leader() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        rt.add("extract", Schedule::new().minute(0).hourly(), |_| ())
+            .unwrap();
+        let runs = Arc::new(Mutex::new(0));
+        let count = Arc::clone(&runs);
+        rt.add_after("load", "extract", Duration::ZERO, move |_| {
+            *count.lock().unwrap() += 1
+        })
+        .unwrap();
+        let events = rt.subscribe();
+
+        rt.remove("extract");
+        assert!(rt.job("load").is_none());
+        assert_eq!(
+            events.try_iter().last(),
+            Some(Event::JobCompleted {
+                name: "load".to_string()
+            })
+        );
+        // A new job under the old name doesn't bring the follower back.
+        rt.add("extract", Schedule::new().minute(0).hourly(), |_| ())
+            .unwrap();
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(rt.run_pending(), 0);
+        assert_eq!(*runs.lock().unwrap(), 0);
+
+        // One that ran out still gets the run its last success queued, then
+        // goes, and a follower's followers after it.
+        rt.add("once", Schedule::new().at(1, 0).daily().repeat(1), |_| ())
+            .unwrap();
+        rt.add_after("then", "once", Duration::ZERO, |_| ())
+            .unwrap();
+        rt.add_after("last", "then", Duration::ZERO, |_| ())
+            .unwrap();
+        clock.set(dt(2025, 1, 1, 1, 0));
+        assert_eq!(rt.run_pending(), 2);
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(rt.run_pending(), 0);
+        let names: Vec<String> = rt.jobs().into_iter().map(|job| job.name).collect();
+        assert_eq!(names, ["extract"]);
+    }
+
+    #[test]
+    fn deferred_backup_runs_later_once() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let config = SchedulerConfig {
+            max_audit: 10,
+            ..SchedulerConfig::default()
+        };
+        let mut rt = Runtime::with_config(clock.clone(), config);
+        rt.add("backup", Schedule::new().at(22, 0).daily(), |_| {})
+            .unwrap();
+        let mut backup = rt.job("backup").unwrap();
+        assert_eq!(
+            backup.defer(Duration::from_secs(2 * 3600)),
+            Some(dt(2025, 1, 2, 0, 0))
+        );
+        assert_eq!(
+            rt.audit_log().last().unwrap().change,
+            Change::Deferred {
+                from: dt(2025, 1, 1, 22, 0),
+                to: dt(2025, 1, 2, 0, 0)
+            }
+        );
+
+        clock.advance(24 * 60);
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(
+            rt.scheduler().next_fire("backup"),
+            Some(dt(2025, 1, 2, 22, 0))
+        );
+    }
+
+    #[test]
+    fn tasks_see_cancellation() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let root = rt.cancellation_token();
+        let steps = Arc::new(Mutex::new(0));
+        let count = Arc::clone(&steps);
+        // Cancelled from "another thread" halfway through its second run.
+        rt.add("long", Schedule::new().minute(0).hourly(), move |ctx| {
+            for _ in 0..10 {
+                if ctx.cancel.is_cancelled() {
+                    return Err("cancelled");
+                }
+                let mut n = count.lock().unwrap();
+                *n += 1;
+                if *n == 15 {
+                    root.cancel();
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+        let job_token = rt.job("long").unwrap().cancellation_token().unwrap();
+
+        rt.run(&AtomicBool::new(false));
+        assert_eq!(*steps.lock().unwrap(), 15);
+        assert_eq!(rt.stats("long").unwrap().failures, 1);
+        assert!(job_token.is_cancelled());
+    }
+
+    #[test]
+    fn crash_loops_are_paused() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        rt.add("flaky", Schedule::new().minute(0).hourly(), |_| {
+            Err::<(), _>("down")
+        })
+        .unwrap();
+        rt.job("flaky").unwrap().supervise(Some(RestartPolicy {
+            max_failures: 3,
+            window: Duration::from_secs(6 * 3600),
+            escalation: Escalation::Pause,
+        }));
+        let events = rt.subscribe();
+
+        // Six fires due at once; the third failure pauses it.
+        clock.advance(5 * 60);
+        rt.run_pending();
+        assert_eq!(rt.stats("flaky").unwrap().runs, 3);
+        assert_eq!(rt.jobs()[0].state, JobState::Paused);
+        let events: Vec<_> = events.try_iter().collect();
+        assert!(events.contains(&Event::JobEscalated {
+            name: "flaky".to_string(),
+            escalation: Escalation::Pause
+        }));
+        let skipped = events
+            .iter()
+            .filter(|e| matches!(e, Event::JobSkipped { .. }))
+            .count();
+        assert_eq!(skipped, 3);
+    }
+
+    #[test]
+    fn full_dispatch_queues_are_reported() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        for name in ["a", "b", "c"] {
+            rt.add(name, Schedule::new().minute(0).hourly(), |_| {})
+                .unwrap();
+        }
+        let events = rt.subscribe();
+        let queue = DispatchQueue::bounded(1, 2, Overflow::Wait);
+
+        assert_eq!(rt.dispatch_due(&queue), 2);
+        assert_eq!(
+            events.try_recv(),
+            Ok(Event::Saturated {
+                capacity: 2,
+                dropped: 0,
+                held: 1
+            })
+        );
+        queue.pop(0);
+        queue.pop(0);
+        assert_eq!(rt.dispatch_due(&queue), 1);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn catch_up_after_downtime_is_bounded() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let config = SchedulerConfig {
+            catch_up: CatchUp {
+                max_fires: Some(1),
+                max_age: None,
+            },
+            ..SchedulerConfig::default()
+        };
+        let mut rt = Runtime::with_config(clock.clone(), config);
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&runs);
+        rt.add("sync", Schedule::new().minute(0).hourly(), move |ctx| {
+            log.lock().unwrap().push(ctx.at)
+        })
+        .unwrap();
+        let events = rt.subscribe();
+
+        // Down all morning: only the latest fire runs.
+        clock.advance(6 * 60 + 10);
+        assert_eq!(rt.run_pending(), 1);
+        assert_eq!(*runs.lock().unwrap(), [dt(2025, 1, 1, 6, 0)]);
+        let skipped = events
+            .try_iter()
+            .filter(|e| matches!(e, Event::JobSkipped { .. }))
+            .count();
+        assert_eq!(skipped, 6);
+    }
+
+    #[test]
+    fn jobs_snapshot() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        rt.add("report", Schedule::new().at(1, 0).daily(), |_| {
+            Err::<(), _>("no data")
+        })
+        .unwrap();
+        rt.add("backup", Schedule::new().at(3, 0).daily(), |_| {})
+            .unwrap();
+        clock.advance(120);
+        rt.run_pending();
+        assert!(rt.pause("backup"));
+        assert!(!rt.pause("backup"));
+
+        assert_eq!(
+            rt.jobs(),
+            [
+                JobInfo {
+                    name: "backup".to_string(),
+                    description: "every day at 03:00".to_string(),
+                    next: None,
+                    last_run: None,
+                    last_outcome: None,
+                    state: JobState::Paused,
+                    tags: Vec::new(),
+                },
+                JobInfo {
+                    name: "report".to_string(),
+                    description: "every day at 01:00".to_string(),
+                    next: Some(dt(2025, 1, 2, 1, 0)),
+                    last_run: Some(dt(2025, 1, 1, 1, 0)),
+                    last_outcome: Some(Outcome::Failed("no data".to_string())),
+                    state: JobState::Scheduled,
+                    tags: Vec::new(),
+                },
+            ]
+        );
+
+        // Paused through the 03:00 fire, which is skipped rather than run late.
+        clock.advance(24 * 60);
+        assert_eq!(rt.run_pending(), 1);
+        assert!(rt.resume("backup"));
+        assert_eq!(rt.run_pending(), 0);
+        assert_eq!(rt.jobs()[0].next, Some(dt(2025, 1, 2, 3, 0)));
+        assert_eq!(rt.jobs()[0].state, JobState::Scheduled);
+    }
+
+    #[test]
+    fn stats_add_up() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let mut rt = Runtime::new(clock.clone());
+        let runs = Arc::new(Mutex::new(0));
+        let count = Arc::clone(&runs);
+        rt.add("sync", Schedule::new().minute(0).hourly(), move |_| {
+            let mut n = count.lock().unwrap();
+            *n += 1;
+            if *n == 2 {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            if *n <= 2 { Ok(()) } else { Err("timeout") }
+        })
+        .unwrap();
+        assert_eq!(rt.stats("sync"), Some(JobStats::default()));
+
+        clock.advance(5 * 60);
+        // 00:00 through 05:00.
+        assert_eq!(rt.run_pending(), 6);
+        let stats = rt.stats("sync").unwrap();
+        assert_eq!(stats.runs, 6);
+        assert_eq!(stats.failures, 4);
+        assert_eq!(stats.streak, Some(Streak::Failed(4)));
+        assert!(stats.max_duration >= Duration::from_millis(20));
+        assert!(stats.average_duration() <= stats.max_duration);
+        assert_eq!(rt.stats("missing"), None);
+    }
+
+    #[test]
+    fn changes_are_audited() {
+        let clock = ManualClock::new(dt(2025, 1, 1, 0, 0));
+        let config = SchedulerConfig {
+            max_audit: 3,
+            ..SchedulerConfig::default()
+        };
+        let mut rt = Runtime::with_config(clock.clone(), config);
+        let daily = Schedule::new().at(9, 0).daily();
+        rt.add("report", daily.clone(), |_| {}).unwrap();
+        rt.set_actor(Some("alice"));
+        clock.advance(5);
+        rt.pause("report");
+        rt.pause("report");
+        rt.remove("missing");
+        rt.set_actor(None);
+        rt.resume("report");
+        rt.remove("report");
+
+        let entry = |minute, actor: Option<&str>, change| AuditEntry {
+            at: dt(2025, 1, 1, 0, minute),
+            actor: actor.map(str::to_string),
+            name: "report".to_string(),
+            change,
+        };
+        // The add fell off the end.
+        assert_eq!(
+            rt.audit_log().cloned().collect::<Vec<_>>(),
+            [
+                entry(5, Some("alice"), Change::Paused),
+                entry(5, None, Change::Resumed),
+                entry(5, None, Change::Removed),
+            ]
+        );
+    }
+}
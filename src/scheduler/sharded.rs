@@ -0,0 +1,145 @@
+// Jobs partitioned by name across several independent Schedulers, each
+// meant to be driven by its own timer loop (and core) through `pop_due_on`,
+// with add/remove/lookup working across all of them.
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use super::{Scheduler, SchedulerConfig, shard_for};
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+#[derive(Debug)]
+pub struct ShardedScheduler {
+    shards: Vec<Mutex<Scheduler>>,
+}
+
+impl ShardedScheduler {
+    // At least one shard, each with its own queue built from `config`.
+    pub fn new(shards: usize, config: SchedulerConfig) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(Scheduler::with_config(config)))
+                .collect(),
+        }
+    }
+
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    // Which shard's loop fires `name`.
+    pub fn shard_of(&self, name: &str) -> usize {
+        shard_for(name, self.shards.len())
+    }
+
+    pub fn add(&self, name: impl Into<String>, schedule: Schedule, now: DateTime) {
+        let name = name.into();
+        self.lock(self.shard_of(&name)).add(name, schedule, now);
+    }
+
+    pub fn remove(&self, name: &str) -> Option<Schedule> {
+        self.lock(self.shard_of(name)).remove(name)
+    }
+
+    pub fn reschedule(&self, name: &str, schedule: Schedule, now: DateTime) -> Option<Schedule> {
+        self.lock(self.shard_of(name))
+            .reschedule(name, schedule, now)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Schedule> {
+        self.lock(self.shard_of(name)).get(name).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        (0..self.shards.len()).map(|i| self.lock(i).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // When one shard's loop should wake next.
+    pub fn next_wakeup_on(&self, shard: usize) -> Option<DateTime> {
+        self.lock(shard).next_wakeup()
+    }
+
+    // What one shard's loop has to fire; only that shard is locked.
+    pub fn pop_due_on(&self, shard: usize, now: DateTime) -> Vec<(String, DateTime)> {
+        self.lock(shard).pop_due(now)
+    }
+
+    // The earliest wakeup across all shards, for a single driving loop.
+    pub fn next_wakeup(&self) -> Option<DateTime> {
+        (0..self.shards.len())
+            .filter_map(|i| self.next_wakeup_on(i))
+            .min()
+    }
+
+    // Everything due on every shard, oldest first.
+    pub fn pop_due(&self, now: DateTime) -> Vec<(String, DateTime)> {
+        let mut due: Vec<_> = (0..self.shards.len())
+            .flat_map(|i| self.pop_due_on(i, now))
+            .collect();
+        due.sort_by_key(|(_, at)| *at);
+        due
+    }
+
+    fn lock(&self, shard: usize) -> MutexGuard<'_, Scheduler> {
+        self.shards[shard % self.shards.len()]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn jobs_are_spread_and_managed_as_one() {
+        let s = ShardedScheduler::new(4, SchedulerConfig::default());
+        let now = dt(2025, 1, 1, 0, 0);
+        for i in 0..40 {
+            s.add(format!("job-{}", i), Schedule::new().at(1, 0).daily(), now);
+        }
+
+        assert_eq!(s.len(), 40);
+        assert!((0..4).all(|i| s.next_wakeup_on(i).is_some()));
+        assert!(s.remove("job-7").is_some());
+        assert_eq!(s.get("job-7"), None);
+        assert_eq!(s.next_wakeup(), Some(dt(2025, 1, 1, 1, 0)));
+        assert_eq!(s.pop_due(dt(2025, 1, 1, 1, 0)).len(), 39);
+    }
+
+    #[test]
+    fn each_loop_drives_its_own_shard() {
+        let s = Arc::new(ShardedScheduler::new(3, SchedulerConfig::default()));
+        let now = dt(2025, 1, 1, 0, 0);
+        for i in 0..30 {
+            s.add(
+                format!("job-{}", i),
+                Schedule::new().hourly().repeat(2),
+                now,
+            );
+        }
+        let loops: Vec<_> = (0..s.shards())
+            .map(|shard| {
+                let s = Arc::clone(&s);
+                thread::spawn(move || {
+                    let fired = s.pop_due_on(shard, dt(2025, 1, 1, 5, 0));
+                    assert!(fired.iter().all(|(name, _)| s.shard_of(name) == shard));
+                    fired.len()
+                })
+            })
+            .collect();
+        let fired: usize = loops.into_iter().map(|l| l.join().unwrap()).sum();
+
+        assert_eq!(fired, 60);
+        assert!(s.is_empty());
+    }
+}
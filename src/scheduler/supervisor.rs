@@ -0,0 +1,75 @@
+// Keeps a crash-looping job from hammering whatever it talks to: once it has
+// failed `max_failures` times within `window` (by the runtime's clock), the
+// runtime escalates as the policy says and starts counting afresh. Every
+// escalation is also sent to event subscribers as JobEscalated, which is
+// where a handler that pages someone would listen.
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::time::DateTime;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RestartPolicy {
+    pub max_failures: u32,
+    pub window: Duration,
+    pub escalation: Escalation,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Escalation {
+    // Pause the job until someone resumes it. Fires of it already due in the
+    // same batch are skipped.
+    Pause,
+    // Push its next fire back by this much, leaving the pattern as it is.
+    Backoff(Duration),
+    // Only send the JobEscalated event.
+    Notify,
+}
+
+#[derive(Debug)]
+pub(crate) struct Supervision {
+    pub(crate) policy: RestartPolicy,
+    failures: VecDeque<DateTime>,
+}
+
+impl Supervision {
+    pub(crate) fn new(policy: RestartPolicy) -> Self {
+        Self {
+            policy,
+            failures: VecDeque::new(),
+        }
+    }
+
+    // Counts a failure at `now`; Some when the policy's limit is reached.
+    pub(crate) fn failed(&mut self, now: DateTime) -> Option<Escalation> {
+        let since = now.add_minutes(-((self.policy.window.as_secs() / 60) as i64));
+        self.failures.retain(|at| *at > since);
+        self.failures.push_back(now);
+        if self.failures.len() < self.policy.max_failures.max(1) as usize {
+            return None;
+        }
+        self.failures.clear();
+        Some(self.policy.escalation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_outside_the_window_are_forgotten() {
+        let mut s = Supervision::new(RestartPolicy {
+            max_failures: 3,
+            window: Duration::from_secs(3600),
+            escalation: Escalation::Pause,
+        });
+        let at = |minute| DateTime::new(2025, 1, 1, 0, 0).unwrap().add_minutes(minute);
+        assert_eq!(s.failed(at(0)), None);
+        assert_eq!(s.failed(at(30)), None);
+        // The first has aged out by now.
+        assert_eq!(s.failed(at(60)), None);
+        assert_eq!(s.failed(at(70)), Some(Escalation::Pause));
+        assert_eq!(s.failed(at(71)), None);
+    }
+}
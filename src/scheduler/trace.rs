@@ -0,0 +1,213 @@
+// Execution traces: every fire a Scheduler hands out while recording, kept
+// so a multi-job setup's behaviour can be checked in and compared against
+// later. A trace is plain text, one fire per line, and with the `serde`
+// feature serializes as a struct too:
+//
+//     until 2025-01-03 00:00
+//     2025-01-01 09:00 report
+//     2025-01-02 09:00 report
+//
+// `Scheduler::replay` ticks a freshly set up scheduler through the same
+// times and reports the first fire that differs.
+use std::fmt;
+use std::str::FromStr;
+
+use super::{DueJob, Scheduler};
+use crate::parse::ParseError;
+use crate::time::DateTime;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    // Oldest first; fires due together in the order they were handed out.
+    pub fires: Vec<DueJob>,
+    // The last tick recorded. Fires after the last one up to here were
+    // looked for and did not happen.
+    pub until: Option<DateTime>,
+}
+
+// Where a replay parted from its trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    // Position in the trace's fires.
+    pub index: usize,
+    // None past the end of either.
+    pub expected: Option<DueJob>,
+    pub actual: Option<DueJob>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fire = |job: &Option<DueJob>| match job {
+            Some(job) => format!("{} at {}", job.name, job.at),
+            None => "nothing".to_string(),
+        };
+        write!(
+            f,
+            "fire {}: expected {}, got {}",
+            self.index + 1,
+            fire(&self.expected),
+            fire(&self.actual)
+        )
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+impl Trace {
+    // The first fire where `actual` differs from this trace.
+    pub fn compare(&self, actual: &Trace) -> Result<(), Divergence> {
+        let len = self.fires.len().max(actual.fires.len());
+        match (0..len).find(|&i| self.fires.get(i) != actual.fires.get(i)) {
+            Some(index) => Err(Divergence {
+                index,
+                expected: self.fires.get(index).cloned(),
+                actual: actual.fires.get(index).cloned(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(until) = self.until {
+            writeln!(f, "until {}", until)?;
+        }
+        for job in &self.fires {
+            writeln!(f, "{} {}", job.at, job.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Trace {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Trace, ParseError> {
+        let mut trace = Trace::default();
+        for (n, line) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let invalid = || ParseError::new(format!("trace line {}: {:?}", n + 1, line));
+            if let Some(until) = line.strip_prefix("until ") {
+                trace.until = Some(until.parse().map_err(|_| invalid())?);
+                continue;
+            }
+            let mut parts = line.splitn(3, ' ');
+            let (date, time, name) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(date), Some(time), Some(name)) if !name.is_empty() => (date, time, name),
+                _ => return Err(invalid()),
+            };
+            let at = format!("{} {}", date, time)
+                .parse()
+                .map_err(|_| invalid())?;
+            trace.fires.push(DueJob {
+                name: name.to_string(),
+                at,
+            });
+        }
+        Ok(trace)
+    }
+}
+
+impl Scheduler {
+    // Starts a new trace of every fire handed out from here on, by tick,
+    // pop_due or dispatch_due; any trace in progress is dropped.
+    pub fn record_trace(&mut self) {
+        self.trace = Some(Trace::default());
+    }
+
+    // Stops recording and hands back what was recorded.
+    pub fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+
+    // Ticks at each time a fire is due in `trace`, then at its `until`,
+    // and checks the fires come out the same. Meant for a scheduler set up
+    // as the recorded one was, at the same start.
+    pub fn replay(&mut self, trace: &Trace) -> Result<(), Divergence> {
+        let mut times: Vec<DateTime> = trace.fires.iter().map(|job| job.at).collect();
+        times.extend(trace.until);
+        times.dedup();
+        let fires = times.into_iter().flat_map(|at| self.tick(at)).collect();
+        trace.compare(&Trace {
+            fires,
+            until: trace.until,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Schedule;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    fn setup(report_at: u8) -> Scheduler {
+        let start = dt(2025, 1, 1, 0, 0);
+        let mut s = Scheduler::new();
+        s.add("report", Schedule::new().at(report_at, 0).daily(), start);
+        s.add("sync", Schedule::new().minute(0).hourly().repeat(3), start);
+        s
+    }
+
+    #[test]
+    fn records_and_replays() {
+        let mut s = setup(9);
+        s.record_trace();
+        s.tick(dt(2025, 1, 1, 12, 0));
+        s.tick(dt(2025, 1, 3, 0, 0));
+        let trace = s.take_trace().unwrap();
+        assert_eq!(trace.fires.len(), 5);
+        assert_eq!(trace.until, Some(dt(2025, 1, 3, 0, 0)));
+
+        let text = trace.to_string();
+        assert!(text.starts_with("until 2025-01-03 00:00\n2025-01-01 00:00 sync\n"));
+        assert_eq!(text.parse::<Trace>(), Ok(trace.clone()));
+
+        assert_eq!(setup(9).replay(&trace), Ok(()));
+        let err = setup(8).replay(&trace).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "fire 4: expected report at 2025-01-01 09:00, got report at 2025-01-01 08:00"
+        );
+    }
+
+    #[test]
+    fn runtimes_record_too() {
+        let mut tm = crate::testing::TimeMachine::new(dt(2025, 1, 1, 0, 0));
+        tm.add("report", Schedule::new().at(9, 0).daily(), |_| ())
+            .unwrap();
+        tm.record_trace();
+        tm.advance(crate::calendar::Span::days(2));
+        let trace = tm.take_trace().unwrap();
+        assert_eq!(
+            setup(9).replay(&trace),
+            Err(Divergence {
+                index: 0,
+                expected: Some(DueJob {
+                    name: "report".to_string(),
+                    at: dt(2025, 1, 1, 9, 0)
+                }),
+                actual: Some(DueJob {
+                    name: "sync".to_string(),
+                    at: dt(2025, 1, 1, 0, 0)
+                }),
+            })
+        );
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            serde_json::from_str::<Trace>(&serde_json::to_string(&trace).unwrap()).unwrap(),
+            trace
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_errors() {
+        assert!("2025-01-01 09:00".parse::<Trace>().is_err());
+        assert!("until tomorrow".parse::<Trace>().is_err());
+        assert_eq!("\n".parse::<Trace>(), Ok(Trace::default()));
+    }
+}
@@ -0,0 +1,29 @@
+// Driving a Runtime in the browser, where threads can't sleep: the loop
+// awaits gloo-timers timeouts instead, and the Runtime stays reachable
+// through the Rc between fires, so the page can add and remove jobs while it
+// runs. Start it with wasm_bindgen_futures::spawn_local.
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gloo_timers::future::sleep;
+
+use super::Runtime;
+use super::runtime::unix_now;
+
+// Returns once no job has occurrences left.
+pub async fn run_web(runtime: Rc<RefCell<Runtime>>) {
+    loop {
+        let next = {
+            let mut runtime = runtime.borrow_mut();
+            runtime.run_pending();
+            runtime.next_wakeup()
+        };
+        let Some(at) = next else {
+            return;
+        };
+        // Capped so a job added for sooner is picked up within a second.
+        let at = Duration::from_secs(at.unix().max(0) as u64);
+        sleep(at.saturating_sub(unix_now()).min(Duration::from_secs(1))).await;
+    }
+}
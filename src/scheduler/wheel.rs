@@ -0,0 +1,122 @@
+// Hierarchical timer wheel: four levels of 64 slots, one minute per slot at
+// the bottom and 64 times coarser each level up, covering about 31 years
+// ahead; anything later waits in an overflow list. Inserting is O(1) and
+// entries trickle down a level each time the wheel turns past their slot.
+use super::Entry;
+
+const SLOTS: i64 = 64;
+const BITS: u32 = 6;
+const LEVELS: usize = 4;
+
+#[derive(Debug)]
+pub(super) struct TimerWheel {
+    // The minute the wheel has turned to; None until the first insert.
+    now: Option<i64>,
+    levels: [Vec<Vec<Entry>>; LEVELS],
+    overflow: Vec<Entry>,
+    // Entries at or before `now`, waiting to be popped.
+    ready: Vec<Entry>,
+    len: usize,
+}
+
+impl Default for TimerWheel {
+    fn default() -> Self {
+        Self {
+            now: None,
+            levels: std::array::from_fn(|_| vec![Vec::new(); SLOTS as usize]),
+            overflow: Vec::new(),
+            ready: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl TimerWheel {
+    pub(super) fn push(&mut self, e: Entry) {
+        self.len += 1;
+        self.place(e);
+    }
+
+    fn place(&mut self, e: Entry) {
+        let t = e.0.minutes_since_epoch();
+        let now = *self.now.get_or_insert(t - 1);
+        if t <= now {
+            self.ready.push(e);
+            return;
+        }
+        let delta = t - now;
+        match (0..LEVELS).find(|&k| delta < SLOTS << (BITS * k as u32)) {
+            Some(k) => {
+                let slot = ((t >> (BITS * k as u32)) & (SLOTS - 1)) as usize;
+                self.levels[k][slot].push(e);
+            }
+            None => self.overflow.push(e),
+        }
+    }
+
+    // Turns the wheel to `to`, a minute at a time, jumping ahead whenever the
+    // bottom level has nothing left before the next cascade.
+    fn advance(&mut self, to: i64) {
+        let Some(mut now) = self.now else {
+            return;
+        };
+        while now < to {
+            if self.len == self.ready.len() {
+                now = to;
+                break;
+            }
+            if self.levels[0].iter().all(Vec::is_empty) {
+                // Nothing fires before the next multiple of 64.
+                now = (now | (SLOTS - 1)).min(to - 1);
+            }
+            now += 1;
+            self.now = Some(now);
+            for k in (1..=LEVELS).rev() {
+                if now & ((SLOTS << (BITS * (k as u32 - 1))) - 1) != 0 {
+                    continue;
+                }
+                let cascading = if k == LEVELS {
+                    std::mem::take(&mut self.overflow)
+                } else {
+                    let slot = ((now >> (BITS * k as u32)) & (SLOTS - 1)) as usize;
+                    std::mem::take(&mut self.levels[k][slot])
+                };
+                for e in cascading {
+                    self.place(e);
+                }
+            }
+            let slot = (now & (SLOTS - 1)) as usize;
+            let fired = std::mem::take(&mut self.levels[0][slot]);
+            self.ready.extend(fired);
+        }
+        self.now = Some(now);
+    }
+
+    // Walks every entry; the wheel trades this for O(1) inserts and expiry.
+    pub(super) fn earliest(&self, live: impl Fn(&Entry) -> bool) -> Option<&Entry> {
+        self.levels
+            .iter()
+            .flatten()
+            .flatten()
+            .chain(&self.overflow)
+            .chain(&self.ready)
+            .filter(|e| live(e))
+            .min()
+    }
+
+    // The oldest live entry due at or before `now`; dead ones are dropped.
+    pub(super) fn pop(&mut self, now: i64, live: impl Fn(&Entry) -> bool) -> Option<Entry> {
+        self.advance(now);
+        let before = self.ready.len();
+        self.ready.retain(|e| live(e));
+        self.len -= before - self.ready.len();
+        let (i, _) = self
+            .ready
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.0.minutes_since_epoch() <= now)
+            .min_by(|a, b| a.1.cmp(b.1))?;
+        self.len -= 1;
+        Some(self.ready.swap_remove(i))
+    }
+}
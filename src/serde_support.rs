@@ -0,0 +1,492 @@
+// Serde support. A Schedule serializes as its ScheduleSpec and is validated
+// like the setters validate on the way back in. For terser configs, a field
+// can pick a text form instead:
+//
+//     #[serde(with = "brahma::serde_support::cron")]    "30 22 20 * *"
+//     #[serde(with = "brahma::serde_support::rrule")]   "FREQ=MONTHLY;..."
+//     #[serde(with = "brahma::serde_support::compact")] either, else the spec
+//
+// `compact` reads any of the three, so configs can mix them.
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::time::{is_leap_day_outside_leap_year, is_valid_day_for_month, is_valid_day_of_year};
+use crate::types::*;
+
+impl Serialize for Schedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.spec().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        from_spec(ScheduleSpec::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+// Dates and times go through `new`, so a config can't hand the scheduler a
+// month 13 or an hour 99.
+#[derive(Deserialize)]
+struct DateSpec {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+#[derive(Deserialize)]
+struct TimeSpec {
+    hour: u8,
+    minute: u8,
+}
+
+#[derive(Deserialize)]
+struct DateTimeSpec {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let d = DateSpec::deserialize(deserializer)?;
+        Date::new(d.year, d.month, d.day).ok_or_else(|| D::Error::custom("invalid date"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let t = TimeSpec::deserialize(deserializer)?;
+        Time::new(t.hour, t.minute).ok_or_else(|| D::Error::custom("invalid time of day"))
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let t = DateTimeSpec::deserialize(deserializer)?;
+        DateTime::new(t.year, t.month, t.day, t.hour, t.minute)
+            .ok_or_else(|| D::Error::custom("invalid date and time"))
+    }
+}
+
+fn from_spec(spec: ScheduleSpec) -> Result<Schedule, String> {
+    let nth_ok = |n: u8| (1..=5).contains(&n);
+    let checks = [
+        (
+            spec.day.is_none_or(|d| (1..=31).contains(&d)),
+            "day must be 1-31",
+        ),
+        (
+            spec.repeat.end_date().is_none_or(|e| {
+                let day = match (e.month, e.day) {
+                    (Some(m), Some(d)) => d > 0 && is_valid_day_for_month(m as u8, d),
+                    (None, Some(d)) => (1..=31).contains(&d),
+                    _ => true,
+                };
+                let leap = match (e.year, e.month, e.day) {
+                    (Some(y), Some(m), Some(d)) => !is_leap_day_outside_leap_year(y, m as u8, d),
+                    _ => true,
+                };
+                day && leap && e.hour.is_none_or(|h| h < 24) && e.minute.is_none_or(|m| m < 60)
+            }),
+            "invalid end date",
+        ),
+        (
+            match (spec.month, spec.day) {
+                (Some(m), Some(d)) => is_valid_day_for_month(m as u8, d),
+                _ => true,
+            },
+            "day does not exist in month",
+        ),
+        (spec.hour.is_none_or(|h| h < 24), "hour must be 0-23"),
+        (spec.minute.is_none_or(|m| m < 60), "minute must be 0-59"),
+        (
+            match spec.frequency {
+                Some(FrequencyPattern::ByDay((Some(n), _))) => nth_ok(n),
+                _ => true,
+            },
+            "weekday occurrence must be 1-5",
+        ),
+        (
+            match spec.except {
+                Some(Except::N(d)) => (1..=31).contains(&d),
+                Some(Except::NthDay((n, _))) => nth_ok(n),
+                _ => true,
+            },
+            "invalid exception",
+        ),
+        (
+            spec.range.is_none_or(|(a, b)| {
+                Time::new(a.hour, a.minute).is_some() && Time::new(b.hour, b.minute).is_some()
+            }),
+            "invalid time window",
+        ),
+        (spec.stride != Some(0), "stride must be at least 1"),
+        (
+            match (spec.year, spec.month, spec.day) {
+                (Some(y), Some(m), Some(d)) => !is_leap_day_outside_leap_year(y, m as u8, d),
+                _ => true,
+            },
+            "Feb 29 needs a leap year",
+        ),
+        (
+            match (spec.year, spec.day_of_year) {
+                (Some(y), Some(n)) => is_valid_day_of_year(y, n),
+                (None, Some(n)) => (1..=366).contains(&n),
+                _ => true,
+            },
+            "day of year does not exist",
+        ),
+        (
+            spec.day_of_year.is_none() || (spec.day.is_none() && spec.month.is_none()),
+            "day of year can't be combined with a day or month",
+        ),
+        (
+            spec.fiscal.is_none()
+                || (spec.day.is_none() && spec.month.is_none() && spec.day_of_year.is_none()),
+            "fiscal period can't be combined with a day or month",
+        ),
+        (
+            spec.dates
+                .iter()
+                .all(|(m, d)| *d > 0 && is_valid_day_for_month(*m as u8, *d)),
+            "a date does not exist",
+        ),
+        (
+            spec.every_months
+                .is_none_or(|(n, _)| n > 0 && 12u8.is_multiple_of(n)),
+            "month interval must divide 12",
+        ),
+        (
+            spec.every_months.is_none()
+                || (spec.month.is_none()
+                    && spec.dates.is_empty()
+                    && spec.day_of_year.is_none()
+                    && spec.fiscal.is_none()
+                    && spec.frequency == Some(FrequencyPattern::Frequency(Frequency::Monthly))),
+            "month interval needs a monthly schedule without a month",
+        ),
+        (spec.holidays.is_valid(), "a holiday does not exist"),
+        (
+            spec.years.is_empty() || spec.year.is_none(),
+            "years can't be combined with a year",
+        ),
+        (
+            spec.dates.is_empty()
+                || (spec.day.is_none()
+                    && spec.month.is_none()
+                    && spec.day_of_year.is_none()
+                    && spec.fiscal.is_none()),
+            "dates can't be combined with a day or month",
+        ),
+        (
+            spec.times
+                .iter()
+                .all(|t| Time::new(t.hour, t.minute).is_some()),
+            "invalid time of day",
+        ),
+        (
+            spec.times.is_empty() || (spec.hour.is_none() && spec.minute.is_none()),
+            "times of day can't be combined with an hour or minute",
+        ),
+        (
+            spec.every_minutes.is_none_or(|n| (1..24 * 60).contains(&n)),
+            "minute interval must be 1 to 1439",
+        ),
+        (
+            spec.every_minutes.is_none()
+                || (spec.hour.is_none()
+                    && spec.minute.is_none()
+                    && spec.times.is_empty()
+                    && spec.frequency != Some(FrequencyPattern::Frequency(Frequency::Hourly))),
+            "minute interval needs a frequency other than hourly and no time of day",
+        ),
+        (
+            spec.hours_step.is_none_or(|n| (1..24).contains(&n))
+                && spec.minutes_step.is_none_or(|n| (1..60).contains(&n)),
+            "steps must be 1 to 23 hours or 1 to 59 minutes",
+        ),
+        (
+            spec.hours_step.is_none()
+                || (spec.hour.is_none() && spec.times.is_empty() && spec.every_minutes.is_none()),
+            "an hour step can't be combined with an hour or times of day",
+        ),
+        (
+            spec.minutes_step.is_none()
+                || (spec.minute.is_none() && spec.times.is_empty() && spec.every_minutes.is_none()),
+            "a minute step can't be combined with a minute or times of day",
+        ),
+    ];
+    if let Some((_, what)) = checks.iter().find(|(ok, _)| !ok) {
+        return Err(what.to_string());
+    }
+    let mut times = spec.times;
+    times.sort();
+    times.dedup();
+    let mut years = spec.years;
+    years.sort_unstable();
+    years.dedup();
+    let mut dates = spec.dates;
+    dates.sort_by_key(|(m, d)| (*m as u8, *d));
+    dates.dedup();
+    #[cfg(feature = "astro")]
+    if spec.solar.is_some_and(|s| !s.location.is_valid()) {
+        return Err("latitude must be within ±90 and longitude within ±180".to_string());
+    }
+    #[cfg(feature = "panchang")]
+    if spec.lunar.is_some_and(|l| !l.is_valid()) {
+        return Err("invalid lunar day".to_string());
+    }
+    Ok(Schedule::from_parts(ScheduleParts {
+        year: spec.year,
+        day: spec.day,
+        month: spec.month,
+        years,
+        month_step: spec.every_months,
+        business_days: spec.business_days,
+        holidays: spec.holidays.sorted(),
+        week_parity: spec.week_parity,
+        day_of_year: spec.day_of_year,
+        dates,
+        fiscal: spec.fiscal,
+        fiscal_year: spec.fiscal_year,
+        #[cfg(feature = "astro")]
+        solar: spec.solar,
+        #[cfg(feature = "panchang")]
+        lunar: spec.lunar,
+        hour: spec.hour,
+        minute: spec.minute,
+        hour_step: spec.hours_step,
+        minute_step: spec.minutes_step,
+        frequency: spec.frequency,
+        except: spec.except,
+        repeat: spec.repeat,
+        range: spec.range,
+        offset: spec.offset,
+        delay: spec.delay,
+        immediate: spec.run_immediately,
+        stride: spec.stride,
+        not_before: spec.starting_from,
+        leap_day: spec.leap_day,
+        times,
+        interval: spec.every_minutes,
+        weekdays: spec.weekdays,
+    }))
+}
+
+pub mod cron {
+    use super::*;
+    use crate::parse::{from_cron, to_cron};
+
+    pub fn serialize<S: Serializer>(s: &Schedule, serializer: S) -> Result<S::Ok, S::Error> {
+        to_cron(s)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Schedule, D::Error> {
+        from_cron(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+pub mod rrule {
+    use super::*;
+    use crate::parse::{from_rrule, to_rrule};
+
+    pub fn serialize<S: Serializer>(s: &Schedule, serializer: S) -> Result<S::Ok, S::Error> {
+        to_rrule(s)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Schedule, D::Error> {
+        from_rrule(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+// Cron where it fits, then RRULE, then the structured spec. Cron is tried
+// first for being the shorter, but it only promises the same occurrences,
+// so a schedule may come back from it configured differently.
+pub mod compact {
+    use super::*;
+    use crate::parse::{to_cron, to_rrule};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Form {
+        Text(String),
+        Spec(Box<Schedule>),
+    }
+
+    pub fn serialize<S: Serializer>(s: &Schedule, serializer: S) -> Result<S::Ok, S::Error> {
+        match to_cron(s).or_else(|_| to_rrule(s)) {
+            Ok(text) => text.serialize(serializer),
+            Err(_) => s.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Schedule, D::Error> {
+        match Form::deserialize(deserializer)? {
+            Form::Text(text) => text.parse().map_err(D::Error::custom),
+            Form::Spec(s) => Ok(*s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        #[serde(with = "compact")]
+        report: Schedule,
+        #[serde(with = "rrule")]
+        backup: Schedule,
+        cleanup: Schedule,
+    }
+
+    #[test]
+    fn structured_round_trip_and_validation() {
+        let s = Schedule::new()
+            .at(22, 0)
+            .every_nth_day(3, Days::SAT)
+            .except_on_month(Month::OCT)
+            .repeat(5)
+            .starting_from(DateTime::new(2026, 1, 1, 0, 0).unwrap());
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["hour"], json!(22));
+        assert_eq!(value["starting_from"]["year"], json!(2026));
+        assert_eq!(
+            serde_json::from_value::<Schedule>(value.clone()).unwrap(),
+            s
+        );
+        let mut bad = value.clone();
+        bad["starting_from"]["month"] = json!(13);
+        assert!(serde_json::from_value::<Schedule>(bad).is_err());
+        assert!(
+            serde_json::from_value::<Date>(json!({ "year": 2025, "month": 2, "day": 29 })).is_err()
+        );
+        assert!(serde_json::from_value::<Time>(json!({ "hour": 9, "minute": 60 })).is_err());
+
+        let s = Schedule::new()
+            .at(9, 0)
+            .daily()
+            .ending_on(EndDate::on(3, Month::MAR).at(18, 0));
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Schedule>(value.clone()).unwrap(),
+            s
+        );
+        let mut bad = value.clone();
+        bad["repeat"]["UntilDate"]["day"] = json!(40);
+        assert!(serde_json::from_value::<Schedule>(bad).is_err());
+        let mut bad = value;
+        bad["repeat"]["UntilDate"]["hour"] = json!(99);
+        assert!(serde_json::from_value::<Schedule>(bad).is_err());
+
+        let s = Schedule::new().on_days_at(&[Days::MON, Days::WED], &[(9, 0), (17, 30)]);
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["weekdays"], json!(["MON", "WED"]));
+        assert_eq!(value["times"][1], json!({ "hour": 17, "minute": 30 }));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let late = json!({ "frequency": { "Frequency": "Weekly" }, "times": [{ "hour": 24, "minute": 0 }] });
+        assert!(serde_json::from_value::<Schedule>(late).is_err());
+
+        let s = Schedule::new()
+            .on_dates(&[(Month::JUL, 4), (Month::JAN, 1)])
+            .at(9, 0)
+            .daily();
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["dates"], json!([["JAN", 1], ["JUL", 4]]));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let s = Schedule::new().at(9, 0).daily().in_years(&[2026, 2025]);
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["years"], json!([2025, 2026]));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let both = json!({ "year": 2025, "years": [2026] });
+        assert!(serde_json::from_value::<Schedule>(both).is_err());
+
+        let s = Schedule::new().every_months_from(3, Month::FEB).on_day(31);
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["every_months"], json!([3, "FEB"]));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let s = Schedule::new()
+            .on_quarter_end()
+            .offset_business_days(-1)
+            .monthly();
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["business_days"], json!(-1));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let s = Schedule::new()
+            .on_first_business_day()
+            .holiday_calendar(Holidays::new().every_year(Month::JAN, 1));
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["holidays"]["yearly"], json!([["JAN", 1]]));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let s = Schedule::new()
+            .every_on_day(Days::TUE)
+            .on_alternate_weeks(Parity::Even);
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["week_parity"], json!("Even"));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let s = Schedule::new().every_minutes(30).between((9, 0), (17, 0));
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["every_minutes"], json!(30));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let s = Schedule::new().minutes_step(15).hours_step(2);
+        let value = serde_json::to_value(&s).unwrap();
+        assert_eq!(value["minutes_step"], json!(15));
+        assert_eq!(serde_json::from_value::<Schedule>(value).unwrap(), s);
+        let both =
+            json!({ "frequency": { "Frequency": "Hourly" }, "minute": 0, "minutes_step": 5 });
+        assert!(serde_json::from_value::<Schedule>(both).is_err());
+        let hourly = json!({ "frequency": { "Frequency": "Hourly" }, "every_minutes": 15 });
+        assert!(serde_json::from_value::<Schedule>(hourly).is_err());
+        let feb_30 = json!({ "holidays": { "yearly": [["FEB", 30]] } });
+        assert!(serde_json::from_value::<Schedule>(feb_30).is_err());
+        let every_5 =
+            json!({ "frequency": { "Frequency": "Monthly" }, "every_months": [5, "JAN"] });
+        assert!(serde_json::from_value::<Schedule>(every_5).is_err());
+        let daily = json!({ "frequency": { "Frequency": "Daily" }, "every_months": [3, "JAN"] });
+        assert!(serde_json::from_value::<Schedule>(daily).is_err());
+
+        let apr_31 = json!({ "dates": [["APR", 31]] });
+        assert!(serde_json::from_value::<Schedule>(apr_31).is_err());
+
+        let feb_31 = json!({ "month": "FEB", "day": 31 });
+        assert!(serde_json::from_value::<Schedule>(feb_31).is_err());
+    }
+
+    #[test]
+    fn text_forms_in_one_config() {
+        let config = Config {
+            report: Schedule::new().day_with_time(20, 22, 30).monthly(),
+            backup: Schedule::new().at(2, 0).daily().repeat(3),
+            cleanup: Schedule::new().daily().except_on_day(Days::SUN),
+        };
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["report"], json!("30 22 20 * *"));
+        assert_eq!(
+            value["backup"],
+            json!("FREQ=DAILY;BYHOUR=2;BYMINUTE=0;COUNT=3")
+        );
+        assert_eq!(serde_json::from_value::<Config>(value).unwrap(), config);
+
+        // compact also takes RRULE and the spec.
+        for report in [
+            json!("FREQ=MONTHLY;BYMONTHDAY=20;BYHOUR=22;BYMINUTE=30"),
+            json!({ "day": 20, "hour": 22, "minute": 30, "frequency": { "Frequency": "Monthly" } }),
+        ] {
+            let value = json!({ "report": report, "backup": "FREQ=DAILY", "cleanup": {} });
+            let config: Config = serde_json::from_value(value).unwrap();
+            assert_eq!(
+                config.report,
+                Schedule::new().day_with_time(20, 22, 30).monthly()
+            );
+        }
+    }
+}
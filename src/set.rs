@@ -0,0 +1,147 @@
+// Named group of related schedules, e.g. everything belonging to one tenant,
+// with operations that apply to the whole group.
+use crate::recurrence::Recurrence;
+use crate::time::DateTime;
+use crate::types::{Except, Schedule};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleSet {
+    name: String,
+    schedules: Vec<(String, Schedule)>,
+}
+
+impl ScheduleSet {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            schedules: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn add(mut self, name: impl Into<String>, schedule: Schedule) -> Self {
+        let name = name.into();
+        if self.get(&name).is_some() {
+            eprintln!("Schedule {} already in set {}. Ignoring.", name, self.name);
+        } else {
+            self.schedules.push((name, schedule));
+        }
+        self
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Schedule> {
+        let i = self.schedules.iter().position(|(n, _)| n == name)?;
+        Some(self.schedules.remove(i).1)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Schedule> {
+        self.schedules
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, s)| s)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = (&str, &Schedule)> {
+        self.schedules.iter().map(|(n, s)| (n.as_str(), s))
+    }
+
+    pub fn len(&self) -> usize {
+        self.schedules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+
+    // Goes through Schedule::except, so members that already have an
+    // exception keep theirs.
+    pub fn except(mut self, e: Except) -> Self {
+        self.schedules = self
+            .schedules
+            .into_iter()
+            .map(|(n, s)| (n, s.except(e)))
+            .collect();
+        self
+    }
+
+    // The earliest occurrence across the group and which member it belongs to.
+    pub fn next_due(&self, after: DateTime) -> Option<(&str, DateTime)> {
+        self.schedules
+            .iter()
+            .filter_map(|(n, s)| s.next_after(after).map(|at| (n.as_str(), at)))
+            .min_by_key(|(_, at)| *at)
+    }
+}
+
+// The group as one stream: fires whenever any member does.
+impl Recurrence for ScheduleSet {
+    fn upcoming(&self, from: DateTime) -> Box<dyn Iterator<Item = DateTime> + '_> {
+        let mut streams: Vec<_> = self
+            .schedules
+            .iter()
+            .map(|(_, s)| s.upcoming(from).peekable())
+            .collect();
+        Box::new(std::iter::from_fn(move || {
+            let next = streams.iter_mut().filter_map(|s| s.peek().copied()).min()?;
+            for s in streams.iter_mut() {
+                s.next_if_eq(&next);
+            }
+            Some(next)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Days, Month};
+
+    fn tenant() -> ScheduleSet {
+        ScheduleSet::new("acme")
+            .add("report", Schedule::new().at(6, 0).daily())
+            .add("invoice", Schedule::new().on_day(1).monthly())
+            .add("backup", Schedule::new().every_on_day(Days::SUN))
+    }
+
+    #[test]
+    fn duplicate_names_are_ignored() {
+        let set = tenant().add("report", Schedule::new().hourly());
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.get("report").unwrap().hour, Some(6));
+    }
+
+    #[test]
+    fn except_applies_to_every_member() {
+        let set = tenant().except(Except::Month(Month::DEC));
+        assert!(
+            set.list()
+                .all(|(_, s)| s.recurring.except == Some(Except::Month(Month::DEC)))
+        );
+    }
+
+    #[test]
+    fn next_due_names_the_member() {
+        let from = DateTime::new(2025, 8, 31, 1, 0).unwrap();
+        assert_eq!(
+            tenant().next_due(from),
+            Some(("report", DateTime::new(2025, 8, 31, 6, 0).unwrap()))
+        );
+    }
+
+    #[test]
+    fn set_merges_members_in_order() {
+        let from = DateTime::new(2025, 8, 31, 0, 0).unwrap();
+        let next: Vec<_> = tenant().upcoming(from).take(3).collect();
+        assert_eq!(
+            next,
+            vec![
+                DateTime::new(2025, 8, 31, 0, 0).unwrap(),
+                DateTime::new(2025, 8, 31, 6, 0).unwrap(),
+                DateTime::new(2025, 9, 1, 0, 0).unwrap()
+            ]
+        );
+    }
+}
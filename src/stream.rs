@@ -0,0 +1,155 @@
+// Occurrences as a futures Stream, each yielded once the clock reaches it,
+// for async code that wants to drive its own execution without a Runtime:
+//
+//     let mut next = schedule.stream(SystemClock);
+//     while let Some(at) = next.next().await { ... }
+//
+// Works on any executor: waiting is done by a helper thread that sleeps on
+// the clock and wakes the task, one at a time per stream, and gone within
+// a second of the stream being dropped.
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use futures_core::Stream;
+
+use crate::occurrence::{Plan, Progress};
+use crate::scheduler::Clock;
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+pub struct OccurrenceStream<C> {
+    schedule: Schedule,
+    plan: Plan,
+    progress: Progress,
+    // The occurrence waited on, taken from the schedule but not yet yielded.
+    next: Option<DateTime>,
+    clock: Arc<C>,
+    waker: Arc<Mutex<Option<Waker>>>,
+    // The occurrence a helper thread is sleeping towards.
+    armed: Option<DateTime>,
+}
+
+impl Schedule {
+    // Starts from the clock's now, like registering with a Runtime would.
+    pub fn stream<C: Clock + Sync + 'static>(&self, clock: C) -> OccurrenceStream<C> {
+        let schedule = self.resolve(clock.now());
+        let mut stream = OccurrenceStream {
+            plan: schedule.plan(),
+            progress: schedule.start(clock.now()),
+            schedule,
+            next: None,
+            clock: Arc::new(clock),
+            waker: Arc::new(Mutex::new(None)),
+            armed: None,
+        };
+        stream.next = stream.advance();
+        stream
+    }
+}
+
+impl<C> OccurrenceStream<C> {
+    fn advance(&mut self) -> Option<DateTime> {
+        let mut occurrences = self.schedule.resume_with(self.plan, self.progress);
+        let next = occurrences.next();
+        self.progress = occurrences.progress();
+        next
+    }
+}
+
+impl<C: Clock + Sync + 'static> Stream for OccurrenceStream<C> {
+    type Item = DateTime;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<DateTime>> {
+        let this = self.get_mut();
+        let Some(at) = this.next else {
+            return Poll::Ready(None);
+        };
+        if this.clock.now() >= at {
+            this.next = this.advance();
+            return Poll::Ready(Some(at));
+        }
+        *this.waker.lock().unwrap_or_else(PoisonError::into_inner) = Some(cx.waker().clone());
+        if this.armed != Some(at) {
+            this.armed = Some(at);
+            let clock = Arc::clone(&this.clock);
+            // Weak, so a dropped stream lets its helper give up early.
+            let waker = Arc::downgrade(&this.waker);
+            thread::spawn(move || {
+                while clock.now() < at {
+                    if waker.strong_count() == 0 {
+                        return;
+                    }
+                    clock.sleep_until(at);
+                }
+                if let Some(waker) = waker.upgrade()
+                    && let Some(w) = waker.lock().unwrap_or_else(PoisonError::into_inner).take()
+                {
+                    w.wake();
+                }
+            });
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler::ManualClock;
+    use std::future::Future;
+    use std::task::Wake;
+    use std::thread::Thread;
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(out) = future.as_mut().poll(&mut cx) {
+                return out;
+            }
+            thread::park();
+        }
+    }
+
+    fn next<S: Stream + Unpin>(stream: &mut S) -> impl Future<Output = Option<S::Item>> + '_ {
+        std::future::poll_fn(move |cx| Pin::new(&mut *stream).poll_next(cx))
+    }
+
+    #[test]
+    fn yields_each_occurrence_once_it_arrives() {
+        let start = DateTime::new(2025, 1, 1, 0, 0).unwrap();
+        let clock = ManualClock::new(start);
+        let mut stream = Schedule::new()
+            .at(9, 0)
+            .daily()
+            .repeat(3)
+            .stream(clock.clone());
+
+        let fired: Vec<_> = block_on(async {
+            let mut fired = Vec::new();
+            while let Some(at) = next(&mut stream).await {
+                // ManualClock sleeps by jumping, so the clock is at `at`.
+                assert_eq!(clock.now(), at);
+                fired.push(at);
+            }
+            fired
+        });
+        assert_eq!(
+            fired,
+            (0..3)
+                .map(|d| start.add_minutes(d * 1440 + 540))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,497 @@
+// Random schedules for property tests. `any::<Schedule>()` covers the whole
+// setter surface, `cron_schedule` only what `to_cron` can write out, and
+// `invalid_spec` specs that break exactly one rule, for checking that
+// deserializing and other validating paths refuse them. They are built from
+// proptest's own strategies, so a failure shrinks field by field towards the
+// simplest schedule that still fails.
+//
+// `Gen` draws the same kinds of schedule, and parser input, from a seed
+// instead, for a plain loop over seeds where any failure can be replayed from
+// the seed alone.
+use crate::parse::{to_cron, to_rrule};
+use crate::time::{DateTime, days_in_month};
+use crate::types::*;
+
+#[cfg(feature = "proptest")]
+pub use self::strategies::*;
+
+const DAYS: [Days; 7] = [
+    Days::SUN,
+    Days::MON,
+    Days::TUE,
+    Days::WED,
+    Days::THUR,
+    Days::FRI,
+    Days::SAT,
+];
+
+// Pieces of the cron, RRULE and natural-language grammars for `input`.
+const PARSER_WORDS: &str = "every at on between and except repeat times until day week month \
+    hourly daily 3rd fifth sat thurs feb sept 29 31 9:30 22:00 24:60 * ? @daily @yearly # #5 #0 \
+    SAT#6 0 6 7 -1 FREQ=MONTHLY FREQ=YEARLY BYDAY=3SA BYDAY=-1MO BYMONTHDAY=31 BYHOUR=24 \
+    COUNT=0 UNTIL=20250230T250000Z UNTIL=2025 UNTIL=20250101T RRULE: FREQ= =";
+
+// SplitMix64: small, fast and good enough to spread seeds over the choices.
+#[derive(Debug, Clone)]
+pub struct Gen(u64);
+
+impl Gen {
+    pub fn new(seed: u64) -> Self {
+        Gen(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // 0..n
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    fn month(&mut self) -> u8 {
+        self.below(12) as u8 + 1
+    }
+
+    fn weekday(&mut self) -> Days {
+        DAYS[self.below(7) as usize]
+    }
+
+    // Exists in every year when `month` is known, else in every month.
+    fn day_in(&mut self, month: Option<u8>) -> u8 {
+        let last = month.map_or(28, |m| days_in_month(2023, m));
+        self.below(last as u64) as u8 + 1
+    }
+
+    fn frequency(&mut self) -> FrequencyPattern {
+        match self.below(6) {
+            0 => FrequencyPattern::Frequency(Frequency::Hourly),
+            1 => FrequencyPattern::Frequency(Frequency::Daily),
+            2 => FrequencyPattern::Frequency(Frequency::Weekly),
+            3 => FrequencyPattern::Frequency(Frequency::Monthly),
+            4 => FrequencyPattern::ByDay((None, self.weekday())),
+            _ => FrequencyPattern::ByDay((Some(self.below(5) as u8 + 1), self.weekday())),
+        }
+    }
+
+    // A schedule built through the setters, so valid by construction:
+    // recurring or one-shot, with any mix of exceptions, bounds, windows and
+    // shifts.
+    pub fn schedule(&mut self) -> Schedule {
+        if self.chance(10) {
+            let at = DateTime::from_unix(self.below(4_102_444_800) as i64);
+            return Schedule::from_datetime(at);
+        }
+        let mut s = Schedule::new().minute(self.below(60) as u8);
+        let frequency = self.frequency();
+        if frequency != FrequencyPattern::Frequency(Frequency::Hourly) && self.chance(80) {
+            s = s.hour(self.below(24) as u8);
+        }
+        let month = self.chance(20).then(|| self.month());
+        if let Some(m) = month {
+            s = s.month(m);
+        }
+        if matches!(frequency, FrequencyPattern::Frequency(_)) && self.chance(30) {
+            s = s.day(self.day_in(month));
+        }
+        s = s.every(frequency);
+        if self.chance(25) {
+            s = s.except(match self.below(4) {
+                0 => Except::Day(self.weekday()),
+                1 => Except::N(self.day_in(None)),
+                2 => Except::NthDay((self.below(5) as u8 + 1, self.weekday())),
+                _ => Except::Month(Month::from_u8(self.month()).unwrap_or(Month::JAN)),
+            });
+        }
+        if self.chance(20) {
+            s = s.repeat(self.below(100) as u32 + 1);
+        }
+        if self.chance(20) {
+            let m = self.month();
+            s = s.until_date(self.day_in(Some(m)), m);
+        }
+        if self.chance(15) {
+            let start = self.below(23) as u8;
+            let end = start + 1 + self.below(23 - start as u64) as u8;
+            s = s.between((start, 0), (end, 0));
+        }
+        if self.chance(10) {
+            s = s.every_nth_occurrence(self.below(4) as u32 + 2);
+        }
+        s
+    }
+
+    // A recurring schedule `to_cron` accepts: a minute, and an hour, a day
+    // of the month, a month or a weekday only where cron has room for them.
+    pub fn cron_schedule(&mut self) -> Schedule {
+        let s = Schedule::new().minute(self.below(60) as u8);
+        match self.below(4) {
+            0 if self.chance(50) => s.day(self.day_in(None)).hourly(),
+            0 => s.hourly(),
+            1 => {
+                let s = s.hour(self.below(24) as u8);
+                match self.chance(30) {
+                    true => s.month(self.month()).daily(),
+                    false => s.daily(),
+                }
+            }
+            2 => s
+                .hour(self.below(24) as u8)
+                .day(self.day_in(None))
+                .monthly(),
+            _ => {
+                let n = self.chance(50).then(|| self.below(5) as u8 + 1);
+                s.hour(self.below(24) as u8)
+                    .every(FrequencyPattern::ByDay((n, self.weekday())))
+            }
+        }
+    }
+
+    // Text for the parsers: half the time a cron, RRULE or English rendering
+    // of a schedule, then a few words from their grammars or stray bytes
+    // put in, swapped in or taken out, so most of it almost parses.
+    pub fn input(&mut self) -> String {
+        let mut pieces: Vec<String> = Vec::new();
+        if self.chance(50) {
+            let s = self.cron_schedule();
+            let text = match self.below(3) {
+                0 => to_cron(&s).unwrap_or_default(),
+                1 => to_rrule(&s).unwrap_or_default(),
+                _ => s.to_string(),
+            };
+            pieces = text.split_inclusive([' ', ';']).map(String::from).collect();
+        }
+        for _ in 0..self.below(8) {
+            let at = self.below(pieces.len() as u64 + 1) as usize;
+            let piece = self.piece();
+            match self.below(3) {
+                0 if at < pieces.len() => pieces[at] = piece,
+                1 if at < pieces.len() => drop(pieces.remove(at)),
+                _ => pieces.insert(at, piece),
+            }
+        }
+        pieces.concat()
+    }
+
+    fn piece(&mut self) -> String {
+        let words: Vec<&str> = PARSER_WORDS.split_whitespace().collect();
+        let mut piece = match self.below(10) {
+            0 => char::from_u32(self.below(0x11_0000) as u32)
+                .unwrap_or('\u{fffd}')
+                .to_string(),
+            1 => self.next().to_string(),
+            2 => (b"0123456789:=;#*@-/,"[self.below(19) as usize] as char).to_string(),
+            _ => words[self.below(words.len() as u64) as usize].to_string(),
+        };
+        if self.chance(70) {
+            piece.push(' ');
+        }
+        piece
+    }
+
+    // The spec of a valid schedule with one field pushed out of range: day
+    // 32, a day the month lacks, hour 24, minute 60, a 6th weekday, a zero
+    // stride or Feb 29 in a common year.
+    pub fn invalid_spec(&mut self) -> ScheduleSpec {
+        let mut spec = Schedule::new().at(9, 0).daily().spec();
+        match self.below(7) {
+            0 => spec.day = Some(32 + self.below(224) as u8),
+            1 => {
+                let (month, day) =
+                    [(Month::FEB, 30), (Month::APR, 31), (Month::NOV, 31)][self.below(3) as usize];
+                (spec.month, spec.day) = (Some(month), Some(day));
+            }
+            2 => spec.hour = Some(24 + self.below(232) as u8),
+            3 => spec.minute = Some(60 + self.below(196) as u8),
+            4 => {
+                let n = 6 + self.below(250) as u8;
+                spec.frequency = Some(FrequencyPattern::ByDay((Some(n), self.weekday())));
+            }
+            5 => spec.stride = Some(0),
+            _ => {
+                let year = 2001 + 4 * self.below(24) as i32 + self.below(3) as i32;
+                (spec.year, spec.month, spec.day) = (Some(year), Some(Month::FEB), Some(29));
+            }
+        }
+        spec
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod strategies {
+    use proptest::arbitrary::Arbitrary;
+    use proptest::option;
+    use proptest::prelude::*;
+
+    use super::DAYS;
+    use crate::time::DateTime;
+    use crate::types::*;
+
+    impl Arbitrary for Schedule {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Schedule>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            schedule().boxed()
+        }
+    }
+
+    pub fn weekday() -> impl Strategy<Value = Days> {
+        (0..7usize).prop_map(|i| DAYS[i])
+    }
+
+    pub fn frequency() -> impl Strategy<Value = FrequencyPattern> {
+        prop_oneof![
+            Just(FrequencyPattern::Frequency(Frequency::Hourly)),
+            Just(FrequencyPattern::Frequency(Frequency::Daily)),
+            Just(FrequencyPattern::Frequency(Frequency::Weekly)),
+            Just(FrequencyPattern::Frequency(Frequency::Monthly)),
+            weekday().prop_map(|d| FrequencyPattern::ByDay((None, d))),
+            (1..=5u8, weekday()).prop_map(|(n, d)| FrequencyPattern::ByDay((Some(n), d))),
+        ]
+    }
+
+    fn except() -> impl Strategy<Value = Except> {
+        prop_oneof![
+            weekday().prop_map(Except::Day),
+            (1..=28u8).prop_map(Except::N),
+            (1..=5u8, weekday()).prop_map(Except::NthDay),
+            (1..=12u8).prop_map(|m| Except::Month(Month::from_u8(m).unwrap_or(Month::JAN))),
+        ]
+    }
+
+    // Built through the setters, so valid by construction: recurring or
+    // one-shot, with any mix of exceptions, bounds, windows and shifts. Days
+    // stop at 28 so they exist in every month.
+    pub fn schedule() -> impl Strategy<Value = Schedule> {
+        let one_shot =
+            (0..4_102_444_800i64).prop_map(|t| Schedule::from_datetime(DateTime::from_unix(t)));
+        let recurring = (
+            0..60u8,
+            option::of(0..24u8),
+            frequency(),
+            option::of(1..=12u8),
+            option::of(1..=28u8),
+            option::of(except()),
+            option::of(1..=100u32),
+            option::of((1..=28u8, 1..=12u8)),
+            option::of((0..23u8, 1..=23u8)),
+            option::of(2..=5u32),
+        )
+            .prop_map(
+                |(minute, hour, frequency, month, day, except, repeat, until, window, stride)| {
+                    let mut s = Schedule::new().minute(minute);
+                    if let Some(h) =
+                        hour.filter(|_| frequency != FrequencyPattern::Frequency(Frequency::Hourly))
+                    {
+                        s = s.hour(h);
+                    }
+                    if let Some(m) = month {
+                        s = s.month(m);
+                    }
+                    if let Some(d) =
+                        day.filter(|_| matches!(frequency, FrequencyPattern::Frequency(_)))
+                    {
+                        s = s.day(d);
+                    }
+                    s = s.every(frequency);
+                    if let Some(e) = except {
+                        s = s.except(e);
+                    }
+                    if let Some(n) = repeat {
+                        s = s.repeat(n);
+                    }
+                    if let Some((d, m)) = until {
+                        s = s.until_date(d, m);
+                    }
+                    if let Some((start, len)) = window {
+                        s = s.between((start, 0), ((start + len).min(23), 0));
+                    }
+                    if let Some(n) = stride {
+                        s = s.every_nth_occurrence(n);
+                    }
+                    s
+                },
+            );
+        prop_oneof![1 => one_shot, 9 => recurring]
+    }
+
+    // A recurring schedule `to_cron` accepts: a minute, and an hour, a day of
+    // the month, a month or a weekday only where cron has room for them.
+    pub fn cron_schedule() -> impl Strategy<Value = Schedule> {
+        prop_oneof![
+            (0..60u8, option::of(1..=28u8)).prop_map(|(minute, day)| {
+                let s = Schedule::new().minute(minute);
+                match day {
+                    Some(d) => s.day(d).hourly(),
+                    None => s.hourly(),
+                }
+            }),
+            (0..60u8, 0..24u8, option::of(1..=12u8)).prop_map(|(minute, hour, month)| {
+                let s = Schedule::new().minute(minute).hour(hour);
+                match month {
+                    Some(m) => s.month(m).daily(),
+                    None => s.daily(),
+                }
+            }),
+            (0..60u8, 0..24u8, 1..=28u8).prop_map(|(minute, hour, day)| {
+                Schedule::new().minute(minute).hour(hour).day(day).monthly()
+            }),
+            (0..60u8, 0..24u8, option::of(1..=5u8), weekday()).prop_map(
+                |(minute, hour, n, day)| {
+                    Schedule::new()
+                        .minute(minute)
+                        .hour(hour)
+                        .every(FrequencyPattern::ByDay((n, day)))
+                }
+            ),
+        ]
+    }
+
+    pub fn valid_spec() -> impl Strategy<Value = ScheduleSpec> {
+        schedule().prop_map(|s| s.spec())
+    }
+
+    fn daily_at_nine() -> ScheduleSpec {
+        Schedule::new().at(9, 0).daily().spec()
+    }
+
+    // The spec of a valid schedule with one field pushed out of range: day
+    // 32, a day the month lacks, hour 24, minute 60, a 6th weekday, a zero
+    // stride or Feb 29 in a common year.
+    pub fn invalid_spec() -> impl Strategy<Value = ScheduleSpec> {
+        prop_oneof![
+            (32..=255u8).prop_map(|d| ScheduleSpec {
+                day: Some(d),
+                ..daily_at_nine()
+            }),
+            prop_oneof![
+                Just((Month::FEB, 30)),
+                Just((Month::APR, 31)),
+                Just((Month::NOV, 31)),
+            ]
+            .prop_map(|(m, d)| ScheduleSpec {
+                month: Some(m),
+                day: Some(d),
+                ..daily_at_nine()
+            }),
+            (24..=255u8).prop_map(|h| ScheduleSpec {
+                hour: Some(h),
+                ..daily_at_nine()
+            }),
+            (60..=255u8).prop_map(|m| ScheduleSpec {
+                minute: Some(m),
+                ..daily_at_nine()
+            }),
+            (6..=255u8, weekday()).prop_map(|(n, d)| ScheduleSpec {
+                frequency: Some(FrequencyPattern::ByDay((Some(n), d))),
+                ..daily_at_nine()
+            }),
+            Just(ScheduleSpec {
+                stride: Some(0),
+                ..daily_at_nine()
+            }),
+            (0..24i32, 0..3i32).prop_map(|(leap, after)| ScheduleSpec {
+                year: Some(2001 + 4 * leap + after),
+                month: Some(Month::FEB),
+                day: Some(29),
+                ..daily_at_nine()
+            }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::from_cron;
+
+    fn from() -> DateTime {
+        DateTime::new(2025, 1, 1, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn cron_round_trips() {
+        for seed in 0..500 {
+            let s = Gen::new(seed).cron_schedule();
+            let expr = to_cron(&s).unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+            let back = from_cron(&expr).unwrap();
+            assert!(
+                s.upcoming(from())
+                    .take(20)
+                    .eq(back.upcoming(from()).take(20)),
+                "seed {}: {}",
+                seed,
+                expr
+            );
+        }
+    }
+
+    #[test]
+    fn schedules_survive_the_engine() {
+        for seed in 0..300 {
+            let s = Gen::new(seed).schedule();
+            let spec = s.spec();
+            assert!(spec.day.is_none_or(|d| (1..=31).contains(&d)));
+            assert!(!s.to_string().is_empty());
+            for at in s.upcoming(from()).take(5) {
+                assert!(at >= from(), "seed {}: {}", seed, s);
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_replay() {
+        assert_eq!(Gen::new(7).schedule(), Gen::new(7).schedule());
+        assert_ne!(Gen::new(7).next(), Gen::new(8).next());
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn any_schedule_survives_the_engine(s in proptest::prelude::any::<Schedule>()) {
+            proptest::prop_assert!(!s.to_string().is_empty());
+            for at in s.upcoming(from()).take(5) {
+                proptest::prop_assert!(at >= from());
+            }
+        }
+
+        #[test]
+        fn cron_schedules_write_out(s in cron_schedule()) {
+            proptest::prop_assert!(to_cron(&s).is_ok());
+        }
+    }
+
+    #[cfg(all(feature = "proptest", feature = "serde"))]
+    proptest::proptest! {
+        #[test]
+        fn specs_deserialize_only_when_valid(valid in valid_spec(), invalid in invalid_spec()) {
+            let json = serde_json::to_string(&valid).unwrap();
+            proptest::prop_assert!(serde_json::from_str::<Schedule>(&json).is_ok());
+            let json = serde_json::to_string(&invalid).unwrap();
+            proptest::prop_assert!(serde_json::from_str::<Schedule>(&json).is_err());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn invalid_specs_do_not_deserialize() {
+        for seed in 0..100 {
+            let json = serde_json::to_string(&Gen::new(seed).invalid_spec()).unwrap();
+            assert!(
+                serde_json::from_str::<Schedule>(&json).is_err(),
+                "seed {}: {}",
+                seed,
+                json
+            );
+        }
+    }
+}
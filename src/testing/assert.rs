@@ -0,0 +1,208 @@
+// What the assertion macros check, as functions returning the failure
+// message, for test suites that want to report it their own way.
+//
+//     assert_fires_at!(schedule, "2025-09-20 22:00");
+//     assert_never_fires_on!(schedule, Days::SUN);
+//     assert_never_fires_on!(schedule, Month::DEC, from = "2025-01-01 00:00");
+//
+// Instants are DateTimes or strings in DateTime's Display format. Checks over
+// "never" walk the two years from `from`, or from now without it.
+use crate::scheduler::{Clock, SystemClock};
+use crate::time::{Date, DateTime};
+use crate::types::{Days, Month, Schedule};
+
+// How far `never_fires_on` looks ahead.
+const HORIZON_DAYS: i64 = 2 * 366;
+
+// An instant named in an assertion. Strings that don't parse fail the
+// assertion rather than panic on their own.
+pub trait Instant {
+    fn instant(&self) -> Result<DateTime, String>;
+}
+
+impl Instant for DateTime {
+    fn instant(&self) -> Result<DateTime, String> {
+        Ok(*self)
+    }
+}
+
+impl Instant for &str {
+    fn instant(&self) -> Result<DateTime, String> {
+        self.parse()
+            .map_err(|_| format!("{:?} is not a date-time", self))
+    }
+}
+
+impl Instant for String {
+    fn instant(&self) -> Result<DateTime, String> {
+        self.as_str().instant()
+    }
+}
+
+// Days a schedule can be asserted never to fire on.
+pub trait DayFilter: std::fmt::Debug {
+    fn matches(&self, date: Date) -> bool;
+}
+
+impl DayFilter for Days {
+    fn matches(&self, date: Date) -> bool {
+        date.weekday() == *self
+    }
+}
+
+impl DayFilter for Month {
+    fn matches(&self, date: Date) -> bool {
+        date.month == *self as u8 + 1
+    }
+}
+
+impl DayFilter for Date {
+    fn matches(&self, date: Date) -> bool {
+        date == *self
+    }
+}
+
+pub fn fires_at(schedule: &Schedule, at: impl Instant) -> Result<(), String> {
+    let at = at.instant()?;
+    match schedule.upcoming(at).next() {
+        Some(next) if next == at => Ok(()),
+        Some(next) => Err(format!(
+            "`{}` does not fire at {}; it next fires at {}",
+            schedule, at, next
+        )),
+        None => Err(format!(
+            "`{}` does not fire at {}, or ever after",
+            schedule, at
+        )),
+    }
+}
+
+pub fn does_not_fire_at(schedule: &Schedule, at: impl Instant) -> Result<(), String> {
+    let at = at.instant()?;
+    match schedule.upcoming(at).next() {
+        Some(next) if next == at => Err(format!("`{}` fires at {}", schedule, at)),
+        _ => Ok(()),
+    }
+}
+
+pub fn never_fires_on(
+    schedule: &Schedule,
+    day: impl DayFilter,
+    from: Option<&dyn Instant>,
+) -> Result<(), String> {
+    let from = match from {
+        Some(from) => from.instant()?,
+        None => SystemClock.now(),
+    };
+    let to = from.add_days(HORIZON_DAYS);
+    match schedule
+        .occurrences_between(from, to)
+        .find(|at| day.matches(at.date()))
+    {
+        Some(at) => Err(format!("`{}` fires on {:?} at {}", schedule, day, at)),
+        None => Ok(()),
+    }
+}
+
+#[macro_export]
+macro_rules! assert_fires_at {
+    ($schedule:expr, $at:expr $(,)?) => {
+        if let Err(e) = $crate::testing::assert::fires_at(&$schedule, $at) {
+            panic!("{}", e);
+        }
+    };
+    ($schedule:expr, $at:expr, $($arg:tt)+) => {
+        if let Err(e) = $crate::testing::assert::fires_at(&$schedule, $at) {
+            panic!("{}: {}", format_args!($($arg)+), e);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! assert_not_fires_at {
+    ($schedule:expr, $at:expr $(,)?) => {
+        if let Err(e) = $crate::testing::assert::does_not_fire_at(&$schedule, $at) {
+            panic!("{}", e);
+        }
+    };
+    ($schedule:expr, $at:expr, $($arg:tt)+) => {
+        if let Err(e) = $crate::testing::assert::does_not_fire_at(&$schedule, $at) {
+            panic!("{}: {}", format_args!($($arg)+), e);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! assert_never_fires_on {
+    ($schedule:expr, $day:expr, from = $from:expr $(,)?) => {
+        if let Err(e) =
+            $crate::testing::assert::never_fires_on(&$schedule, $day, Some(&$from))
+        {
+            panic!("{}", e);
+        }
+    };
+    ($schedule:expr, $day:expr $(,)?) => {
+        if let Err(e) = $crate::testing::assert::never_fires_on(&$schedule, $day, None) {
+            panic!("{}", e);
+        }
+    };
+    ($schedule:expr, $day:expr, $($arg:tt)+) => {
+        if let Err(e) = $crate::testing::assert::never_fires_on(&$schedule, $day, None) {
+            panic!("{}: {}", format_args!($($arg)+), e);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Except;
+
+    fn saturdays() -> Schedule {
+        Schedule::new()
+            .every_nth_day(3, Days::SAT)
+            .at(22, 0)
+            .except_on_month(Month::DEC)
+    }
+
+    #[test]
+    fn macros_pass() {
+        assert_fires_at!(saturdays(), "2025-09-20 22:00");
+        assert_fires_at!(saturdays(), DateTime::new(2025, 10, 18, 22, 0).unwrap());
+        assert_not_fires_at!(saturdays(), "2025-12-20 22:00");
+        assert_never_fires_on!(saturdays(), Days::SUN);
+        assert_never_fires_on!(saturdays(), Month::DEC, from = "2025-01-01 00:00");
+        let weekdays = Schedule::new().daily().except(Except::Day(Days::SUN));
+        assert_never_fires_on!(weekdays, Days::SUN, "weekends are off");
+    }
+
+    #[test]
+    fn failures_explain_themselves() {
+        assert_eq!(
+            fires_at(&saturdays(), "2025-09-21 22:00"),
+            Err(
+                "`every 3rd Saturday of the month at 22:00 except in December` does not fire \
+                 at 2025-09-21 22:00; it next fires at 2025-10-18 22:00"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            fires_at(&saturdays(), "Sept 20"),
+            Err("\"Sept 20\" is not a date-time".to_string())
+        );
+        let from = "2025-01-01 00:00";
+        assert!(
+            never_fires_on(&saturdays(), Days::SAT, Some(&from))
+                .unwrap_err()
+                .ends_with("fires on SAT at 2025-01-18 22:00")
+        );
+        let third_in_december = Date::new(2025, 12, 20).unwrap();
+        assert!(never_fires_on(&saturdays(), third_in_december, Some(&from)).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fire at 2025-09-21 22:00")]
+    fn macro_panics_with_the_reason() {
+        assert_fires_at!(saturdays(), "2025-09-21 22:00");
+    }
+}
@@ -0,0 +1,188 @@
+// A Runtime without the clock: nothing happens until `tick(now)` says what
+// time it is, tasks run on the calling thread in a fixed order, and nothing
+// reads the wall clock, so two runs of a test see exactly the same thing.
+// Tasks need not be Send, so they can share an Rc<RefCell<_>> with the test.
+//
+// Fires due at the same minute run in the order their jobs were added.
+use std::collections::HashMap;
+
+use crate::scheduler::{
+    CancellationToken, IntoOutcome, JobContext, JobError, JobId, Outcome, Scheduler,
+    SchedulerConfig, catch_panic,
+};
+use crate::time::DateTime;
+use crate::types::Schedule;
+
+type LocalTask = Box<dyn FnMut(&JobContext) -> Outcome>;
+
+// One task run by a tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub name: String,
+    pub at: DateTime,
+    pub outcome: Outcome,
+}
+
+pub struct ManualExecutor {
+    scheduler: Scheduler,
+    tasks: HashMap<String, (LocalTask, CancellationToken)>,
+    // The latest tick, or the start before the first; jobs are added as of it.
+    now: DateTime,
+}
+
+impl ManualExecutor {
+    pub fn new(start: DateTime) -> Self {
+        Self::with_config(start, SchedulerConfig::default())
+    }
+
+    pub fn with_config(start: DateTime, config: SchedulerConfig) -> Self {
+        Self {
+            scheduler: Scheduler::with_config(config),
+            tasks: HashMap::new(),
+            now: start,
+        }
+    }
+
+    pub fn now(&self) -> DateTime {
+        self.now
+    }
+
+    pub fn add<R: IntoOutcome>(
+        &mut self,
+        id: impl Into<JobId>,
+        schedule: Schedule,
+        mut task: impl FnMut(&JobContext) -> R + 'static,
+    ) -> Result<JobId, JobError> {
+        let id = id.into();
+        if self.tasks.contains_key(id.as_str()) {
+            return Err(JobError::AlreadyExists(id));
+        }
+        self.scheduler.add(id.to_string(), schedule, self.now);
+        let task: LocalTask = Box::new(move |ctx| task(ctx).into_outcome());
+        self.tasks
+            .insert(id.to_string(), (task, CancellationToken::new()));
+        Ok(id)
+    }
+
+    // Its token is cancelled, for a task that kept hold of it.
+    pub fn remove(&mut self, name: &str) -> Option<Schedule> {
+        let (_, token) = self.tasks.remove(name)?;
+        token.cancel();
+        self.scheduler.remove(name)
+    }
+
+    pub fn pause(&mut self, name: &str) -> bool {
+        self.scheduler.pause(name)
+    }
+
+    pub fn resume(&mut self, name: &str) -> bool {
+        self.scheduler.resume(name, self.now)
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    pub fn next_wakeup(&mut self) -> Option<DateTime> {
+        self.scheduler.next_wakeup()
+    }
+
+    // Runs everything due by `now`, oldest first, and reports each run. A
+    // `now` before the previous tick runs nothing.
+    pub fn tick(&mut self, now: DateTime) -> Vec<Run> {
+        let due = self.scheduler.tick(now);
+        self.now = self.now.max(now);
+        let mut runs = Vec::with_capacity(due.len());
+        for job in due {
+            let Some((task, token)) = self.tasks.get_mut(&job.name) else {
+                continue;
+            };
+            let ctx = JobContext {
+                name: job.name,
+                at: job.at,
+                cancel: token.clone(),
+            };
+            let outcome = catch_panic(|| task(&ctx));
+            runs.push(Run {
+                name: ctx.name,
+                at: ctx.at,
+                outcome,
+            });
+        }
+        let done: Vec<String> = self
+            .tasks
+            .keys()
+            .filter(|name| self.scheduler.get(name).is_none())
+            .cloned()
+            .collect();
+        for name in done {
+            self.tasks.remove(&name);
+        }
+        runs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn ticks_run_what_is_due_in_order() {
+        let mut ex = ManualExecutor::new(dt(2025, 1, 1, 0, 0));
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for name in ["b", "a"] {
+            let log = Rc::clone(&log);
+            ex.add(name, Schedule::new().at(9, 0).daily(), move |ctx| {
+                log.borrow_mut().push(format!("{} {}", ctx.name, ctx.at))
+            })
+            .unwrap();
+        }
+        ex.add(
+            "once",
+            Schedule::after(std::time::Duration::from_secs(3600)),
+            |_| Err::<(), _>("no"),
+        )
+        .unwrap();
+
+        assert!(ex.tick(dt(2025, 1, 1, 0, 59)).is_empty());
+        let runs = ex.tick(dt(2025, 1, 2, 9, 0));
+        assert_eq!(
+            runs.iter()
+                .map(|r| (r.name.as_str(), r.at))
+                .collect::<Vec<_>>(),
+            [
+                ("once", dt(2025, 1, 1, 1, 0)),
+                ("b", dt(2025, 1, 1, 9, 0)),
+                ("a", dt(2025, 1, 1, 9, 0)),
+                ("b", dt(2025, 1, 2, 9, 0)),
+                ("a", dt(2025, 1, 2, 9, 0)),
+            ]
+        );
+        assert_eq!(runs[0].outcome, Outcome::Failed("no".to_string()));
+        assert_eq!(log.borrow().len(), 4);
+        // The one-shot is done and its name free again.
+        assert_eq!(ex.scheduler().len(), 2);
+        assert!(ex.add("once", Schedule::new().daily(), |_| ()).is_ok());
+    }
+
+    #[test]
+    fn paused_jobs_sit_out() {
+        let mut ex = ManualExecutor::new(dt(2025, 1, 1, 0, 0));
+        ex.add("tick", Schedule::new().minute(0).hourly(), |_| ())
+            .unwrap();
+        assert!(ex.pause("tick"));
+        assert!(ex.tick(dt(2025, 1, 1, 5, 0)).is_empty());
+        assert!(ex.resume("tick"));
+        // Picks up from the last tick, not from where it was paused.
+        assert_eq!(ex.next_wakeup(), Some(dt(2025, 1, 1, 5, 0)));
+        assert_eq!(ex.tick(dt(2025, 1, 1, 7, 0)).len(), 3);
+        assert!(ex.remove("tick").is_some());
+        assert_eq!(ex.next_wakeup(), None);
+    }
+}
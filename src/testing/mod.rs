@@ -0,0 +1,191 @@
+// Time travel for testing schedule logic: a Runtime on a ManualClock that
+// jumps ahead instantly, stopping at each fire on the way so tasks see the
+// time they were due, and a log of what ran when.
+//
+//     let mut tm = TimeMachine::new(DateTime::new(2025, 1, 1, 0, 0).unwrap());
+//     tm.add("report", Schedule::new().at(9, 0).daily(), |_| ()).unwrap();
+//     tm.advance(Span::days(30));
+//     assert_eq!(tm.fired_for("report").len(), 30);
+//
+// `arbitrary` generates schedules for property tests, `assert` backs
+// assert_fires_at! and friends, and `executor` is a clockless, single
+// threaded runtime driven by explicit ticks.
+pub mod arbitrary;
+pub mod assert;
+pub mod executor;
+
+use std::ops::{Deref, DerefMut};
+use std::sync::mpsc::Receiver;
+
+use crate::calendar::Span;
+use crate::scheduler::{Clock, Event, ManualClock, Runtime, SchedulerConfig};
+use crate::time::DateTime;
+
+// One run of a job, as the TimeMachine saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fire {
+    pub name: String,
+    // When it was due.
+    pub at: DateTime,
+    // The clock's now when it ran: `at` unless the clock was set past it.
+    pub ran_at: DateTime,
+    // Why it failed or what it panicked with.
+    pub error: Option<String>,
+}
+
+// Derefs to its Runtime, so jobs are added, paused and inspected as usual.
+pub struct TimeMachine {
+    runtime: Runtime,
+    clock: ManualClock,
+    events: Receiver<Event>,
+    fired: Vec<Fire>,
+}
+
+impl TimeMachine {
+    pub fn new(start: DateTime) -> Self {
+        Self::with_config(start, SchedulerConfig::default())
+    }
+
+    pub fn with_config(start: DateTime, config: SchedulerConfig) -> Self {
+        let clock = ManualClock::new(start);
+        let mut runtime = Runtime::with_config(clock.clone(), config);
+        let events = runtime.subscribe();
+        Self {
+            runtime,
+            clock,
+            events,
+            fired: Vec::new(),
+        }
+    }
+
+    pub fn now(&self) -> DateTime {
+        self.clock.now()
+    }
+
+    // Shares its time with the runtime, for tasks that need to ask.
+    pub fn clock(&self) -> ManualClock {
+        self.clock.clone()
+    }
+
+    // Moves the clock on by `span`, calendar-aware ("1 month" from Jan 31 is
+    // Feb 28), running everything due on the way. Returns how many ran.
+    pub fn advance(&mut self, span: Span) -> usize {
+        self.advance_to(self.now() + span)
+    }
+
+    // Runs each fire up to and including `to` at its own time, then leaves
+    // the clock at `to`. A `to` in the past only runs what is already due.
+    pub fn advance_to(&mut self, to: DateTime) -> usize {
+        let before = self.fired.len();
+        while let Some(at) = self.runtime.next_wakeup().filter(|at| *at <= to) {
+            self.clock.set(at.max(self.now()));
+            self.run_pending();
+            if self.runtime.next_wakeup() == Some(at) {
+                break;
+            }
+        }
+        self.clock.set(to.max(self.now()));
+        self.run_pending();
+        self.fired.len() - before
+    }
+
+    // Every run so far, oldest first.
+    pub fn fired(&self) -> &[Fire] {
+        &self.fired
+    }
+
+    // When `name` was due each time it ran.
+    pub fn fired_for(&self, name: &str) -> Vec<DateTime> {
+        self.fired
+            .iter()
+            .filter(|fire| fire.name == name)
+            .map(|fire| fire.at)
+            .collect()
+    }
+
+    // Hands back the log and starts a new one.
+    pub fn take_fired(&mut self) -> Vec<Fire> {
+        std::mem::take(&mut self.fired)
+    }
+
+    fn run_pending(&mut self) {
+        self.runtime.run_pending();
+        let ran_at = self.now();
+        for event in self.events.try_iter() {
+            let (name, at, error) = match event {
+                Event::JobFinished { name, at } => (name, at, None),
+                Event::JobFailed { name, at, error } => (name, at, Some(error)),
+                _ => continue,
+            };
+            self.fired.push(Fire {
+                name,
+                at,
+                ran_at,
+                error,
+            });
+        }
+    }
+}
+
+impl Deref for TimeMachine {
+    type Target = Runtime;
+
+    fn deref(&self) -> &Runtime {
+        &self.runtime
+    }
+}
+
+impl DerefMut for TimeMachine {
+    fn deref_mut(&mut self) -> &mut Runtime {
+        &mut self.runtime
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Days, Schedule};
+
+    fn dt(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> DateTime {
+        DateTime::new(year, month, day, hour, minute).unwrap()
+    }
+
+    #[test]
+    fn a_month_passes_instantly() {
+        let mut tm = TimeMachine::new(dt(2025, 1, 1, 0, 0));
+        let clock = tm.clock();
+        tm.add("report", Schedule::new().at(9, 0).daily(), move |ctx| {
+            assert_eq!(clock.now(), ctx.at)
+        })
+        .unwrap();
+        tm.add(
+            "backup",
+            Schedule::new().every_on_day(Days::SAT).at(2, 0),
+            |_| Err::<(), _>("disk full"),
+        )
+        .unwrap();
+
+        assert_eq!(tm.advance(Span::days(30)), 30 + 4);
+        assert_eq!(tm.now(), dt(2025, 1, 31, 0, 0));
+        assert_eq!(tm.fired_for("report").len(), 30);
+        assert_eq!(tm.fired_for("report")[29], dt(2025, 1, 30, 9, 0));
+        let backups: Vec<_> = tm.fired().iter().filter(|f| f.name == "backup").collect();
+        assert_eq!(backups[0].at, dt(2025, 1, 4, 2, 0));
+        assert_eq!(backups[0].error.as_deref(), Some("disk full"));
+        assert!(tm.fired().iter().all(|f| f.at == f.ran_at));
+    }
+
+    #[test]
+    fn advancing_stops_at_the_target() {
+        let mut tm = TimeMachine::new(dt(2025, 1, 31, 12, 0));
+        tm.add("tick", Schedule::new().minute(0).hourly(), |_| ())
+            .unwrap();
+
+        // Both ends included: 12:00 on Jan 31 through 12:00 on Feb 28.
+        assert_eq!(tm.advance(Span::months(1)), 28 * 24 + 1);
+        assert_eq!(tm.now(), dt(2025, 2, 28, 12, 0));
+        assert_eq!(tm.take_fired().len(), 28 * 24 + 1);
+        assert_eq!(tm.advance_to(dt(2025, 2, 28, 12, 0)), 0);
+        assert!(tm.fired().is_empty());
+    }
+}
@@ -1,3 +1,8 @@
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+use crate::types::Days;
+
 pub fn is_valid_day_for_month(month: u8, day: u8) -> bool {
     match month + 1 {
         1 | 3 | 5 | 7 | 8 | 10 | 12 => day <= 31,
@@ -6,3 +11,467 @@ pub fn is_valid_day_for_month(month: u8, day: u8) -> bool {
         _ => false,
     }
 }
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+// Feb 29 pinned to a year without one. month is 0-based, as in
+// is_valid_day_for_month.
+pub fn is_leap_day_outside_leap_year(year: i32, month: u8, day: u8) -> bool {
+    month == 1 && day == 29 && !is_leap_year(year)
+}
+
+// Day `n` of `year`, counting Jan 1 as day 1.
+pub fn is_valid_day_of_year(year: i32, n: u16) -> bool {
+    (1..=365).contains(&n) || (n == 366 && is_leap_year(year))
+}
+
+// month is 1-based here, unlike is_valid_day_for_month.
+pub fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+// Sakamoto's method, 0 = Sunday to match the order of Days. Month 0 and 13
+// read as December before and January after, so a hand-built Date out of
+// range gets an answer rather than a panic.
+pub fn weekday(year: i32, month: u8, day: u8) -> Days {
+    const T: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = if month < 3 { year - 1 } else { year };
+    let n = (y + y.div_euclid(4) - y.div_euclid(100)
+        + y.div_euclid(400)
+        + T[(month as usize + 11) % 12]
+        + day as i32)
+        .rem_euclid(7);
+    match n {
+        0 => Days::SUN,
+        1 => Days::MON,
+        2 => Days::TUE,
+        3 => Days::WED,
+        4 => Days::THUR,
+        5 => Days::FRI,
+        _ => Days::SAT,
+    }
+}
+
+// Days since 1970-01-01 for a proleptic Gregorian date, and back.
+// (Howard Hinnant's days_from_civil / civil_from_days.)
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year } as i64;
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+    (year, month, day)
+}
+
+// A calendar day, valid by construction through `new`, and checked the same
+// way when deserialized. Fields are ordered so the derived Ord is
+// chronological.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Date> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    pub fn weekday(&self) -> Days {
+        weekday(self.year, self.month, self.day)
+    }
+
+    pub fn add_days(&self, days: i64) -> Date {
+        let (year, month, day) = civil_from_days(self.days_since_epoch() + days);
+        Date { year, month, day }
+    }
+
+    // Clamped to the end of a shorter month: Jan 31 plus one month is Feb 28,
+    // or Feb 29 in a leap year.
+    pub fn add_months(&self, months: i64) -> Date {
+        let total = self.year as i64 * 12 + self.month as i64 - 1 + months;
+        let (year, month) = (total.div_euclid(12) as i32, total.rem_euclid(12) as u8 + 1);
+        Date {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    // Day of the year, 1 for Jan 1 up to 365, or 366 in a leap year.
+    pub fn ordinal(&self) -> u16 {
+        Date {
+            month: 1,
+            day: 1,
+            ..*self
+        }
+        .days_until(*self) as u16
+            + 1
+    }
+
+    // Negative when `other` comes first.
+    pub fn days_until(&self, other: Date) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    pub fn at(&self, time: Time) -> DateTime {
+        DateTime {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+            hour: time.hour,
+            minute: time.minute,
+        }
+    }
+
+    fn days_since_epoch(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+}
+
+// A time of day with minute resolution, valid by construction through `new`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl Time {
+    pub const MIDNIGHT: Time = Time { hour: 0, minute: 0 };
+
+    pub fn new(hour: u8, minute: u8) -> Option<Time> {
+        (hour < 24 && minute < 60).then_some(Time { hour, minute })
+    }
+
+    pub fn minutes_since_midnight(&self) -> u16 {
+        self.hour as u16 * 60 + self.minute as u16
+    }
+
+    // Wraps around midnight.
+    pub fn add_minutes(&self, minutes: i64) -> Time {
+        let of_day = (self.minutes_since_midnight() as i64 + minutes).rem_euclid(1440);
+        Time {
+            hour: (of_day / 60) as u8,
+            minute: (of_day % 60) as u8,
+        }
+    }
+}
+
+// A wall-clock instant with minute resolution, the unit occurrences are
+// reported in. Fields are ordered so the derived Ord is chronological.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl DateTime {
+    pub fn new(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> Option<DateTime> {
+        Some(Date::new(year, month, day)?.at(Time::new(hour, minute)?))
+    }
+
+    pub fn date(&self) -> Date {
+        Date {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+        }
+    }
+
+    pub fn time(&self) -> Time {
+        Time {
+            hour: self.hour,
+            minute: self.minute,
+        }
+    }
+
+    pub fn weekday(&self) -> Days {
+        weekday(self.year, self.month, self.day)
+    }
+
+    // Midnight of the following day.
+    pub fn next_day(&self) -> DateTime {
+        let (mut year, mut month, mut day) = (self.year, self.month, self.day + 1);
+        if day > days_in_month(year, month) {
+            day = 1;
+            month += 1;
+            if month > 12 {
+                month = 1;
+                year += 1;
+            }
+        }
+        DateTime {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+        }
+    }
+
+    pub fn add_days(&self, days: i64) -> DateTime {
+        self.date().add_days(days).at(self.time())
+    }
+
+    // Keeps the time of day; the day is clamped as in Date::add_months.
+    pub fn add_months(&self, months: i64) -> DateTime {
+        self.date().add_months(months).at(self.time())
+    }
+
+    pub fn add_minutes(&self, minutes: i64) -> DateTime {
+        let total = self.minutes_since_epoch() + minutes;
+        let (year, month, day) = civil_from_days(total.div_euclid(1440));
+        let of_day = total.rem_euclid(1440);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (of_day / 60) as u8,
+            minute: (of_day % 60) as u8,
+        }
+    }
+
+    pub(crate) fn minutes_since_epoch(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * 1440
+            + self.hour as i64 * 60
+            + self.minute as i64
+    }
+
+    // UTC wall-clock for seconds since the Unix epoch, seconds dropped.
+    pub fn from_unix(seconds: i64) -> DateTime {
+        DateTime {
+            year: 1970,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+        }
+        .add_minutes(seconds.div_euclid(60))
+    }
+
+    // Seconds since the Unix epoch, reading this as UTC.
+    pub fn unix(&self) -> i64 {
+        self.minutes_since_epoch() * 60
+    }
+
+    pub fn next_minute(&self) -> DateTime {
+        if self.minute < 59 {
+            DateTime {
+                minute: self.minute + 1,
+                ..*self
+            }
+        } else if self.hour < 23 {
+            DateTime {
+                hour: self.hour + 1,
+                minute: 0,
+                ..*self
+            }
+        } else {
+            self.next_day()
+        }
+    }
+}
+
+// "2025-09-20 22:00"
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+// "2025-09-20"
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// "22:00"
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+// The inverses of Display: "2025-09-20 22:00" (or with a `T`), "2025-09-20"
+// and "22:00". A year before 1 AD takes a leading minus.
+impl FromStr for DateTime {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<DateTime, ParseError> {
+        let (date, time) = s
+            .trim()
+            .split_once([' ', 'T'])
+            .ok_or_else(|| ParseError::new(format!("invalid date-time {:?}", s)))?;
+        Ok(date.parse::<Date>()?.at(time.parse()?))
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Date, ParseError> {
+        let mut parts = s.trim().rsplitn(3, '-');
+        let (day, month, year) = (parts.next(), parts.next(), parts.next());
+        year.zip(month)
+            .zip(day)
+            .and_then(|((y, m), d)| Date::new(y.parse().ok()?, m.parse().ok()?, d.parse().ok()?))
+            .ok_or_else(|| ParseError::new(format!("invalid date {:?}", s)))
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Time, ParseError> {
+        s.trim()
+            .split_once(':')
+            .and_then(|(h, m)| Time::new(h.parse().ok()?, m.parse().ok()?))
+            .ok_or_else(|| ParseError::new(format!("invalid time {:?}", s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_and_times() {
+        assert_eq!(Date::new(2025, 2, 29), None);
+        assert_eq!(Time::new(24, 0), None);
+
+        let date = Date::new(2024, 2, 28).unwrap();
+        assert_eq!(date.add_days(2), Date::new(2024, 3, 1).unwrap());
+        assert_eq!(date.days_until(Date::new(2025, 2, 28).unwrap()), 366);
+        assert!(date < date.add_days(1));
+
+        let late = Time::new(23, 45).unwrap();
+        assert_eq!(late.add_minutes(30), Time::new(0, 15).unwrap());
+        assert!(Time::MIDNIGHT < late);
+
+        let at = date.at(late);
+        assert_eq!((at.date(), at.time()), (date, late));
+        assert_eq!(at.to_string(), format!("{} {}", date, late));
+    }
+
+    #[test]
+    fn ordinal_days() {
+        assert_eq!(Date::new(2025, 1, 1).unwrap().ordinal(), 1);
+        assert_eq!(Date::new(2025, 3, 1).unwrap().ordinal(), 60);
+        assert_eq!(Date::new(2024, 3, 1).unwrap().ordinal(), 61);
+        assert_eq!(Date::new(2024, 12, 31).unwrap().ordinal(), 366);
+        assert!(is_valid_day_of_year(2024, 366));
+        assert!(!is_valid_day_of_year(2025, 366));
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(is_leap_year(2024));
+        assert!(is_leap_year(2000));
+        assert!(!is_leap_year(1900));
+        assert_eq!(days_in_month(2025, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+    }
+
+    #[test]
+    fn weekday_of_known_dates() {
+        assert_eq!(weekday(2025, 9, 20), Days::SAT);
+        assert_eq!(weekday(2000, 1, 1), Days::SAT);
+        assert_eq!(weekday(2024, 2, 29), Days::THUR);
+        // Out-of-range months roll over instead of panicking.
+        assert_eq!(weekday(2025, 13, 1), weekday(2026, 1, 1));
+        assert_eq!(weekday(2025, 0, 31), weekday(2024, 12, 31));
+    }
+
+    #[test]
+    fn datetime_rejects_invalid() {
+        assert!(DateTime::new(2025, 2, 29, 0, 0).is_none());
+        assert!(DateTime::new(2025, 13, 1, 0, 0).is_none());
+        assert!(DateTime::new(2025, 1, 1, 24, 0).is_none());
+    }
+
+    #[test]
+    fn add_minutes_across_leap_day() {
+        let dt = DateTime::new(2024, 2, 28, 23, 30).unwrap();
+        assert_eq!(
+            dt.add_minutes(60),
+            DateTime::new(2024, 2, 29, 0, 30).unwrap()
+        );
+        assert_eq!(
+            dt.add_minutes(-24 * 60 * 59),
+            DateTime::new(2023, 12, 31, 23, 30).unwrap()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let dt = DateTime::new(2025, 9, 2, 7, 5).unwrap();
+        assert_eq!(dt.to_string(), "2025-09-02 07:05");
+    }
+
+    #[test]
+    fn parse_display_round_trips() {
+        let dt = DateTime::new(2025, 9, 20, 22, 0).unwrap();
+        assert_eq!(dt.to_string().parse(), Ok(dt));
+        assert_eq!("2025-09-20T22:00".parse(), Ok(dt));
+        let ides = DateTime::new(-44, 3, 15, 12, 0).unwrap();
+        assert_eq!(ides.to_string().parse(), Ok(ides));
+        assert!("2025-02-29".parse::<Date>().is_err());
+        assert!("2025-09-20 24:00".parse::<DateTime>().is_err());
+        assert!("2025-09".parse::<Date>().is_err());
+        assert!("22".parse::<Time>().is_err());
+    }
+
+    #[test]
+    fn unix_round_trip() {
+        let dt = DateTime::new(2025, 9, 20, 22, 0).unwrap();
+        assert_eq!(dt.unix(), 1_758_405_600);
+        assert_eq!(DateTime::from_unix(1_758_405_659), dt);
+        assert_eq!(
+            DateTime::from_unix(-1),
+            DateTime::new(1969, 12, 31, 23, 59).unwrap()
+        );
+    }
+
+    #[test]
+    fn next_minute_rolls_over_year() {
+        let dt = DateTime::new(2025, 12, 31, 23, 59).unwrap();
+        assert_eq!(dt.next_minute(), DateTime::new(2026, 1, 1, 0, 0).unwrap());
+    }
+}
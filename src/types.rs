@@ -1,12 +1,25 @@
-use crate::time::is_valid_day_for_month;
+use std::time::Duration;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg(feature = "astro")]
+pub use crate::astro::{Location, Solar, SolarEvent};
+pub use crate::business::Holidays;
+#[cfg(feature = "i18n")]
+pub use crate::describe::{Locale, Simulation};
+pub use crate::fiscal::{FiscalPeriod, FiscalYear};
+#[cfg(feature = "panchang")]
+pub use crate::panchang::{Lunar, LunarEvent};
+pub use crate::time::{Date, DateTime, Time};
+use crate::time::{is_leap_day_outside_leap_year, is_valid_day_for_month, is_valid_day_of_year};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum FrequencyPattern {
     Frequency(Frequency),
     ByDay((Option<u8>, Days)),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Frequency {
     Hourly,
     Daily,
@@ -14,7 +27,8 @@ pub enum Frequency {
     Monthly,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Days {
     SUN,
     MON,
@@ -25,7 +39,8 @@ pub enum Days {
     SAT,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Month {
     JAN,
     FEB,
@@ -61,7 +76,8 @@ impl Month {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Except {
     Day(Days),
     N(u8),
@@ -69,57 +85,357 @@ pub enum Except {
     Month(Month),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Time {
-    pub hour: u8,
-    pub minute: u8,
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Recurring {
+    pub(crate) frequency: Option<FrequencyPattern>,
+    pub(crate) except: Option<Except>,
 }
 
-#[derive(Debug)]
-pub struct Recurring {
-    frequency: Option<FrequencyPattern>,
-    except: Option<Except>,
+// When a schedule stops firing. With both a count and a date, whichever is
+// reached first ends it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Repeat {
+    #[default]
+    Forever,
+    Count(u32),
+    UntilDate(EndDate),
+    CountOrUntil {
+        count: u32,
+        until: EndDate,
+    },
+}
+
+// Where a Feb 29 schedule fires in years without a Feb 29.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum LeapDay {
+    #[default]
+    Skip,
+    Feb28,
+    Mar1,
+}
+
+// Odd or even ISO week numbers, for schedules on every other week. Years
+// with a week 53 have two odd weeks in a row at the turn of the year.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Parity {
+    Odd,
+    Even,
+}
+
+impl Parity {
+    pub(crate) fn of(week: u8) -> Parity {
+        if week.is_multiple_of(2) {
+            Parity::Even
+        } else {
+            Parity::Odd
+        }
+    }
+}
+
+// A set of weekdays, e.g. Mondays and Wednesdays. Empty means unset.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "Vec<Days>", from = "Vec<Days>"))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Weekdays(u8);
+
+const WEEK: [Days; 7] = [
+    Days::SUN,
+    Days::MON,
+    Days::TUE,
+    Days::WED,
+    Days::THUR,
+    Days::FRI,
+    Days::SAT,
+];
+
+impl Weekdays {
+    pub fn new(days: &[Days]) -> Self {
+        Weekdays(days.iter().fold(0, |set, d| set | 1 << *d as u8))
+    }
+
+    pub fn contains(&self, d: Days) -> bool {
+        self.0 & 1 << d as u8 != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // Sunday first.
+    pub fn iter(&self) -> impl Iterator<Item = Days> + '_ {
+        WEEK.into_iter().filter(|d| self.contains(*d))
+    }
+}
+
+impl From<Vec<Days>> for Weekdays {
+    fn from(days: Vec<Days>) -> Self {
+        Weekdays::new(&days)
+    }
+}
+
+impl From<Weekdays> for Vec<Days> {
+    fn from(days: Weekdays) -> Self {
+        days.iter().collect()
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct Until {
-    pub total: u8,
+// The last instant a schedule may fire at. Without a year it is the first
+// match at or after the schedule starts; a missing time means the end of that
+// day, a missing day the end of the month, a missing month (with a year) the
+// end of that year. Like the pattern, it is read in the zone of the clock the
+// schedule is run against.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EndDate {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub year: Option<i32>,
     pub day: Option<u8>,
     pub month: Option<Month>,
-    pub hr: Option<u8>,
+    pub hour: Option<u8>,
     pub minute: Option<u8>,
 }
 
-#[derive(Debug)]
+impl Repeat {
+    pub fn times(n: u32) -> Self {
+        Repeat::Count(n)
+    }
+
+    pub fn until(date: EndDate) -> Self {
+        Repeat::UntilDate(date)
+    }
+
+    // Adds an end date, keeping any count.
+    pub fn or_until(self, date: EndDate) -> Self {
+        match self.count() {
+            Some(count) => Repeat::CountOrUntil { count, until: date },
+            None => Repeat::UntilDate(date),
+        }
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        match *self {
+            Repeat::Count(n) | Repeat::CountOrUntil { count: n, .. } => Some(n),
+            Repeat::Forever | Repeat::UntilDate(_) => None,
+        }
+    }
+
+    pub fn end_date(&self) -> Option<EndDate> {
+        match *self {
+            Repeat::UntilDate(d) | Repeat::CountOrUntil { until: d, .. } => Some(d),
+            Repeat::Forever | Repeat::Count(_) => None,
+        }
+    }
+
+    pub fn is_forever(&self) -> bool {
+        *self == Repeat::Forever
+    }
+
+    pub(crate) fn with_count(self, n: u32) -> Self {
+        match self.end_date() {
+            Some(until) => Repeat::CountOrUntil { count: n, until },
+            None => Repeat::Count(n),
+        }
+    }
+}
+
+impl EndDate {
+    pub fn on(day: u8, month: Month) -> Self {
+        EndDate {
+            day: Some(day),
+            month: Some(month),
+            ..EndDate::default()
+        }
+    }
+
+    pub fn at(self, hour: u8, minute: u8) -> Self {
+        EndDate {
+            hour: Some(hour),
+            minute: Some(minute),
+            ..self
+        }
+    }
+
+    pub fn in_year(self, year: i32) -> Self {
+        EndDate {
+            year: Some(year),
+            ..self
+        }
+    }
+}
+
+// Equality and hashing are field-wise: two schedules built through different
+// call orders compare equal when they end up configured the same.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Schedule {
-    recurring: Recurring,
-    year: Option<u16>,
-    day: Option<u8>,
-    month: Option<Month>,
-    hour: Option<u8>,
-    minute: Option<u8>,
-    repeat: Option<Until>,
-    range: Option<(Time, Time)>,
+    pub(crate) recurring: Recurring,
+    pub(crate) year: Option<i32>,
+    // Several years, in place of one. Sorted.
+    pub(crate) years: Vec<i32>,
+    pub(crate) day: Option<u8>,
+    pub(crate) month: Option<Month>,
+    // Every this many months counting from a start month, in place of a
+    // month. A day past the end of a shorter month clamps to its last day.
+    pub(crate) month_step: Option<(u8, Month)>,
+    // Day of the year, in place of a day and month.
+    pub(crate) day_of_year: Option<u16>,
+    // Several dates every year, likewise in place of a day and month.
+    // Sorted.
+    pub(crate) dates: Vec<(Month, u8)>,
+    // A day of the fiscal year, likewise in place of a day and month.
+    pub(crate) fiscal: Option<FiscalPeriod>,
+    pub(crate) fiscal_year: FiscalYear,
+    // Business days the matching days are moved by, once rolled onto one.
+    pub(crate) business_days: Option<i8>,
+    // Days off besides weekends when counting business days.
+    pub(crate) holidays: Holidays,
+    // A lunar day, in place of a day of the month.
+    #[cfg(feature = "panchang")]
+    pub(crate) lunar: Option<Lunar>,
+    pub(crate) hour: Option<u8>,
+    pub(crate) minute: Option<u8>,
+    // Every this many hours from midnight, or minutes from the hour, in
+    // place of an hour or minute: cron's `*/n`.
+    pub(crate) hour_step: Option<u8>,
+    pub(crate) minute_step: Option<u8>,
+    // Follows the sun in place of an hour and minute.
+    #[cfg(feature = "astro")]
+    pub(crate) solar: Option<Solar>,
+    // Several times of day, likewise in place of an hour and minute. Sorted.
+    pub(crate) times: Vec<Time>,
+    // Every this many minutes from the start of the window, or midnight,
+    // again in place of an hour and minute.
+    pub(crate) interval: Option<u16>,
+    // The days a weekly schedule fires on, in place of Sunday.
+    pub(crate) weekdays: Weekdays,
+    // Only fires in ISO weeks of this parity.
+    pub(crate) week_parity: Option<Parity>,
+    pub(crate) repeat: Repeat,
+    pub(crate) range: Option<(Time, Time)>,
+    // Minutes every occurrence is shifted by after matching.
+    pub(crate) offset: Option<u32>,
+    // Relative one-shot: fires this many minutes after the instant it is
+    // resolved against, instead of at a wall-clock date.
+    pub(crate) delay: Option<u32>,
+    // Also fire once at the starting instant, ahead of the pattern.
+    pub(crate) immediate: bool,
+    // Fire on every this many matches, starting with the first.
+    pub(crate) stride: Option<u32>,
+    // Nothing fires before this, whenever the schedule is started.
+    pub(crate) not_before: Option<DateTime>,
+    // Only read when the schedule is on Feb 29.
+    pub(crate) leap_day: LeapDay,
+}
+
+// Already validated pieces of a Schedule, assembled by the builder and turned
+// into a Schedule without going through the lenient setters.
+#[derive(Debug, Default)]
+pub(crate) struct ScheduleParts {
+    pub(crate) year: Option<i32>,
+    pub(crate) years: Vec<i32>,
+    pub(crate) day: Option<u8>,
+    pub(crate) month: Option<Month>,
+    pub(crate) month_step: Option<(u8, Month)>,
+    pub(crate) day_of_year: Option<u16>,
+    pub(crate) dates: Vec<(Month, u8)>,
+    pub(crate) fiscal: Option<FiscalPeriod>,
+    pub(crate) fiscal_year: FiscalYear,
+    pub(crate) business_days: Option<i8>,
+    pub(crate) holidays: Holidays,
+    #[cfg(feature = "panchang")]
+    pub(crate) lunar: Option<Lunar>,
+    pub(crate) hour: Option<u8>,
+    pub(crate) minute: Option<u8>,
+    pub(crate) hour_step: Option<u8>,
+    pub(crate) minute_step: Option<u8>,
+    #[cfg(feature = "astro")]
+    pub(crate) solar: Option<Solar>,
+    pub(crate) times: Vec<Time>,
+    pub(crate) interval: Option<u16>,
+    pub(crate) weekdays: Weekdays,
+    pub(crate) week_parity: Option<Parity>,
+    pub(crate) frequency: Option<FrequencyPattern>,
+    pub(crate) except: Option<Except>,
+    pub(crate) repeat: Repeat,
+    pub(crate) range: Option<(Time, Time)>,
+    pub(crate) offset: Option<u32>,
+    pub(crate) delay: Option<u32>,
+    pub(crate) immediate: bool,
+    pub(crate) stride: Option<u32>,
+    pub(crate) not_before: Option<DateTime>,
+    pub(crate) leap_day: LeapDay,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Schedule {
     pub fn new() -> Self {
+        Self::from_parts(ScheduleParts::default())
+    }
+
+    pub(crate) fn from_parts(parts: ScheduleParts) -> Self {
         Self {
             recurring: Recurring {
-                frequency: None,
-                except: None,
+                frequency: parts.frequency,
+                except: parts.except,
             },
-            year: None,
-            day: None,
-            month: None,
-            hour: None,
-            minute: None,
-            repeat: None,
-            range: None,
+            year: parts.year,
+            years: parts.years,
+            day: parts.day,
+            month: parts.month,
+            month_step: parts.month_step,
+            day_of_year: parts.day_of_year,
+            dates: parts.dates,
+            fiscal: parts.fiscal,
+            fiscal_year: parts.fiscal_year,
+            business_days: parts.business_days,
+            holidays: parts.holidays,
+            #[cfg(feature = "panchang")]
+            lunar: parts.lunar,
+            #[cfg(feature = "astro")]
+            solar: parts.solar,
+            hour: parts.hour,
+            minute: parts.minute,
+            hour_step: parts.hour_step,
+            minute_step: parts.minute_step,
+            times: parts.times,
+            interval: parts.interval,
+            weekdays: parts.weekdays,
+            week_parity: parts.week_parity,
+            repeat: parts.repeat,
+            range: parts.range,
+            offset: parts.offset,
+            delay: parts.delay,
+            immediate: parts.immediate,
+            stride: parts.stride,
+            not_before: parts.not_before,
+            leap_day: parts.leap_day,
         }
     }
 
-    pub fn year(mut self, year: u16) -> Self {
+    pub fn year(mut self, year: i32) -> Self {
+        if !self.years.is_empty() {
+            eprintln!("Years are already set. Ignoring {}", year);
+            return self;
+        }
+        if let (Some(m), Some(d)) = (self.month, self.day)
+            && is_leap_day_outside_leap_year(year, m as u8, d)
+        {
+            eprintln!("{} is not a leap year. Ignoring.", year);
+            return self;
+        }
+        if let Some(n) = self.day_of_year
+            && !is_valid_day_of_year(year, n)
+        {
+            eprintln!("{} has no day {}. Ignoring.", year, n);
+            return self;
+        }
         if self.year.is_none() {
             self.year = Some(year);
         } else {
@@ -129,12 +445,29 @@ impl Schedule {
     }
 
     pub fn day(mut self, d: u8) -> Self {
-        if d >= 1 && d <= 31 {
-            if let Some(m) = self.month {
-                if !is_valid_day_for_month(m as u8, d) {
-                    eprintln!("Invalid day {} for month {:?}.", d, m);
-                    return self;
-                }
+        if !self.dates.is_empty() {
+            eprintln!("Dates are already set. Ignoring day {}", d);
+            return self;
+        }
+        if self.day_of_year.is_some() || self.fiscal.is_some() {
+            eprintln!(
+                "Day of year or fiscal period is already set. Ignoring day {}",
+                d
+            );
+            return self;
+        }
+        if (1..=31).contains(&d) {
+            if let Some(m) = self.month
+                && !is_valid_day_for_month(m as u8, d)
+            {
+                eprintln!("Invalid day {} for month {:?}.", d, m);
+                return self;
+            }
+            if let (Some(y), Some(m)) = (self.year, self.month)
+                && is_leap_day_outside_leap_year(y, m as u8, d)
+            {
+                eprintln!("{} is not a leap year. Ignoring day {}.", y, d);
+                return self;
             }
             if self.day.is_none() {
                 self.day = Some(d);
@@ -148,12 +481,33 @@ impl Schedule {
     }
 
     pub fn month(mut self, m: u8) -> Self {
+        if !self.dates.is_empty() {
+            eprintln!("Dates are already set. Ignoring month {}", m);
+            return self;
+        }
+        if self.month_step.is_some() {
+            eprintln!("Month interval is already set. Ignoring month {}", m);
+            return self;
+        }
+        if self.day_of_year.is_some() || self.fiscal.is_some() {
+            eprintln!(
+                "Day of year or fiscal period is already set. Ignoring month {}",
+                m
+            );
+            return self;
+        }
         match Month::from_u8(m) {
             Some(month) => {
-                if let Some(d) = self.day {
-                    if !is_valid_day_for_month(m, d) {
-                        eprintln!("Invalid day {} for month {}.", d, m);
-                    }
+                if let (Some(y), Some(d)) = (self.year, self.day)
+                    && is_leap_day_outside_leap_year(y, month as u8, d)
+                {
+                    eprintln!("{} is not a leap year. Ignoring month {}.", y, m);
+                    return self;
+                }
+                if let Some(d) = self.day
+                    && !is_valid_day_for_month(m, d)
+                {
+                    eprintln!("Invalid day {} for month {}.", d, m);
                 }
                 self.month = Some(month);
             }
@@ -165,7 +519,11 @@ impl Schedule {
     }
 
     pub fn hour(mut self, h: u8) -> Self {
-        if self.hour.is_some() {
+        if !self.times.is_empty() || self.interval.is_some() {
+            eprintln!("Times of day are already set. Ignoring hour {}", h);
+            return self;
+        }
+        if self.hour.is_some() || self.hour_step.is_some() {
             eprintln!("Hour is already set. Ignoring {}", h);
             return self;
         }
@@ -178,7 +536,11 @@ impl Schedule {
     }
 
     pub fn minute(mut self, m: u8) -> Self {
-        if self.minute.is_some() {
+        if !self.times.is_empty() || self.interval.is_some() {
+            eprintln!("Times of day are already set. Ignoring minute {}", m);
+            return self;
+        }
+        if self.minute.is_some() || self.minute_step.is_some() {
             eprintln!("Minute is already set. Ignoring {}", m);
             return self;
         }
@@ -191,7 +553,9 @@ impl Schedule {
     }
 
     pub fn every(mut self, f: FrequencyPattern) -> Self {
-        if self.recurring.frequency.is_none() {
+        if f == FrequencyPattern::Frequency(Frequency::Hourly) && self.interval.is_some() {
+            eprintln!("Interval is already set. Ignoring hourly.");
+        } else if self.recurring.frequency.is_none() {
             self.recurring.frequency = Some(f);
         } else {
             eprintln!("Recurring frequency already set. Ignoring.");
@@ -208,21 +572,25 @@ impl Schedule {
         self
     }
 
-    pub fn repeat(mut self, n: u8) -> Self {
-        if self.repeat.is_none() {
-            self.repeat = Some(Until {
-                total: n,
-                day: None,
-                month: None,
-                hr: None,
-                minute: None,
-            });
+    // Keeps any end date; whichever of the two is reached first ends the
+    // schedule.
+    pub fn repeat(mut self, n: u32) -> Self {
+        if self.repeat.count().is_none() {
+            self.repeat = self.repeat.with_count(n);
         } else {
             eprintln!("Repeat count already set. Ignoring {}", n);
         }
         self
     }
 
+    // Stops the schedule after `date`, with or without a repeat count, e.g.
+    // "daily until 3 March". Replaces any end date already set.
+    pub fn ending_on(mut self, date: EndDate) -> Self {
+        self.repeat = self.repeat.or_until(date);
+        self
+    }
+
+    // Replaces any end date, keeping the count.
     pub fn until(
         mut self,
         d: Option<u8>,
@@ -230,62 +598,722 @@ impl Schedule {
         h: Option<u8>,
         min: Option<u8>,
     ) -> Self {
-        if self.repeat.is_none() {
-            eprintln!("repeat should be invoked before until, ignoring this");
+        self.repeat = self.repeat.or_until(EndDate {
+            year: None,
+            day: d,
+            month: m,
+            hour: h,
+            minute: min,
+        });
+        self
+    }
+
+    pub fn ending(mut self, repeat: Repeat) -> Self {
+        if self.repeat.is_forever() {
+            self.repeat = repeat;
         } else {
-            self.repeat = Some(Until {
-                total: self.repeat.unwrap().total,
-                day: d,
-                month: m,
-                hr: h,
-                minute: min,
-            })
+            eprintln!("Repeat already set. Ignoring {:?}", repeat);
         }
         self
     }
 
     pub fn between(mut self, start: (u8, u8), end: (u8, u8)) -> Self {
-        if self.range.is_none() {
-            self.range = Some((
-                Time {
-                    hour: start.0,
-                    minute: start.1,
-                },
-                Time {
-                    hour: end.0,
-                    minute: end.1,
-                },
-            ));
-        } else {
+        let window = Time::new(start.0, start.1).zip(Time::new(end.0, end.1));
+        if self.range.is_some() {
             eprintln!("Range already set. Ignoring new range.");
+        } else if window.is_none() {
+            eprintln!("Invalid range: {:?} - {:?}.", start, end);
+        } else {
+            self.range = window;
+        }
+        self
+    }
+
+    // `at` for a Time already validated.
+    pub fn at_time(self, t: Time) -> Self {
+        self.hour(t.hour).minute(t.minute)
+    }
+
+    // A one-shot on `date` at midnight, or at a time set with `at`.
+    pub fn on_date(self, date: Date) -> Self {
+        self.year(date.year).month(date.month).day(date.day)
+    }
+
+    // Shifts every occurrence by `d`, truncated to whole minutes.
+    pub fn offset_by(mut self, d: Duration) -> Self {
+        if self.offset.is_none() {
+            self.offset = Some((d.as_secs() / 60) as u32);
+        } else {
+            eprintln!("Offset already set. Ignoring {:?}", d);
+        }
+        self
+    }
+
+    // Fires once right at the starting instant as well, then follows the
+    // pattern. The extra run counts towards `repeat`.
+    pub fn and_run_immediately(mut self) -> Self {
+        self.immediate = true;
+        self
+    }
+
+    // Fires on the first match, then on every `n`th one after it, e.g. 2 for
+    // every other Monday. The matches in between are not counted towards
+    // `repeat`.
+    pub fn every_nth_occurrence(mut self, n: u32) -> Self {
+        if n == 0 {
+            eprintln!("Invalid stride: {}. Ignoring.", n);
+        } else if self.stride.is_none() {
+            self.stride = Some(n);
+        } else {
+            eprintln!("Stride already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // Holds occurrences back until `start`; repeat counts, strides and an
+    // immediate run all begin there. Kept with the schedule, so it applies
+    // wherever the schedule is registered.
+    pub fn starting_from(mut self, start: impl Into<DateTime>) -> Self {
+        let start = start.into();
+        if self.not_before.is_none() {
+            self.not_before = Some(start);
+        } else {
+            eprintln!("Start already set. Ignoring {}", start);
+        }
+        self
+    }
+
+    // Fires on the `n`th day of the year, 1 for Jan 1. From March on the
+    // same `n` lands a date later in leap years, and day 366 only comes in
+    // leap years. Takes the place of a day and month.
+    pub fn on_day_of_year(mut self, n: u16) -> Self {
+        if !(1..=366).contains(&n) {
+            eprintln!("Invalid day of year: {}. Must be 1–366.", n);
+        } else if self.day.is_some()
+            || self.month.is_some()
+            || self.month_step.is_some()
+            || self.fiscal.is_some()
+            || !self.dates.is_empty()
+        {
+            eprintln!("Day or month is already set. Ignoring day of year {}", n);
+        } else if let Some(y) = self.year
+            && !is_valid_day_of_year(y, n)
+        {
+            eprintln!("{} has no day {}. Ignoring.", y, n);
+        } else if self.day_of_year.is_none() {
+            self.day_of_year = Some(n);
+        } else {
+            eprintln!("Day of year is already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // Where quarters and years begin for the fiscal helpers below. January,
+    // the calendar year, unless set.
+    pub fn fiscal_year_starting(mut self, start: Month) -> Self {
+        self.fiscal_year = FiscalYear::starting(start);
+        self
+    }
+
+    pub fn on_fiscal_quarter_start(self) -> Self {
+        self.on_fiscal(FiscalPeriod::QuarterStart)
+    }
+
+    pub fn on_fiscal_quarter_end(self) -> Self {
+        self.on_fiscal(FiscalPeriod::QuarterEnd)
+    }
+
+    pub fn on_fiscal_year_start(self) -> Self {
+        self.on_fiscal(FiscalPeriod::YearStart)
+    }
+
+    pub fn on_fiscal_year_end(self) -> Self {
+        self.on_fiscal(FiscalPeriod::YearEnd)
+    }
+
+    // The first and last days of the calendar quarters: Jan 1, Apr 1, Jul 1
+    // and Oct 1, and Mar 31, Jun 30, Sep 30 and Dec 31.
+    pub fn on_quarter_start(self) -> Self {
+        self.fiscal_year_starting(Month::JAN)
+            .on_fiscal(FiscalPeriod::QuarterStart)
+    }
+
+    pub fn on_quarter_end(self) -> Self {
+        self.fiscal_year_starting(Month::JAN)
+            .on_fiscal(FiscalPeriod::QuarterEnd)
+    }
+
+    // Moves every matching day onto a business day (Monday to Friday) and
+    // then `n` more, e.g. `on_quarter_start().offset_business_days(2)` for
+    // the 3rd business day of each quarter. Days off roll forward, or back
+    // from the end of a quarter or year, so `on_quarter_end()` with 0 is
+    // its last business day and with -1 the one before.
+    pub fn offset_business_days(mut self, n: i8) -> Self {
+        if self.business_days.is_none() {
+            self.business_days = Some(n);
+        } else {
+            eprintln!("Business-day offset already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // Monthly on the 1st, or the first business day after it when the 1st
+    // is a weekend or one of the holidays set with `holiday_calendar`.
+    pub fn on_first_business_day(self) -> Self {
+        self.day(1)
+            .every(FrequencyPattern::Frequency(Frequency::Monthly))
+            .offset_business_days(0)
+    }
+
+    // Monthly on its last day, or the last business day before it, e.g. for
+    // payroll. Holidays set with `holiday_calendar` count as days off.
+    pub fn on_last_business_day(self) -> Self {
+        self.every_months(1).day(31).offset_business_days(0)
+    }
+
+    // Days that don't count as business days, on top of weekends. Replaces
+    // any set before.
+    pub fn holiday_calendar(mut self, holidays: Holidays) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    // Takes the place of a day and month, like `on_day_of_year`.
+    pub fn on_fiscal(mut self, period: FiscalPeriod) -> Self {
+        if self.day.is_some()
+            || self.month.is_some()
+            || self.month_step.is_some()
+            || self.day_of_year.is_some()
+            || !self.dates.is_empty()
+        {
+            eprintln!("Day or month is already set. Ignoring {:?}", period);
+        } else if self.fiscal.is_none() {
+            self.fiscal = Some(period);
+        } else {
+            eprintln!("Fiscal period is already set. Ignoring {:?}", period);
+        }
+        self
+    }
+
+    // Fires at a solar event each matching day, e.g.
+    // `Solar::sunset(home).before(30 min)`, instead of at an hour and minute.
+    // Days the shifted event falls outside of (polar day and night included)
+    // are skipped.
+    #[cfg(feature = "astro")]
+    pub fn at_solar(mut self, solar: Solar) -> Self {
+        if self.hour.is_some()
+            || self.minute.is_some()
+            || self.range.is_some()
+            || !self.times.is_empty()
+            || self.interval.is_some()
+            || self.hour_step.is_some()
+            || self.minute_step.is_some()
+        {
+            eprintln!("Time of day is already set. Ignoring {:?}", solar.event);
+        } else if self.solar.is_none() {
+            self.solar = Some(solar);
+        } else {
+            eprintln!("Solar time is already set. Ignoring {:?}", solar.event);
+        }
+        self
+    }
+
+    // Only fires in the listed years, e.g. for a campaign running through
+    // 2025 and 2026. Takes the place of a single year.
+    pub fn in_years(mut self, years: &[i32]) -> Self {
+        if years.is_empty() {
+            eprintln!("No years given. Ignoring.");
+        } else if self.year.is_some() || !self.years.is_empty() {
+            eprintln!("Year is already set. Ignoring {:?}", years);
+        } else {
+            let mut years = years.to_vec();
+            years.sort_unstable();
+            years.dedup();
+            self.years = years;
+        }
+        self
+    }
+
+    // Monthly, but only every `n`th month counting from `start`, e.g. every
+    // 3 months from February for Feb, May, Aug and Nov. `n` must divide 12
+    // so the months are the same every year. A day the month doesn't have
+    // fires on its last day instead, so the 31st lands on Apr 30.
+    pub fn every_months_from(mut self, n: u8, start: Month) -> Self {
+        let monthly = FrequencyPattern::Frequency(Frequency::Monthly);
+        if n == 0 || !12u8.is_multiple_of(n) {
+            eprintln!("Invalid month interval: {}. Must divide 12.", n);
+        } else if self.recurring.frequency.is_some_and(|f| f != monthly) {
+            eprintln!("Recurring frequency already set. Ignoring.");
+        } else if self.month.is_some()
+            || !self.dates.is_empty()
+            || self.day_of_year.is_some()
+            || self.fiscal.is_some()
+        {
+            eprintln!("Month is already set. Ignoring every {} months", n);
+        } else if self.month_step.is_none() {
+            self.month_step = Some((n, start));
+            self.recurring.frequency = Some(monthly);
+        } else {
+            eprintln!("Month interval is already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // Every year on each of `dates`, e.g. Jan 1, Jul 4 and Dec 25, given a
+    // frequency; a one-shot on the first of them to come without. Takes the
+    // place of a day and month. Feb 29 only comes in leap years.
+    pub fn on_dates(mut self, dates: &[(Month, u8)]) -> Self {
+        let invalid = dates
+            .iter()
+            .find(|(m, d)| *d == 0 || !is_valid_day_for_month(*m as u8, *d));
+        if let Some((m, d)) = invalid {
+            eprintln!("Invalid day {} for month {:?}. Ignoring dates.", d, m);
+        } else if dates.is_empty() {
+            eprintln!("No dates given. Ignoring.");
+        } else if self.day.is_some()
+            || self.month.is_some()
+            || self.month_step.is_some()
+            || self.day_of_year.is_some()
+            || self.fiscal.is_some()
+            || self.follows_moon()
+        {
+            eprintln!("Day or month is already set. Ignoring {:?}", dates);
+        } else if self.dates.is_empty() {
+            let mut dates = dates.to_vec();
+            dates.sort_by_key(|(m, d)| (*m as u8, *d));
+            dates.dedup();
+            self.dates = dates;
+        } else {
+            eprintln!("Dates are already set. Ignoring {:?}", dates);
+        }
+        self
+    }
+
+    // Weekly on each of `days` at each of `times`, e.g. Mondays and
+    // Wednesdays at 9:00 and 17:00 for four runs a week. Takes the place of
+    // an hour and minute.
+    pub fn on_days_at(mut self, days: &[Days], times: &[(u8, u8)]) -> Self {
+        let parsed: Option<Vec<Time>> = times.iter().map(|&(h, m)| Time::new(h, m)).collect();
+        let Some(mut parsed) = parsed.filter(|t| !t.is_empty()) else {
+            eprintln!("Invalid times of day: {:?}. Ignoring.", times);
+            return self;
+        };
+        if days.is_empty() {
+            eprintln!("No days given. Ignoring.");
+        } else if self
+            .recurring
+            .frequency
+            .is_some_and(|f| f != FrequencyPattern::Frequency(Frequency::Weekly))
+        {
+            eprintln!("Recurring frequency already set. Ignoring.");
+        } else if self.hour.is_some()
+            || self.minute.is_some()
+            || self.hour_step.is_some()
+            || self.minute_step.is_some()
+            || self.interval.is_some()
+            || self.follows_sun()
+        {
+            eprintln!("Time of day is already set. Ignoring {:?}", times);
+        } else if !self.times.is_empty() || !self.weekdays.is_empty() {
+            eprintln!("Days and times are already set. Ignoring.");
+        } else {
+            parsed.sort();
+            parsed.dedup();
+            self.times = parsed;
+            self.weekdays = Weekdays::new(days);
+            self.recurring.frequency = Some(FrequencyPattern::Frequency(Frequency::Weekly));
+        }
+        self
+    }
+
+    // Every `n` minutes through each matching day, counting from the start
+    // of a `between` window up to its end, or from midnight without one,
+    // e.g. every 30 minutes between 09:00 and 17:00 for 17 runs a day.
+    // Takes the place of an hour and minute. Days follow the frequency, and
+    // without one it fires every day.
+    pub fn every_minutes(mut self, n: u16) -> Self {
+        let hourly = FrequencyPattern::Frequency(Frequency::Hourly);
+        if n == 0 || n >= 24 * 60 {
+            eprintln!("Invalid interval: {} minutes. Must be 1–1439.", n);
+        } else if self.recurring.frequency == Some(hourly) {
+            eprintln!("Recurring frequency already set. Ignoring.");
+        } else if self.hour.is_some()
+            || self.minute.is_some()
+            || self.hour_step.is_some()
+            || self.minute_step.is_some()
+            || !self.times.is_empty()
+            || self.follows_sun()
+        {
+            eprintln!("Time of day is already set. Ignoring every {} minutes", n);
+        } else if self.interval.is_none() {
+            self.interval = Some(n);
+        } else {
+            eprintln!("Interval is already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // At minutes 0, n, 2n, ... past each hour it fires in, like `*/n` in
+    // cron's minute field: every hour unless an hour or hour step narrows it.
+    // In place of a minute; days follow the frequency, every day without one.
+    pub fn minutes_step(mut self, n: u8) -> Self {
+        if n == 0 || n >= 60 {
+            eprintln!("Invalid minute step: {}. Must be 1–59.", n);
+        } else if self.minute.is_some()
+            || !self.times.is_empty()
+            || self.interval.is_some()
+            || self.follows_sun()
+        {
+            eprintln!("Minute is already set. Ignoring step {}", n);
+        } else if self.minute_step.is_none() {
+            self.minute_step = Some(n);
+        } else {
+            eprintln!("Minute step is already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // At hours 0, n, 2n, ... of each day it fires on, like `*/n` in cron's
+    // hour field. In place of an hour; days follow the frequency, every day
+    // without one.
+    pub fn hours_step(mut self, n: u8) -> Self {
+        if n == 0 || n >= 24 {
+            eprintln!("Invalid hour step: {}. Must be 1–23.", n);
+        } else if self.hour.is_some()
+            || !self.times.is_empty()
+            || self.interval.is_some()
+            || self.follows_sun()
+        {
+            eprintln!("Hour is already set. Ignoring step {}", n);
+        } else if self.hour_step.is_none() {
+            self.hour_step = Some(n);
+        } else {
+            eprintln!("Hour step is already set. Ignoring {}", n);
+        }
+        self
+    }
+
+    // Every other week, going by ISO week numbers rather than by when the
+    // schedule started, e.g. `every_on_day(Days::TUE)` with `Parity::Even`
+    // for a pickup on Tuesdays of even weeks. Two instances set up apart
+    // still agree on which weeks are on.
+    pub fn on_alternate_weeks(mut self, parity: Parity) -> Self {
+        if self.week_parity.is_none() {
+            self.week_parity = Some(parity);
+        } else {
+            eprintln!("Week parity already set. Ignoring {:?}", parity);
+        }
+        self
+    }
+
+    // The frequency, or daily for a schedule without one whose times of day
+    // repeat through the day, as with `every_minutes` or the steps.
+    pub(crate) fn frequency_or_daily(&self) -> Option<FrequencyPattern> {
+        let repeats =
+            self.interval.is_some() || self.hour_step.is_some() || self.minute_step.is_some();
+        self.recurring
+            .frequency
+            .or(repeats.then_some(FrequencyPattern::Frequency(Frequency::Daily)))
+    }
+
+    // Whether the time of day moves with the sun; always false without the
+    // astro feature.
+    pub(crate) fn follows_sun(&self) -> bool {
+        #[cfg(feature = "astro")]
+        return self.solar.is_some();
+        #[cfg(not(feature = "astro"))]
+        false
+    }
+
+    // Fires on the days observed as a lunar event at a location, e.g.
+    // `Lunar::ekadashi(home)`. Takes the place of a day of the month; a
+    // month still narrows it.
+    #[cfg(feature = "panchang")]
+    pub fn on_lunar(mut self, lunar: Lunar) -> Self {
+        if self.day.is_some()
+            || self.day_of_year.is_some()
+            || self.fiscal.is_some()
+            || !self.dates.is_empty()
+        {
+            eprintln!("Day is already set. Ignoring {:?}", lunar.event);
+        } else if self.lunar.is_none() {
+            self.lunar = Some(lunar);
+        } else {
+            eprintln!("Lunar day is already set. Ignoring {:?}", lunar.event);
         }
         self
     }
+
+    // Whether the days it fires on follow the moon; always false without
+    // the panchang feature.
+    pub(crate) fn follows_moon(&self) -> bool {
+        #[cfg(feature = "panchang")]
+        return self.lunar.is_some();
+        #[cfg(not(feature = "panchang"))]
+        false
+    }
+
+    // Whether something other than the frequency picks the day: a day of the
+    // month or year, dates, a fiscal period or a lunar day.
+    pub(crate) fn pins_day(&self) -> bool {
+        self.day.is_some()
+            || !self.dates.is_empty()
+            || self.day_of_year.is_some()
+            || self.fiscal.is_some()
+            || self.follows_moon()
+    }
+
+    // Whether an `every_months` schedule fires in `month` (1 to 12); always
+    // true without one.
+    pub(crate) fn in_month_step(&self, month: u8) -> bool {
+        self.month_step
+            .is_none_or(|(n, start)| (month + 11 - start as u8).is_multiple_of(n))
+    }
+
+    // For a schedule on Feb 29: where it fires in the other three years.
+    // The default skips them.
+    pub fn on_leap_day(mut self, policy: LeapDay) -> Self {
+        self.leap_day = policy;
+        self
+    }
+
+    // The policy in effect, if the schedule is on Feb 29 and doesn't skip.
+    pub(crate) fn leap_day_policy(&self) -> Option<LeapDay> {
+        (self.month == Some(Month::FEB) && self.day == Some(29) && self.leap_day != LeapDay::Skip)
+            .then_some(self.leap_day)
+    }
 }
 
+// Non-destructive variants of the setters above: each returns a copy with one
+// value replaced. A value the setter rejects leaves the copy unchanged.
+impl Schedule {
+    pub fn with_year(&self, year: i32) -> Schedule {
+        let s = Schedule {
+            year: None,
+            ..self.clone()
+        }
+        .year(year);
+        if s.year.is_some() { s } else { self.clone() }
+    }
+
+    pub fn with_month(&self, m: u8) -> Schedule {
+        let s = Schedule {
+            month: None,
+            ..self.clone()
+        }
+        .month(m);
+        if s.month.is_some() { s } else { self.clone() }
+    }
+
+    pub fn with_day(&self, d: u8) -> Schedule {
+        let s = Schedule {
+            day: None,
+            ..self.clone()
+        }
+        .day(d);
+        if s.day.is_some() { s } else { self.clone() }
+    }
+
+    pub fn with_hour(&self, h: u8) -> Schedule {
+        let s = Schedule {
+            hour: None,
+            ..self.clone()
+        }
+        .hour(h);
+        if s.hour.is_some() { s } else { self.clone() }
+    }
+
+    pub fn with_minute(&self, m: u8) -> Schedule {
+        let s = Schedule {
+            minute: None,
+            ..self.clone()
+        }
+        .minute(m);
+        if s.minute.is_some() { s } else { self.clone() }
+    }
+
+    pub fn with_time(&self, h: u8, m: u8) -> Schedule {
+        self.with_hour(h).with_minute(m)
+    }
+
+    pub fn with_frequency(&self, f: FrequencyPattern) -> Schedule {
+        let mut s = self.clone();
+        s.recurring.frequency = Some(f);
+        s
+    }
+
+    pub fn with_except(&self, e: Except) -> Schedule {
+        let mut s = self.clone();
+        s.recurring.except = Some(e);
+        s
+    }
+
+    // Keeps any until bound already set.
+    pub fn with_repeat(&self, n: u32) -> Schedule {
+        Schedule {
+            repeat: self.repeat.with_count(n),
+            ..self.clone()
+        }
+    }
+
+    pub fn with_range(&self, start: (u8, u8), end: (u8, u8)) -> Schedule {
+        let s = Schedule {
+            range: None,
+            ..self.clone()
+        }
+        .between(start, end);
+        if s.range.is_some() { s } else { self.clone() }
+    }
+
+    pub fn with_offset(&self, d: Duration) -> Schedule {
+        Schedule {
+            offset: None,
+            ..self.clone()
+        }
+        .offset_by(d)
+    }
+}
+
+// Read-only snapshot of everything configured on a Schedule.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScheduleSpec {
+    pub year: Option<i32>,
+    pub month: Option<Month>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub frequency: Option<FrequencyPattern>,
+    pub except: Option<Except>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub repeat: Repeat,
+    pub range: Option<(Time, Time)>,
+    pub offset: Option<u32>,
+    pub delay: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub run_immediately: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stride: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub starting_from: Option<DateTime>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub leap_day: LeapDay,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub day_of_year: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fiscal: Option<FiscalPeriod>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fiscal_year: FiscalYear,
+    #[cfg(feature = "astro")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub solar: Option<Solar>,
+    #[cfg(feature = "panchang")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lunar: Option<Lunar>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub times: Vec<Time>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub weekdays: Weekdays,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dates: Vec<(Month, u8)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub years: Vec<i32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub every_months: Option<(u8, Month)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub business_days: Option<i8>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub holidays: Holidays,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub week_parity: Option<Parity>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub every_minutes: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hours_step: Option<u8>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub minutes_step: Option<u8>,
+}
+
+// Getters. day/hour/month/minute/year/except/repeat share their names with
+// the builder-style setters, so those are read through `spec()`.
+impl Schedule {
+    pub fn spec(&self) -> ScheduleSpec {
+        ScheduleSpec {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+            hour: self.hour,
+            minute: self.minute,
+            frequency: self.recurring.frequency,
+            except: self.recurring.except,
+            repeat: self.repeat,
+            range: self.range,
+            offset: self.offset,
+            delay: self.delay,
+            run_immediately: self.immediate,
+            stride: self.stride,
+            starting_from: self.not_before,
+            leap_day: self.leap_day,
+            day_of_year: self.day_of_year,
+            fiscal: self.fiscal,
+            fiscal_year: self.fiscal_year,
+            #[cfg(feature = "astro")]
+            solar: self.solar,
+            #[cfg(feature = "panchang")]
+            lunar: self.lunar,
+            times: self.times.clone(),
+            weekdays: self.weekdays,
+            dates: self.dates.clone(),
+            years: self.years.clone(),
+            every_months: self.month_step,
+            business_days: self.business_days,
+            holidays: self.holidays.clone(),
+            week_parity: self.week_parity,
+            every_minutes: self.interval,
+            hours_step: self.hour_step,
+            minutes_step: self.minute_step,
+        }
+    }
+
+    pub fn frequency(&self) -> Option<FrequencyPattern> {
+        self.recurring.frequency
+    }
+
+    pub fn range(&self) -> Option<(Time, Time)> {
+        self.range
+    }
+}
+
+#[deprecated(note = "use `schedule.spec().day`")]
 pub fn get_day(sc: &Schedule) -> Option<u8> {
     sc.day
 }
+#[deprecated(note = "use `schedule.spec().hour`")]
 pub fn get_hour(sc: &Schedule) -> Option<u8> {
     sc.hour
 }
+#[deprecated(note = "use `schedule.spec().month`")]
 pub fn get_month(sc: &Schedule) -> Option<Month> {
     sc.month
 }
 
+#[deprecated(note = "use `schedule.frequency()`")]
 pub fn get_frequency(sc: &Schedule) -> Option<FrequencyPattern> {
-    (&sc.recurring).frequency
+    sc.recurring.frequency
 }
 
+#[deprecated(note = "use `schedule.spec().except`")]
 pub fn get_except(sc: &Schedule) -> Option<Except> {
-    (&sc.recurring).except
+    sc.recurring.except
 }
 
+#[deprecated(note = "use `schedule.range()`")]
 pub fn get_range(sc: &Schedule) -> Option<(Time, Time)> {
     sc.range
 }
 
-pub fn get_repeat(sc: &Schedule) -> Option<Until> {
+#[deprecated(note = "use `schedule.spec().repeat`")]
+pub fn get_repeat(sc: &Schedule) -> Repeat {
     sc.repeat
 }
 
@@ -298,6 +1326,173 @@ mod tests {
         assert_eq!(s.day, Some(2));
     }
 
+    #[test]
+    fn days_and_times_set_together() {
+        let s = Schedule::new().on_days_at(&[Days::MON, Days::WED], &[(9, 0), (17, 0)]);
+        assert_eq!(
+            s.weekdays.iter().collect::<Vec<_>>(),
+            [Days::MON, Days::WED]
+        );
+        assert_eq!(s.times.len(), 2);
+        assert_eq!(s.hour(10).spec().hour, None);
+
+        let ignored = [
+            Schedule::new().on_days_at(&[Days::MON], &[(24, 0)]),
+            Schedule::new().on_days_at(&[Days::MON], &[]),
+            Schedule::new().on_days_at(&[], &[(9, 0)]),
+            Schedule::new().daily().on_days_at(&[Days::MON], &[(9, 0)]),
+        ];
+        for s in ignored {
+            assert!(s.times.is_empty() && s.weekdays.is_empty());
+        }
+        let s = Schedule::new().on_days_at(&[Days::SUN], &[(23, 59), (0, 0), (23, 59)]);
+        assert_eq!(
+            s.times,
+            [Time::new(0, 0).unwrap(), Time::new(23, 59).unwrap()]
+        );
+    }
+
+    #[test]
+    fn years_set() {
+        let s = Schedule::new().daily().in_years(&[2026, 2025, 2026]);
+        assert_eq!(s.years, [2025, 2026]);
+        assert_eq!(s.year(2027).spec().year, None);
+        assert!(
+            Schedule::new()
+                .year(2025)
+                .in_years(&[2026])
+                .years
+                .is_empty()
+        );
+        assert!(Schedule::new().in_years(&[]).years.is_empty());
+    }
+
+    #[test]
+    fn interval_set() {
+        let s = Schedule::new().every_minutes(30).between((9, 0), (17, 0));
+        assert_eq!(s.interval, Some(30));
+        assert_eq!(s.frequency(), None);
+        assert_eq!(
+            s.frequency_or_daily(),
+            Some(FrequencyPattern::Frequency(Frequency::Daily))
+        );
+        assert_eq!(
+            s.clone().weekly().frequency(),
+            Some(FrequencyPattern::Frequency(Frequency::Weekly))
+        );
+        assert_eq!(s.clone().hourly().frequency(), None);
+        assert_eq!(s.clone().at(9, 0).spec().hour, None);
+        assert_eq!(
+            Schedule::new()
+                .every_on_day(Days::MON)
+                .every_minutes(90)
+                .frequency(),
+            Some(FrequencyPattern::ByDay((None, Days::MON)))
+        );
+
+        let ignored = [
+            Schedule::new().every_minutes(0),
+            Schedule::new().every_minutes(1440),
+            Schedule::new().hourly().every_minutes(15),
+            Schedule::new().at(9, 0).every_minutes(15),
+            Schedule::new().every_minutes(20).every_minutes(15),
+        ];
+        for (s, kept) in ignored.iter().zip([None, None, None, None, Some(20)]) {
+            assert_eq!(s.interval, kept);
+        }
+    }
+
+    #[test]
+    fn steps_set() {
+        let s = Schedule::new().minutes_step(5);
+        assert_eq!(s.minute_step, Some(5));
+        assert_eq!(s.frequency(), None);
+        assert_eq!(
+            s.clone().daily().frequency(),
+            Some(FrequencyPattern::Frequency(Frequency::Daily))
+        );
+        assert_eq!(s.clone().minute(3).spec().minute, None);
+        let s = s.hours_step(2);
+        assert_eq!(s.hour_step, Some(2));
+        assert_eq!(s.hour(3).spec().hour, None);
+        assert_eq!(
+            Schedule::new()
+                .hours_step(6)
+                .every_on_day(Days::MON)
+                .frequency(),
+            Some(FrequencyPattern::ByDay((None, Days::MON)))
+        );
+
+        let ignored = [
+            Schedule::new().minutes_step(0),
+            Schedule::new().minutes_step(60),
+            Schedule::new().minute(0).minutes_step(5),
+            Schedule::new().every_minutes(30).minutes_step(5),
+            Schedule::new().minutes_step(10).minutes_step(5),
+        ];
+        for (s, kept) in ignored.iter().zip([None, None, None, None, Some(10)]) {
+            assert_eq!(s.minute_step, kept);
+        }
+        let ignored = [
+            Schedule::new().hours_step(24),
+            Schedule::new().at(9, 0).hours_step(2),
+            Schedule::new().hours_step(3).hours_step(2),
+        ];
+        for (s, kept) in ignored.iter().zip([None, None, Some(3)]) {
+            assert_eq!(s.hour_step, kept);
+        }
+    }
+
+    #[test]
+    fn month_step_set() {
+        let s = Schedule::new().every_months(3).on_day(15);
+        assert_eq!(s.month_step, Some((3, Month::JAN)));
+        assert_eq!(
+            s.frequency(),
+            Some(FrequencyPattern::Frequency(Frequency::Monthly))
+        );
+        assert_eq!(s.month(3).spec().month, None);
+        assert_eq!(
+            Schedule::new()
+                .monthly()
+                .every_months_from(6, Month::APR)
+                .month_step,
+            Some((6, Month::APR))
+        );
+
+        let ignored = [
+            Schedule::new().every_months(5),
+            Schedule::new().every_months(0),
+            Schedule::new().daily().every_months(3),
+            Schedule::new().month(2).every_months(3),
+            Schedule::new().every_months(2).every_months(3),
+        ];
+        for (s, kept) in ignored.iter().zip([None, None, None, None, Some(2)]) {
+            assert_eq!(s.month_step.map(|(n, _)| n), kept);
+        }
+    }
+
+    #[test]
+    fn dates_set() {
+        let s = Schedule::new().on_dates(&[(Month::DEC, 25), (Month::JAN, 1), (Month::DEC, 25)]);
+        assert_eq!(s.dates, [(Month::JAN, 1), (Month::DEC, 25)]);
+        assert_eq!(s.clone().day(3).spec().day, None);
+        assert_eq!(s.month(3).spec().month, None);
+
+        let ignored = [
+            Schedule::new().on_dates(&[(Month::JAN, 1), (Month::APR, 31)]),
+            Schedule::new().on_dates(&[(Month::JAN, 0)]),
+            Schedule::new().on_dates(&[]),
+            Schedule::new().day(4).on_dates(&[(Month::JUL, 4)]),
+            Schedule::new()
+                .on_day_of_year(1)
+                .on_dates(&[(Month::JUL, 4)]),
+        ];
+        for s in ignored {
+            assert!(s.dates.is_empty());
+        }
+    }
+
     #[test]
     fn day_not_set() {
         let s = Schedule::new().day(32);
@@ -422,13 +1617,13 @@ mod tests {
     #[test]
     fn repeat_set() {
         let s = Schedule::new().repeat(10);
-        assert_eq!(s.repeat.unwrap().total, 10);
+        assert_eq!(s.repeat, Repeat::Count(10));
     }
 
     #[test]
     fn repeat_ignored_on_second_call() {
         let s = Schedule::new().repeat(10).repeat(20);
-        assert_eq!(s.repeat.unwrap().total, 10);
+        assert_eq!(s.repeat.count(), Some(10));
     }
 
     #[test]
@@ -437,20 +1632,43 @@ mod tests {
             .repeat(5)
             .until(Some(3), Some(Month::MAR), Some(10), Some(30));
 
-        let repeat = s.repeat.unwrap();
-        assert_eq!(repeat.total, 5);
-        assert_eq!(repeat.day, Some(3));
-        assert_eq!(repeat.month, Some(Month::MAR));
-        assert_eq!(repeat.hr, Some(10));
-        assert_eq!(repeat.minute, Some(30));
+        assert_eq!(
+            s.repeat,
+            Repeat::CountOrUntil {
+                count: 5,
+                until: EndDate::on(3, Month::MAR).at(10, 30),
+            }
+        );
+    }
+
+    #[test]
+    fn repeat_after_until_keeps_both() {
+        let s = Schedule::new()
+            .until(Some(3), Some(Month::MAR), None, None)
+            .repeat(5);
+        assert_eq!(s, Schedule::new().repeat_until_date(5, 3, Month::MAR));
     }
 
     #[test]
-    fn until_without_repeat_is_ignored() {
+    fn until_without_repeat_ends_on_the_date() {
         let s = Schedule::new().until(Some(5), Some(Month::JAN), Some(8), Some(45));
 
-        // until should not be set because repeat was not set
-        assert!(s.repeat.is_none());
+        assert_eq!(
+            s.repeat,
+            Repeat::until(EndDate::on(5, Month::JAN).at(8, 45))
+        );
+        assert_eq!(s.repeat.count(), None);
+    }
+
+    #[test]
+    fn date_and_time_values() {
+        let s = Schedule::new()
+            .on_date(Date::new(2026, 3, 1).unwrap())
+            .at_time(Time::new(9, 30).unwrap());
+        assert_eq!(s, Schedule::once(2026, 3, 1, 9, 30).unwrap());
+
+        let s = Schedule::new().daily().between((9, 0), (24, 0));
+        assert_eq!(s.range, None);
     }
 
     #[test]
@@ -484,4 +1702,134 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn with_replaces_on_a_copy() {
+        let base = Schedule::new().hour(9).minute(0).day(1);
+        let evening = base.with_hour(18);
+
+        assert_eq!(base.hour, Some(9));
+        assert_eq!(evening.hour, Some(18));
+        assert_eq!(evening.day, Some(1));
+    }
+
+    #[test]
+    fn with_keeps_value_on_invalid_input() {
+        let base = Schedule::new().hour(9);
+        assert_eq!(base.with_hour(24).hour, Some(9));
+        let leap = Schedule::new().year(2028).date(2, 29);
+        assert_eq!(leap.with_year(2027).year, Some(2028));
+        let window = base.between((9, 0), (17, 0));
+        assert_eq!(window.with_range((9, 0), (25, 0)), window);
+    }
+
+    #[test]
+    fn day_of_year_replaces_day_and_month() {
+        let s = Schedule::new().on_day_of_year(100).daily();
+        assert_eq!(s.spec().day_of_year, Some(100));
+        assert_eq!(s.clone().day(3).spec().day, None);
+        assert_eq!(s.month(3).spec().month, None);
+        assert_eq!(
+            Schedule::new()
+                .date(1, 2)
+                .on_day_of_year(100)
+                .spec()
+                .day_of_year,
+            None
+        );
+        assert_eq!(Schedule::new().on_day_of_year(367).spec().day_of_year, None);
+        assert_eq!(
+            Schedule::new()
+                .year(2025)
+                .on_day_of_year(366)
+                .spec()
+                .day_of_year,
+            None
+        );
+        assert_eq!(
+            Schedule::new().on_day_of_year(366).year(2025).spec().year,
+            None
+        );
+    }
+
+    #[test]
+    fn fiscal_period_replaces_day_and_month() {
+        let s = Schedule::new()
+            .on_fiscal_quarter_start()
+            .fiscal_year_starting(Month::APR);
+        assert_eq!(s.spec().fiscal, Some(FiscalPeriod::QuarterStart));
+        assert_eq!(s.spec().fiscal_year.start, Month::APR);
+        assert_eq!(s.clone().day(1).spec().day, None);
+        assert_eq!(
+            s.on_fiscal_year_end().spec().fiscal,
+            Some(FiscalPeriod::QuarterStart)
+        );
+        assert_eq!(
+            Schedule::new()
+                .on_day(1)
+                .on_fiscal_year_start()
+                .spec()
+                .fiscal,
+            None
+        );
+    }
+
+    #[test]
+    fn feb_29_needs_a_leap_year() {
+        assert_eq!(Schedule::new().year(2027).date(2, 29).spec().day, None);
+        assert_eq!(Schedule::new().date(2, 29).year(2027).spec().year, None);
+        assert_eq!(
+            Schedule::new().year(2027).day(29).month(2).spec().month,
+            None
+        );
+        let s = Schedule::new().year(2028).date(2, 29).spec();
+        assert_eq!((s.year, s.day), (Some(2028), Some(29)));
+    }
+
+    #[test]
+    fn with_except_overwrites() {
+        let base = Schedule::new().except(Except::Day(Days::MON));
+        let s = base.with_except(Except::Month(Month::DEC));
+        assert_eq!(s.recurring.except, Some(Except::Month(Month::DEC)));
+        assert_eq!(base.recurring.except, Some(Except::Day(Days::MON)));
+    }
+
+    #[test]
+    fn spec_reflects_configuration() {
+        let s = Schedule::new()
+            .date(9, 20)
+            .hour(22)
+            .weekly()
+            .between((9, 0), (10, 0));
+        let spec = s.spec();
+
+        assert_eq!(spec.month, Some(Month::SEP));
+        assert_eq!(spec.day, Some(20));
+        assert_eq!(spec.hour, Some(22));
+        assert_eq!(spec.minute, None);
+        assert_eq!(spec.frequency, s.frequency());
+        assert_eq!(spec.range, s.range());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_getters_still_work() {
+        let s = Schedule::new().day(3).hour(4).daily();
+        assert_eq!(get_day(&s), s.spec().day);
+        assert_eq!(get_hour(&s), s.spec().hour);
+        assert_eq!(get_frequency(&s), s.frequency());
+    }
+
+    #[test]
+    fn equal_schedules_hash_together() {
+        use std::collections::HashSet;
+
+        let a = Schedule::new().hour(9).minute(0).daily();
+        let b = Schedule::new().daily().minute(0).hour(9);
+        assert_eq!(a, b);
+        assert_ne!(a, a.with_hour(10));
+
+        let set: HashSet<Schedule> = [a.clone(), b, a.with_hour(10)].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
 }